@@ -0,0 +1,97 @@
+// Adaptive bot - a predictive bot whose accuracy follows the score
+
+use super::predictive_bot::PredictiveBot;
+use super::Bot;
+use crate::game::{GameState, InputAction, Player};
+
+/// Accuracy at level 0 (about Easy) and level 1 (sharper than Hard)
+const LOOSEST: (f32, f32) = (40.0, 0.15); // (error_stddev, catastrophic_miss_rate)
+const SHARPEST: (f32, f32) = (10.0, 0.02);
+
+/// Level change per point of score difference (bot ahead = lower)
+const LEVEL_PER_POINT: f32 = 0.15;
+
+/// Fastest the level moves toward its target, per second of play
+const LEVEL_RATE: f32 = 0.05;
+
+/// Predictive bot that eases off while the player is losing and tightens up while
+/// they're winning. Its level drifts slowly so the change is never abrupt.
+pub struct AdaptiveBot {
+    inner: PredictiveBot,
+    side: Player,
+    level: f32, // 0 = loosest, 1 = sharpest
+}
+
+impl AdaptiveBot {
+    /// Create an AdaptiveBot controlling the paddle on `side`, starting in the middle
+    pub fn new(side: Player) -> Self {
+        let mut bot = Self {
+            inner: PredictiveBot::hard(side),
+            side,
+            level: 0.5,
+        };
+        bot.apply_level();
+        bot
+    }
+
+    /// Level the score calls for: even scores sit in the middle
+    fn target_level(&self, game_state: &GameState) -> f32 {
+        let (own, opponent) = match self.side {
+            Player::Left => (game_state.left_score, game_state.right_score),
+            Player::Right => (game_state.right_score, game_state.left_score),
+        };
+        let lead = f32::from(own) - f32::from(opponent);
+        (0.5 - lead * LEVEL_PER_POINT).clamp(0.0, 1.0)
+    }
+
+    fn apply_level(&mut self) {
+        let lerp = |loose: f32, sharp: f32| loose + (sharp - loose) * self.level;
+        self.inner
+            .set_accuracy(lerp(LOOSEST.0, SHARPEST.0), lerp(LOOSEST.1, SHARPEST.1));
+    }
+}
+
+impl Bot for AdaptiveBot {
+    fn get_action(&mut self, game_state: &GameState, dt: f32) -> Option<InputAction> {
+        let target = self.target_level(game_state);
+        let step = LEVEL_RATE * dt;
+        self.level += (target - self.level).clamp(-step, step);
+        self.apply_level();
+
+        self.inner.get_action(game_state, dt)
+    }
+
+    fn reset(&mut self) {
+        // The level carries over: it tracks the player across games
+        self.inner.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PhysicsConfig;
+
+    #[test]
+    fn test_level_follows_score_gradually() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        let mut bot = AdaptiveBot::new(Player::Right);
+
+        // Bot well ahead: eases off, but only a little per frame
+        state.right_score = 4;
+        bot.get_action(&state, 1.0);
+        assert!((bot.level - 0.45).abs() < 1e-4);
+        for _ in 0..20 {
+            bot.get_action(&state, 1.0);
+        }
+        assert_eq!(bot.level, 0.0);
+
+        // Player ahead: tightens up toward the top level
+        state.right_score = 0;
+        state.left_score = 4;
+        for _ in 0..30 {
+            bot.get_action(&state, 1.0);
+        }
+        assert_eq!(bot.level, 1.0);
+    }
+}
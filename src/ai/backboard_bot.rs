@@ -1,38 +1,65 @@
 // Backboard bot - instant tracker for training mode
 
+use super::bot::{move_action, paddle_center_y};
 use super::Bot;
-use crate::game::{GameState, InputAction};
+use crate::game::{GameState, InputAction, Player};
+use std::time::{Duration, Instant};
 
-/// A simple training bot that tracks the ball's Y position instantly
+/// A simple training bot that tracks the ball's Y position
 ///
 /// This bot is designed as a "backboard" or training mode:
-/// - Instantly tracks ball when it's coming toward the bot
+/// - Tracks ball when it's coming toward the bot
 /// - Returns to center when ball is moving away
 /// - No prediction errors or delays (perfect tracking)
+/// - Moves no faster than its speed cap, so a fast enough ball beats it
 /// - Good for beginners learning controls
 pub struct BackboardBot {
     movement_threshold: f32, // How far from target before moving
+    side: Player,            // Paddle this bot controls
+    max_paddle_speed: f32,   // Virtual units per second (0 = instant)
+    last_move: Option<Instant>,
 }
 
 impl BackboardBot {
-    /// Create a new BackboardBot
-    pub fn new() -> Self {
+    /// Create a new BackboardBot controlling the paddle on `side`, moving at most
+    /// `max_paddle_speed` virtual units per second (0 = as fast as it is asked)
+    pub fn new(side: Player, max_paddle_speed: f32) -> Self {
         Self {
             movement_threshold: 30.0, // Threshold for smooth movement
+            side,
+            max_paddle_speed,
+            last_move: None,
         }
     }
+
+    /// Whether a tap now would keep the paddle within its speed cap
+    fn can_move(&self, tap_distance: f32) -> bool {
+        if self.max_paddle_speed <= 0.0 {
+            return true;
+        }
+        // An interval too long to represent is as good as standing still
+        let Ok(interval) = Duration::try_from_secs_f32(tap_distance / self.max_paddle_speed) else {
+            return self.last_move.is_none();
+        };
+        self.last_move.is_none_or(|at| at.elapsed() >= interval)
+    }
 }
 
 impl Bot for BackboardBot {
     fn get_action(&mut self, game_state: &GameState, _dt: f32) -> Option<InputAction> {
-        // Right paddle (AI side)
-        let paddle_center_y = game_state.right_paddle.y + (game_state.right_paddle.height / 2.0);
+        let paddle_center_y = paddle_center_y(game_state, self.side);
         let field_center_y = game_state.field_height / 2.0;
 
+        let ball = game_state.ball_toward(self.side);
+        let approaching = match self.side {
+            Player::Left => ball.vx < 0.0,
+            Player::Right => ball.vx > 0.0,
+        };
+
         // Determine target position based on ball direction
-        let target_y = if game_state.ball.vx > 0.0 {
+        let target_y = if approaching {
             // Ball is moving toward bot - track ball position
-            game_state.ball.y
+            ball.y
         } else {
             // Ball is moving away - stay near center
             field_center_y
@@ -40,17 +67,68 @@ impl Bot for BackboardBot {
 
         let diff = target_y - paddle_center_y;
 
-        // Only move if significantly away from target
-        if diff.abs() < self.movement_threshold {
-            None // Close enough, don't move
-        } else if diff > 0.0 {
-            Some(InputAction::RightPaddleDown) // Target below, move down
+        // Only move if significantly away from target, and not faster than the cap
+        if diff.abs() < self.movement_threshold || !self.can_move(game_state.tap_distance) {
+            None // Close enough (or already moving flat out), don't move
         } else {
-            Some(InputAction::RightPaddleUp) // Target above, move up
+            self.last_move = Some(Instant::now());
+            Some(move_action(self.side, diff > 0.0)) // Move toward target
         }
     }
 
     fn reset(&mut self) {
-        // Simple tracker has no state to reset
+        self.last_move = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PhysicsConfig;
+
+    #[test]
+    fn test_tracks_ball_on_either_side() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        state.ball.y = state.field_height - 50.0;
+
+        // Ball heading left: left bot chases it, right bot stays put at center
+        state.ball.vx = -300.0;
+        let mut left = BackboardBot::new(Player::Left, 0.0);
+        let mut right = BackboardBot::new(Player::Right, 0.0);
+        assert_eq!(
+            left.get_action(&state, 0.0),
+            Some(InputAction::LeftPaddleDown)
+        );
+        assert_eq!(right.get_action(&state, 0.0), None);
+
+        // Ball heading right: roles swap
+        state.ball.vx = 300.0;
+        assert_eq!(left.get_action(&state, 0.0), None);
+        assert_eq!(
+            right.get_action(&state, 0.0),
+            Some(InputAction::RightPaddleDown)
+        );
+    }
+
+    #[test]
+    fn test_speed_cap_spaces_out_moves() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        state.ball.y = state.field_height - 50.0;
+        state.ball.vx = 300.0;
+
+        // A tap every 40 / 10 = 4 seconds: one move now, then it has to wait
+        let mut capped = BackboardBot::new(Player::Right, 10.0);
+        assert!(capped.get_action(&state, 0.0).is_some());
+        assert_eq!(capped.get_action(&state, 0.0), None);
+
+        // A cap too slow for a Duration doesn't panic; the paddle just stays put
+        let mut stuck = BackboardBot::new(Player::Right, 1.0e-30);
+        assert!(stuck.get_action(&state, 0.0).is_some());
+        assert_eq!(stuck.get_action(&state, 0.0), None);
+
+        // Uncapped, it moves every frame
+        let mut instant = BackboardBot::new(Player::Right, 0.0);
+        assert!(instant.get_action(&state, 0.0).is_some());
+        assert!(instant.get_action(&state, 0.0).is_some());
     }
 }
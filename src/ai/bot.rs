@@ -1,6 +1,6 @@
 // Bot trait for AI opponents
 
-use crate::game::{GameState, InputAction};
+use crate::game::{GameState, InputAction, Player};
 
 /// Trait for AI bot implementations
 ///
@@ -22,3 +22,22 @@ pub trait Bot {
     /// Reset bot internal state (called when new game/round starts)
     fn reset(&mut self);
 }
+
+/// Center y-position of the paddle on `side`
+pub(super) fn paddle_center_y(game_state: &GameState, side: Player) -> f32 {
+    let paddle = match side {
+        Player::Left => &game_state.left_paddle,
+        Player::Right => &game_state.right_paddle,
+    };
+    paddle.y + paddle.height / 2.0
+}
+
+/// Move action for the paddle on `side` (down = toward larger y)
+pub(super) fn move_action(side: Player, down: bool) -> InputAction {
+    match (side, down) {
+        (Player::Left, false) => InputAction::LeftPaddleUp,
+        (Player::Left, true) => InputAction::LeftPaddleDown,
+        (Player::Right, false) => InputAction::RightPaddleUp,
+        (Player::Right, true) => InputAction::RightPaddleDown,
+    }
+}
@@ -1,14 +1,22 @@
 // AI module for bot opponents
 
+mod adaptive_bot;
 mod backboard_bot;
 mod bot;
+mod offensive_bot;
 mod prediction;
 mod predictive_bot;
 
+pub use adaptive_bot::AdaptiveBot;
 pub use backboard_bot::BackboardBot;
 pub use bot::Bot;
+pub use offensive_bot::OffensiveBot;
+pub use prediction::predict_ball_intercept;
 pub use predictive_bot::PredictiveBot;
 
+use crate::config::types::AIConfig;
+use crate::game::Player;
+
 /// Bot type selection
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum BotType {
@@ -18,6 +26,12 @@ pub enum BotType {
     Hard,
     /// Backboard - instant tracker for training mode
     Backboard,
+    /// Adaptive - predictive bot that eases off or tightens up with the score
+    Adaptive,
+    /// Offensive - predictive bot that aims returns away from the player
+    Offensive,
+    /// Custom - predictive bot tuned in the config file (`[ai.custom]`)
+    Custom,
 }
 
 impl BotType {
@@ -27,20 +41,59 @@ impl BotType {
             BotType::Easy => "Easy",
             BotType::Hard => "Hard",
             BotType::Backboard => "Backboard",
+            BotType::Adaptive => "Adaptive",
+            BotType::Offensive => "Offensive",
+            BotType::Custom => "Custom",
         }
     }
 
     /// Get all available bot types
     pub fn all() -> Vec<BotType> {
-        vec![BotType::Easy, BotType::Hard, BotType::Backboard]
+        vec![
+            BotType::Easy,
+            BotType::Hard,
+            BotType::Adaptive,
+            BotType::Offensive,
+            BotType::Custom,
+            BotType::Backboard,
+        ]
+    }
+
+    /// Bot paddle height relative to the configured one. A bigger paddle forgives the
+    /// bot's misreads, so this is a difficulty lever separate from prediction error:
+    /// Easy defends a wider goal mouth, every other bot plays with the player's size.
+    pub fn paddle_height_multiplier(&self) -> f32 {
+        match self {
+            BotType::Easy => 1.3,
+            BotType::Hard
+            | BotType::Backboard
+            | BotType::Adaptive
+            | BotType::Offensive
+            | BotType::Custom => 1.0,
+        }
+    }
+
+    /// Bot named in config (`ai.difficulty`), case-insensitive
+    pub fn from_name(name: &str) -> Option<BotType> {
+        Self::all()
+            .into_iter()
+            .find(|bot| bot.display_name().eq_ignore_ascii_case(name))
     }
 }
 
-/// Create a bot instance from a bot type
-pub fn create_bot(bot_type: BotType) -> Box<dyn Bot> {
+/// Create a bot instance from a bot type, controlling the paddle on `side`
+pub fn create_bot(bot_type: BotType, side: Player, ai: &AIConfig) -> Box<dyn Bot> {
     match bot_type {
-        BotType::Easy => Box::new(PredictiveBot::easy()),
-        BotType::Hard => Box::new(PredictiveBot::hard()),
-        BotType::Backboard => Box::new(BackboardBot::new()),
+        BotType::Easy => Box::new(PredictiveBot::easy(side)),
+        BotType::Hard => Box::new(PredictiveBot::hard(side)),
+        BotType::Backboard => Box::new(BackboardBot::new(side, ai.backboard_max_paddle_speed)),
+        BotType::Adaptive => Box::new(AdaptiveBot::new(side)),
+        BotType::Offensive => Box::new(OffensiveBot::new(side)),
+        BotType::Custom => Box::new(PredictiveBot::custom(
+            side,
+            ai.custom.error_stddev,
+            ai.custom.miss_rate,
+            ai.custom.reaction_delay_ms,
+        )),
     }
 }
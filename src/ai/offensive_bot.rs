@@ -0,0 +1,74 @@
+// Offensive bot - a predictive bot that aims its returns away from the opponent
+
+use super::bot::paddle_center_y;
+use super::predictive_bot::PredictiveBot;
+use super::Bot;
+use crate::game::{GameState, InputAction, Player};
+
+/// How far off center it meets the ball, as a share of its paddle height
+/// (0.3 hits at 80% of the way down the paddle: about a 36 degree return)
+const AIM_OFFSET: f32 = 0.3;
+
+/// Predictive bot that meets the ball off center to send it toward the far corner
+/// from the opponent's paddle, rather than just blocking it.
+///
+/// The tradeoff: the ball lands near the edge of its paddle, so the same prediction
+/// error that a centered block would absorb can now make it miss entirely.
+pub struct OffensiveBot {
+    inner: PredictiveBot,
+    side: Player,
+}
+
+impl OffensiveBot {
+    /// Create an OffensiveBot controlling the paddle on `side` (Hard accuracy)
+    pub fn new(side: Player) -> Self {
+        Self {
+            inner: PredictiveBot::hard(side),
+            side,
+        }
+    }
+
+    /// Where its paddle's center should sit relative to the ball at contact: above it
+    /// (negative) to send the ball down, when the opponent is in the top half
+    fn aim_offset(&self, game_state: &GameState) -> f32 {
+        let opponent_y = paddle_center_y(game_state, self.side.opponent());
+        let offset = AIM_OFFSET * game_state.paddle(self.side).height;
+        if opponent_y < game_state.field_height / 2.0 {
+            -offset
+        } else {
+            offset
+        }
+    }
+}
+
+impl Bot for OffensiveBot {
+    fn get_action(&mut self, game_state: &GameState, dt: f32) -> Option<InputAction> {
+        self.inner.set_aim_offset(self.aim_offset(game_state));
+        self.inner.get_action(game_state, dt)
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PhysicsConfig;
+
+    #[test]
+    fn test_aims_away_from_opponent() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        let bot = OffensiveBot::new(Player::Right);
+        let offset = AIM_OFFSET * state.right_paddle.height;
+
+        // Opponent high: meet the ball with the bottom of the paddle, sending it down
+        state.left_paddle.y = 0.0;
+        assert_eq!(bot.aim_offset(&state), -offset);
+
+        // Opponent low: the other way round
+        state.left_paddle.y = state.field_height - state.left_paddle.height;
+        assert_eq!(bot.aim_offset(&state), offset);
+    }
+}
@@ -113,6 +113,25 @@ mod tests {
         assert!(pred_y < FIELD_HEIGHT); // But still in bounds
     }
 
+    #[test]
+    fn test_same_path_mirrored_for_either_paddle() {
+        // A ball the same distance from each paddle, heading for it at the same
+        // angle, arrives at the same height on both sides (here after one bounce)
+        let right = predict_ball_intercept(700.0, 500.0, 6.0, 4.0, RIGHT_PADDLE_X, FIELD_HEIGHT);
+        let left = predict_ball_intercept(
+            1200.0 - 700.0,
+            500.0,
+            -6.0,
+            4.0,
+            LEFT_PADDLE_X,
+            FIELD_HEIGHT,
+        );
+
+        // (1172 - 700) / 6 * 4 = 314.7 up from 500 reaches 814.7: bounces at 600, back to 385.3
+        assert!((right.unwrap() - 385.3).abs() < 0.1);
+        assert!((left.unwrap() - right.unwrap()).abs() < 0.01);
+    }
+
     #[test]
     fn test_single_wall_bounce_top() {
         // Ball moving right and up, will hit top wall
@@ -128,7 +147,7 @@ mod tests {
         // Should predict somewhere valid after bouncing off top
         assert!(predicted.is_some());
         let pred_y = predicted.unwrap();
-        assert!(pred_y >= 0.0 && pred_y <= FIELD_HEIGHT);
+        assert!((0.0..=FIELD_HEIGHT).contains(&pred_y));
     }
 
     #[test]
@@ -146,7 +165,7 @@ mod tests {
         // Should predict somewhere valid after bouncing off bottom
         assert!(predicted.is_some());
         let pred_y = predicted.unwrap();
-        assert!(pred_y >= 0.0 && pred_y <= FIELD_HEIGHT);
+        assert!((0.0..=FIELD_HEIGHT).contains(&pred_y));
     }
 
     #[test]
@@ -164,7 +183,7 @@ mod tests {
         // Should handle multiple bounces and return valid position
         assert!(predicted.is_some());
         let pred_y = predicted.unwrap();
-        assert!(pred_y >= 0.0 && pred_y <= FIELD_HEIGHT);
+        assert!((0.0..=FIELD_HEIGHT).contains(&pred_y));
     }
 
     #[test]
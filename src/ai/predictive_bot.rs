@@ -1,33 +1,49 @@
 // Predictive bot with imperfect trajectory prediction
 
+use super::bot::{move_action, paddle_center_y};
 use super::prediction::predict_ball_intercept;
 use super::Bot;
-use crate::game::{GameState, InputAction};
+use crate::game::physics::{PADDLE_MARGIN, PADDLE_WIDTH};
+use crate::game::{GameState, InputAction, Player};
 use rand::rngs::ThreadRng;
 use rand::{thread_rng, Rng};
 use rand_distr::{Distribution, Normal};
-use std::time::Instant;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
 /// Configuration for a predictive bot's behavior
 #[derive(Debug, Clone)]
 struct PredictiveBotConfig {
     error_stddev: f32, // Standard deviation of prediction error (normal distribution)
     catastrophic_miss_rate: f32, // Probability of total whiff
-    reaction_delay_ms: u64, // Delay between actions
+    reaction_delay_ms: u64, // Delay between actions, and how stale the bot's view of the ball is
     prediction_update_interval_ms: u64, // How often bot recalculates prediction
     movement_threshold: f32, // Dead zone to avoid jittery movement
 }
 
+/// The ball as the bot saw it at some moment
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BallSighting {
+    at: Instant,
+    x: f32,
+    y: f32,
+    vx: f32,
+    vy: f32,
+}
+
 /// Predictive bot that uses trajectory prediction with human-like errors
 pub struct PredictiveBot {
     config: PredictiveBotConfig,
+    side: Player, // Paddle this bot controls
 
     // Cached prediction state
     last_prediction_time: Instant,
     cached_target_y: Option<f32>, // None = return to center
+    aim_offset: f32,              // Added to the predicted y when meeting the ball
 
     // Reaction delay
     last_action_time: Instant,
+    sightings: VecDeque<BallSighting>, // Recent views of the ball, oldest first
 
     // RNG for error injection
     rng: ThreadRng,
@@ -35,49 +51,105 @@ pub struct PredictiveBot {
 
 impl PredictiveBot {
     /// Create a new PredictiveBot with the given configuration
-    fn new(config: PredictiveBotConfig) -> Self {
+    fn new(config: PredictiveBotConfig, side: Player) -> Self {
         Self {
             config,
+            side,
             last_prediction_time: Instant::now(),
             cached_target_y: None,
+            aim_offset: 0.0,
             last_action_time: Instant::now(),
+            sightings: VecDeque::new(),
             rng: thread_rng(),
         }
     }
 
     /// Create an Easy difficulty bot (high variance, frequent mistakes)
-    pub fn easy() -> Self {
-        Self::new(PredictiveBotConfig {
-            error_stddev: 35.0,           // High variance: ±35 units (1σ), ±70 units (2σ)
-            catastrophic_miss_rate: 0.12, // 12% total whiffs
-            reaction_delay_ms: 200,
-            prediction_update_interval_ms: 250,
-            movement_threshold: 40.0,
-        })
+    pub fn easy(side: Player) -> Self {
+        Self::new(
+            PredictiveBotConfig {
+                error_stddev: 35.0,           // High variance: ±35 units (1σ), ±70 units (2σ)
+                catastrophic_miss_rate: 0.12, // 12% total whiffs
+                reaction_delay_ms: 200,
+                prediction_update_interval_ms: 250,
+                movement_threshold: 40.0,
+            },
+            side,
+        )
     }
 
     /// Create a Hard difficulty bot (moderate variance, occasional mistakes)
-    pub fn hard() -> Self {
-        Self::new(PredictiveBotConfig {
-            error_stddev: 18.0,           // Medium variance: ±18 units (1σ), ±36 units (2σ)
-            catastrophic_miss_rate: 0.05, // 5% whiffs
-            reaction_delay_ms: 120,
-            prediction_update_interval_ms: 150,
-            movement_threshold: 30.0,
-        })
-    }
-
-    /// Update the cached prediction based on current game state
-    fn update_prediction(&mut self, game_state: &GameState) {
-        // Calculate paddle x-position (right paddle for AI)
-        let paddle_x = game_state.field_width - 18.0 - 10.0; // PADDLE_MARGIN - PADDLE_WIDTH/2
-
-        // Predict where ball will be when it reaches the paddle
+    pub fn hard(side: Player) -> Self {
+        Self::new(
+            PredictiveBotConfig {
+                error_stddev: 18.0,           // Medium variance: ±18 units (1σ), ±36 units (2σ)
+                catastrophic_miss_rate: 0.05, // 5% whiffs
+                reaction_delay_ms: 120,
+                prediction_update_interval_ms: 150,
+                movement_threshold: 30.0,
+            },
+            side,
+        )
+    }
+
+    /// Create a bot with player-chosen accuracy and reaction delay (`[ai.custom]`)
+    pub fn custom(
+        side: Player,
+        error_stddev: f32,
+        catastrophic_miss_rate: f32,
+        reaction_delay_ms: u64,
+    ) -> Self {
+        Self::new(
+            PredictiveBotConfig {
+                error_stddev,
+                catastrophic_miss_rate,
+                reaction_delay_ms,
+                // As Easy and Hard
+                prediction_update_interval_ms: reaction_delay_ms.saturating_mul(5) / 4,
+                movement_threshold: 30.0,
+            },
+            side,
+        )
+    }
+
+    /// Change how accurate the bot is (see PredictiveBotConfig)
+    pub(super) fn set_accuracy(&mut self, error_stddev: f32, catastrophic_miss_rate: f32) {
+        self.config.error_stddev = error_stddev;
+        self.config.catastrophic_miss_rate = catastrophic_miss_rate;
+    }
+
+    /// Meet the ball this far from the paddle center (positive = paddle lower)
+    pub(super) fn set_aim_offset(&mut self, offset: f32) {
+        self.aim_offset = offset;
+    }
+
+    /// Record the ball as seen now, and return it as seen `reaction_delay_ms` ago
+    /// (or as first seen, if the bot hasn't been watching that long)
+    fn perceive(&mut self, sighting: BallSighting) -> BallSighting {
+        let delay = Duration::from_millis(self.config.reaction_delay_ms);
+        self.sightings.push_back(sighting);
+        // Keep a single sighting at least `delay` old: the one the bot acts on
+        while self.sightings.len() > 1 && sighting.at.duration_since(self.sightings[1].at) >= delay
+        {
+            self.sightings.pop_front();
+        }
+        self.sightings[0]
+    }
+
+    /// Update the cached prediction from the ball as the bot perceives it
+    fn update_prediction(&mut self, game_state: &GameState, ball: &BallSighting) {
+        // Calculate the x-position of this bot's paddle center
+        let paddle_x = match self.side {
+            Player::Left => PADDLE_MARGIN + PADDLE_WIDTH / 2.0,
+            Player::Right => game_state.field_width - PADDLE_MARGIN - PADDLE_WIDTH / 2.0,
+        };
+
+        // Predict where the most pressing ball will be when it reaches the paddle
         let true_prediction = predict_ball_intercept(
-            game_state.ball.x,
-            game_state.ball.y,
-            game_state.ball.vx,
-            game_state.ball.vy,
+            ball.x,
+            ball.y,
+            ball.vx,
+            ball.vy,
             paddle_x,
             game_state.field_height,
         );
@@ -124,9 +196,18 @@ impl PredictiveBot {
 
 impl Bot for PredictiveBot {
     fn get_action(&mut self, game_state: &GameState, _dt: f32) -> Option<InputAction> {
-        // 1. Update prediction if interval has passed
+        // 1. Update prediction if interval has passed, from a slightly stale view of
+        //    the most pressing ball (so sudden bounces catch the bot out)
+        let ball = game_state.ball_toward(self.side);
+        let seen = self.perceive(BallSighting {
+            at: Instant::now(),
+            x: ball.x,
+            y: ball.y,
+            vx: ball.vx,
+            vy: ball.vy,
+        });
         if self.should_update_prediction() {
-            self.update_prediction(game_state);
+            self.update_prediction(game_state, &seen);
         }
 
         // 2. Check reaction delay
@@ -135,11 +216,11 @@ impl Bot for PredictiveBot {
         }
 
         // 3. Determine target position
-        let paddle_center_y = game_state.right_paddle.y + (game_state.right_paddle.height / 2.0);
+        let paddle_center_y = paddle_center_y(game_state, self.side);
         let field_center_y = game_state.field_height / 2.0;
 
         let target_y = match self.cached_target_y {
-            Some(y) => y,           // Move toward predicted position
+            Some(y) => y + self.aim_offset, // Move toward predicted position
             None => field_center_y, // Ball moving away or catastrophic miss → return to center
         };
 
@@ -154,11 +235,7 @@ impl Bot for PredictiveBot {
         // 6. Update action timestamp and return move command
         self.last_action_time = Instant::now();
 
-        if diff > 0.0 {
-            Some(InputAction::RightPaddleDown)
-        } else {
-            Some(InputAction::RightPaddleUp)
-        }
+        Some(move_action(self.side, diff > 0.0))
     }
 
     fn reset(&mut self) {
@@ -166,5 +243,83 @@ impl Bot for PredictiveBot {
         self.last_prediction_time = Instant::now();
         self.last_action_time = Instant::now();
         self.cached_target_y = None;
+        self.sightings.clear(); // The ball was just re-served
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PhysicsConfig;
+    use crate::game::physics::paddle_input;
+
+    fn sighting(at: Instant, y: f32) -> BallSighting {
+        BallSighting {
+            at,
+            x: 400.0,
+            y,
+            vx: 300.0,
+            vy: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_meets_the_ball_on_either_side() {
+        for side in [Player::Left, Player::Right] {
+            let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+            state.ball.y = 150.0;
+            state.ball.vx = match side {
+                Player::Left => -500.0,
+                Player::Right => 500.0,
+            };
+            state.ball.vy = 0.0;
+
+            // No errors or delays: the bot walks its own paddle to the ball's line
+            let mut bot = PredictiveBot::custom(side, 0.0, 0.0, 0);
+            for _ in 0..20 {
+                match bot.get_action(&state, 0.0) {
+                    Some(InputAction::LeftPaddleUp) => {
+                        paddle_input(&mut state, Player::Left, false)
+                    }
+                    Some(InputAction::LeftPaddleDown) => {
+                        paddle_input(&mut state, Player::Left, true)
+                    }
+                    Some(InputAction::RightPaddleUp) => {
+                        paddle_input(&mut state, Player::Right, false)
+                    }
+                    Some(InputAction::RightPaddleDown) => {
+                        paddle_input(&mut state, Player::Right, true)
+                    }
+                    _ => {}
+                }
+            }
+
+            let (own, other) = (
+                paddle_center_y(&state, side),
+                paddle_center_y(&state, side.opponent()),
+            );
+            assert!((own - 150.0).abs() < 30.0, "{:?} paddle at {}", side, own);
+            assert_eq!(other, state.field_height / 2.0);
+        }
+    }
+
+    #[test]
+    fn test_perceives_ball_after_reaction_delay() {
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+
+        let mut bot = PredictiveBot::custom(Player::Right, 0.0, 0.0, 100);
+        assert_eq!(bot.perceive(sighting(ms(0), 10.0)).y, 10.0);
+        // Within the delay the bot still sees the ball where it first was
+        assert_eq!(bot.perceive(sighting(ms(50), 20.0)).y, 10.0);
+        assert_eq!(bot.perceive(sighting(ms(120), 30.0)).y, 10.0);
+        // Then it catches up to what it saw 100ms earlier
+        assert_eq!(bot.perceive(sighting(ms(160), 40.0)).y, 20.0);
+        assert_eq!(bot.perceive(sighting(ms(250), 50.0)).y, 30.0);
+
+        // With no delay it sees the ball as it is
+        let mut sharp = PredictiveBot::custom(Player::Right, 0.0, 0.0, 0);
+        assert_eq!(sharp.perceive(sighting(ms(0), 10.0)).y, 10.0);
+        assert_eq!(sharp.perceive(sighting(ms(50), 20.0)).y, 20.0);
     }
 }
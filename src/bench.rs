@@ -0,0 +1,126 @@
+// Headless physics benchmark (the `bench` binary): plays scripted games through
+// update_with_events with no terminal or rendering, then reports throughput and a
+// hash of the final state. The hash must be the same on every run of a build;
+// a change means the simulation picked up some nondeterminism.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::Instant;
+
+use crate::config::PhysicsConfig;
+use crate::game::physics::paddle_input;
+use crate::game::{self, GameState, Player};
+
+/// Frames simulated when the benchmark is given no count
+pub const DEFAULT_FRAMES: u64 = 10_000_000;
+
+/// Fixed timestep, as at the default 60 Hz tick rate
+const DT: f32 = 1.0 / 60.0;
+
+/// Outcome of a scripted run
+#[derive(Debug, PartialEq, Eq)]
+pub struct BenchResult {
+    pub frames: u64,
+    pub games: u64,
+    pub goals: u64,
+    pub hash: u64,
+}
+
+/// Rules that exercise most of the physics: seeded serve tilts, spin and power-ups
+fn bench_physics() -> PhysicsConfig {
+    PhysicsConfig {
+        serve_seed: Some(0x5eed),
+        powerups: true,
+        ..PhysicsConfig::default()
+    }
+}
+
+/// Scripted player: the left paddle chases the ball, the right one only every
+/// third frame and not at all for a stretch of each rally, so both sides score
+fn scripted_inputs(state: &mut GameState, frame: u64) {
+    let ball_y = state.ball.y;
+    for (player, every) in [(Player::Left, 1), (Player::Right, 3)] {
+        if !frame.is_multiple_of(every) || (player == Player::Right && frame % 600 > 450) {
+            continue;
+        }
+        let paddle = state.paddle(player);
+        let center = paddle.y + paddle.height / 2.0;
+        if (ball_y - center).abs() > state.tap_distance {
+            paddle_input(state, player, ball_y > center);
+        }
+    }
+}
+
+/// Play `frames` frames of scripted games (a new one starts whenever one ends)
+pub fn simulate(frames: u64) -> BenchResult {
+    let mut state = GameState::new(0, 0, &bench_physics());
+    let (mut games, mut goals) = (0, 0);
+
+    for frame in 0..frames {
+        if state.game_over {
+            state.reset_game();
+            games += 1;
+        }
+        scripted_inputs(&mut state, frame);
+        if game::update_with_events(&mut state, DT).goal_scored {
+            goals += 1;
+        }
+    }
+
+    BenchResult {
+        frames,
+        games,
+        goals,
+        hash: state_hash(&state),
+    }
+}
+
+/// Hash of everything the simulation evolves (floats by their exact bits)
+fn state_hash(state: &GameState) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for ball in state.balls() {
+        [ball.x, ball.y, ball.vx, ball.vy]
+            .map(f32::to_bits)
+            .hash(&mut hasher);
+    }
+    for paddle in [&state.left_paddle, &state.right_paddle] {
+        [paddle.y, paddle.height, paddle.vy]
+            .map(f32::to_bits)
+            .hash(&mut hasher);
+    }
+    (state.left_score, state.right_score, state.serve_count).hash(&mut hasher);
+    state.elapsed.to_bits().hash(&mut hasher);
+    state.powerups.len().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Run the benchmark and print the results
+pub fn run(frames: u64) {
+    let started = Instant::now();
+    let result = simulate(frames);
+    let secs = started.elapsed().as_secs_f64();
+
+    println!("frames:     {}", result.frames);
+    println!("games:      {}", result.games);
+    println!("goals:      {}", result.goals);
+    println!("time:       {:.3}s", secs);
+    println!(
+        "frames/sec: {:.0}",
+        result.frames as f64 / secs.max(f64::EPSILON)
+    );
+    println!("state hash: {:016x}", result.hash);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_is_deterministic() {
+        let a = simulate(20_000);
+        assert_eq!(a, simulate(20_000));
+        // The script has to actually play: goals on both sides and whole games
+        assert!(a.goals > 0 && a.games > 0, "{:?}", a);
+        assert_ne!(a.hash, simulate(19_999).hash);
+    }
+}
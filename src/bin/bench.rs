@@ -0,0 +1,21 @@
+// Headless physics benchmark: plays scripted games with no terminal and reports
+// frames/sec and a hash of the final state (see p2pong::bench)
+//
+// Usage: cargo run --release --bin bench -- [frames]
+
+use p2pong::bench;
+
+fn main() {
+    let frames = match std::env::args().nth(1) {
+        Some(frames) => match frames.parse() {
+            Ok(frames) => frames,
+            Err(_) => {
+                eprintln!("{:?} is not a frame count", frames);
+                eprintln!("Usage: bench [frames]");
+                std::process::exit(2);
+            }
+        },
+        None => bench::DEFAULT_FRAMES,
+    };
+    bench::run(frames);
+}
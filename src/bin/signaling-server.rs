@@ -2,6 +2,10 @@
 // Relays SDP offers/answers between peers for WebRTC connection establishment
 //
 // Usage: cargo run --bin signaling-server
+//
+// Speaks plain WebSocket on $PORT (default 8080). For wss://, put it behind a proxy that
+// terminates TLS (Railway does this for its public domain; elsewhere e.g. Caddy with
+// `reverse_proxy localhost:8080`, or nginx with the Upgrade/Connection headers passed on).
 
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
@@ -48,6 +52,13 @@ enum SignalingMessage {
         sdp: String,
     },
 
+    /// Offer to watch the match a paired peer is hosting (answered like an offer)
+    Spectate {
+        target: PeerId,
+        from: PeerId,
+        sdp: String,
+    },
+
     /// Send an SDP answer to a peer
     Answer {
         target: PeerId,
@@ -138,8 +149,8 @@ async fn handle_websocket(
             Err(e) => {
                 // Connection reset after signaling is normal, don't log as error
                 let error_str = e.to_string();
-                if error_str.contains("Connection reset") && peer_id.is_some() {
-                    info!("📤 Peer {} disconnected (signaling complete)", peer_id.as_ref().unwrap());
+                if let (true, Some(id)) = (error_str.contains("Connection reset"), &peer_id) {
+                    info!("📤 Peer {} disconnected (signaling complete)", id);
                 } else {
                     error!("Error receiving message: {}", e);
                 }
@@ -246,6 +257,33 @@ async fn handle_signaling_message(
             .await;
         }
 
+        SignalingMessage::Spectate { target, from, sdp } => {
+            // Only a peer already in a match has anything to watch
+            if !pairings.read().await.contains_key(&target) {
+                warn!("{} asked to watch {}, which has no match", from, target);
+                let error_msg = SignalingMessage::Error {
+                    message: format!("Peer {} has no match to watch", target),
+                };
+                if let Ok(json) = serde_json::to_string(&error_msg) {
+                    let _ = tx.send(Message::Text(json));
+                }
+                return;
+            }
+
+            info!("👀 Relaying spectate offer from {} to {}", from, target);
+            relay_message(
+                peers,
+                &target,
+                SignalingMessage::Spectate {
+                    target: target.clone(),
+                    from,
+                    sdp,
+                },
+                tx,
+            )
+            .await;
+        }
+
         SignalingMessage::Answer { target, from, sdp } => {
             info!("📨 Relaying answer from {} to {}", from, target);
             relay_message(
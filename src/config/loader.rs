@@ -1,9 +1,29 @@
 // Configuration file loading and creation
 
-use super::types::Config;
+use super::types::{
+    AIConfig, Config, CustomBotConfig, DisplayConfig, IceServerConfig, KeyBindings, NetworkConfig,
+    PhysicsConfig, ThemeConfig,
+};
+use crate::error::P2PongError;
+use crate::game::input::binding_problems;
+use crate::game::physics::{BALL_SIZE, MAX_BALL_SPEED, PADDLE_MARGIN, PADDLE_WIDTH};
+use crate::ui::theme::{parse_hex_color, Theme, THEME_NAMES};
+use std::ffi::OsString;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+/// Environment variable pointing at an alternate config file
+const CONFIG_ENV_VAR: &str = "P2PONG_CONFIG";
+
+/// Set when the config file failed to parse and defaults are in use, so that saving
+/// settings doesn't replace the user's file with those defaults
+static PARSE_FAILED: AtomicBool = AtomicBool::new(false);
+
+/// The config file load_config resolved, so saving writes back to that same file
+static LOADED_PATH: OnceLock<PathBuf> = OnceLock::new();
 
 /// Get the path to the configuration file
 pub fn get_config_path() -> PathBuf {
@@ -17,35 +37,329 @@ pub fn get_config_path() -> PathBuf {
     path
 }
 
-/// Load configuration from file, or create default if it doesn't exist
-pub fn load_config() -> Result<Config, io::Error> {
-    let config_path = get_config_path();
+/// Resolve the P2PONG_CONFIG override, ignoring it (with a warning) if the file is unusable
+fn override_config_path(value: Option<OsString>) -> Option<PathBuf> {
+    let path = PathBuf::from(value?);
+
+    if path.is_file() {
+        Some(path)
+    } else {
+        eprintln!(
+            "Warning: {} points at {}, which is not a readable file",
+            CONFIG_ENV_VAR,
+            path.display()
+        );
+        eprintln!("Using default config location");
+        None
+    }
+}
+
+/// Drop ICE server URLs other than STUN ones (with a warning), and servers left with none.
+/// The network runtime has no TURN client, so TURN relays can't be used yet.
+fn validate_ice_servers(servers: &mut Vec<IceServerConfig>) {
+    for server in servers.iter_mut() {
+        server.urls.retain(|url| {
+            if url.starts_with("stun:") {
+                return true;
+            }
+            if url.starts_with("turn:") || url.starts_with("turns:") {
+                eprintln!(
+                    "Warning: Ignoring ICE server URL {:?} (TURN relays are not supported yet)",
+                    url
+                );
+            } else {
+                eprintln!(
+                    "Warning: Ignoring ICE server URL {:?} (must start with stun:)",
+                    url
+                );
+            }
+            false
+        });
+    }
+    servers.retain(|server| !server.urls.is_empty());
+}
+
+/// Signaling server URL schemes (WebSocket, plain or TLS)
+const SIGNALING_URL_PREFIXES: [&str; 2] = ["ws://", "wss://"];
+
+/// Fall back to the default signaling server (with a warning) if the URL isn't a WebSocket one
+fn validate_signaling_server(server: &mut String) {
+    let valid = SIGNALING_URL_PREFIXES
+        .iter()
+        .any(|prefix| server.starts_with(prefix) && server.len() > prefix.len());
+    if valid {
+        return;
+    }
+    let default = NetworkConfig::default().signaling_server;
+    eprintln!(
+        "Warning: Ignoring signaling server {:?} (must start with ws:// or wss://)",
+        server
+    );
+    eprintln!("Using {}", default);
+    *server = default;
+}
+
+/// Fall back to the default key bindings (with a warning) if any key is unknown or bound twice
+fn validate_keybindings(bindings: &mut KeyBindings) {
+    let problems = binding_problems(bindings);
+    if problems.is_empty() {
+        return;
+    }
+    for problem in &problems {
+        eprintln!("Warning: Key binding {}", problem);
+    }
+    eprintln!("Using default key bindings");
+    *bindings = KeyBindings::default();
+}
+
+/// Reset out-of-range Custom bot settings to their defaults (with a warning)
+fn validate_custom_bot(custom: &mut CustomBotConfig) {
+    let default = CustomBotConfig::default();
+    if !(custom.error_stddev >= 0.0 && custom.error_stddev.is_finite()) {
+        eprintln!(
+            "Warning: Ignoring ai.custom.error_stddev {} (must be 0 or more)",
+            custom.error_stddev
+        );
+        custom.error_stddev = default.error_stddev;
+    }
+    if !(0.0..=1.0).contains(&custom.miss_rate) {
+        eprintln!(
+            "Warning: Ignoring ai.custom.miss_rate {} (must be between 0 and 1)",
+            custom.miss_rate
+        );
+        custom.miss_rate = default.miss_rate;
+    }
+}
+
+/// Fall back to the default serve speed (with a warning) if it isn't a positive number
+/// up to the speed cap (run after validate_max_speed)
+fn validate_launch_speed(physics: &mut PhysicsConfig) {
+    let speed = physics.ball_initial_speed;
+    let cap = physics.max_ball_speed;
+    if speed.is_finite() && speed > 0.0 && speed <= cap {
+        return;
+    }
+    let default = PhysicsConfig::default().ball_initial_speed.min(cap);
+    eprintln!(
+        "Warning: Ignoring physics.ball_initial_speed {} (must be more than 0 and at most physics.max_ball_speed {})",
+        speed, cap
+    );
+    eprintln!("Using {}", default);
+    physics.ball_initial_speed = default;
+}
+
+/// Fall back to the default speed cap (with a warning) if it isn't a positive number
+/// within the physics hard limit
+fn validate_max_speed(physics: &mut PhysicsConfig) {
+    let speed = physics.max_ball_speed;
+    if speed.is_finite() && speed > 0.0 && speed <= MAX_BALL_SPEED {
+        return;
+    }
+    let default = PhysicsConfig::default().max_ball_speed;
+    eprintln!(
+        "Warning: Ignoring physics.max_ball_speed {} (must be more than 0 and at most {})",
+        speed, MAX_BALL_SPEED
+    );
+    eprintln!("Using {}", default);
+    physics.max_ball_speed = default;
+}
+
+/// Fall back to the default paddle height and tap distance (with a warning) if they
+/// aren't positive numbers, or the paddle is taller than the field
+fn validate_paddle(physics: &mut PhysicsConfig) {
+    let default = PhysicsConfig::default();
+    let height = physics.paddle_height;
+    if !(height.is_finite() && height > 0.0 && height <= physics.virtual_height) {
+        eprintln!(
+            "Warning: Ignoring physics.paddle_height {} (must be more than 0 and at most the field height {})",
+            height, physics.virtual_height
+        );
+        physics.paddle_height = default.paddle_height.min(physics.virtual_height);
+        eprintln!("Using {}", physics.paddle_height);
+    }
+
+    let distance = physics.paddle_tap_distance;
+    if !(distance.is_finite() && distance > 0.0) {
+        eprintln!(
+            "Warning: Ignoring physics.paddle_tap_distance {} (must be more than 0)",
+            distance
+        );
+        eprintln!("Using {}", default.paddle_tap_distance);
+        physics.paddle_tap_distance = default.paddle_tap_distance;
+    }
+}
+
+/// Drop obstacles (with a warning) that don't fit inside the field between the paddle
+/// lanes, or that cover the serve spot in the center
+fn validate_obstacles(physics: &mut PhysicsConfig) {
+    let lane = PADDLE_MARGIN + PADDLE_WIDTH + BALL_SIZE;
+    let (width, height) = (physics.virtual_width, physics.virtual_height);
+    physics.obstacles.retain(|o| {
+        let fits = o.width > 0.0
+            && o.height > 0.0
+            && o.x >= lane
+            && o.right() <= width - lane
+            && o.y >= 0.0
+            && o.bottom() <= height
+            && !o.contains(width / 2.0, height / 2.0);
+        if !fits {
+            eprintln!(
+                "Warning: Ignoring physics.obstacles entry at ({}, {}) {}x{} (must be inside the field, clear of the paddle lanes and the serve spot)",
+                o.x, o.y, o.width, o.height
+            );
+        }
+        fits
+    });
+}
+
+/// Drop theme colors that aren't "#rrggbb" hex (with a warning), so the theme's own
+/// color is used; an unknown theme name falls back to classic
+fn validate_theme(theme: &mut ThemeConfig) {
+    if Theme::named(&theme.name).is_none() {
+        eprintln!(
+            "Warning: Ignoring display.theme.name {:?} (must be one of {})",
+            theme.name,
+            THEME_NAMES.join(", ")
+        );
+        eprintln!("Using classic");
+        theme.name = ThemeConfig::default().name;
+    }
+
+    let colors = [
+        ("ball_color", &mut theme.ball_color),
+        ("left_paddle_color", &mut theme.left_paddle_color),
+        ("right_paddle_color", &mut theme.right_paddle_color),
+        ("your_paddle_color", &mut theme.your_paddle_color),
+        ("opponent_paddle_color", &mut theme.opponent_paddle_color),
+        ("border_color", &mut theme.border_color),
+        ("background_color", &mut theme.background_color),
+        ("danger_zone_color", &mut theme.danger_zone_color),
+    ];
+    for (name, color) in colors {
+        if let Some(hex) = color
+            .as_deref()
+            .filter(|hex| parse_hex_color(hex).is_none())
+        {
+            eprintln!(
+                "Warning: Ignoring display.theme.{} {:?} (must be a hex color like \"#00ffff\")",
+                name, hex
+            );
+            *color = None;
+        }
+    }
+}
+
+/// Fall back to no speed cap (with a warning) if the Backboard bot's is negative or
+/// not a number
+fn validate_backboard_speed(ai: &mut AIConfig) {
+    let speed = ai.backboard_max_paddle_speed;
+    if speed.is_finite() && speed >= 0.0 {
+        return;
+    }
+    let default = AIConfig::default().backboard_max_paddle_speed;
+    eprintln!(
+        "Warning: Ignoring ai.backboard_max_paddle_speed {} (must be 0 or more)",
+        speed
+    );
+    eprintln!("Using {}", default);
+    ai.backboard_max_paddle_speed = default;
+}
+
+/// Supported range for display.target_fps
+const TARGET_FPS_RANGE: std::ops::RangeInclusive<u64> = 10..=240;
+
+/// Fall back to the default frame rate (with a warning) if target_fps is out of range
+fn validate_target_fps(fps: &mut u64) {
+    if TARGET_FPS_RANGE.contains(fps) {
+        return;
+    }
+    let default = DisplayConfig::default().target_fps;
+    eprintln!(
+        "Warning: Ignoring display.target_fps {} (must be between {} and {})",
+        fps,
+        TARGET_FPS_RANGE.start(),
+        TARGET_FPS_RANGE.end()
+    );
+    eprintln!("Using {}", default);
+    *fps = default;
+}
+
+/// The config file in use: P2PONG_CONFIG, when set to an existing file, or the default path
+fn config_path() -> PathBuf {
+    override_config_path(std::env::var_os(CONFIG_ENV_VAR)).unwrap_or_else(get_config_path)
+}
+
+/// Load configuration from file, or create default if it doesn't exist.
+/// P2PONG_CONFIG, when set to an existing file, takes precedence over the default path.
+pub fn load_config() -> Result<Config, P2PongError> {
+    let config_path = config_path();
+    let _ = LOADED_PATH.set(config_path.clone());
 
     if config_path.exists() {
-        let contents = fs::read_to_string(&config_path)?;
-        match toml::from_str(&contents) {
-            Ok(config) => Ok(config),
+        let contents =
+            fs::read_to_string(&config_path).map_err(|e| config_error(&config_path, e))?;
+        match toml::from_str::<Config>(&contents) {
+            Ok(mut config) => {
+                validate_ice_servers(&mut config.network.ice_servers);
+                validate_signaling_server(&mut config.network.signaling_server);
+                validate_keybindings(&mut config.keybindings);
+                validate_custom_bot(&mut config.ai.custom);
+                validate_backboard_speed(&mut config.ai);
+                validate_max_speed(&mut config.physics);
+                validate_launch_speed(&mut config.physics);
+                validate_paddle(&mut config.physics);
+                validate_obstacles(&mut config.physics);
+                validate_target_fps(&mut config.display.target_fps);
+                validate_theme(&mut config.display.theme);
+                Ok(config)
+            }
             Err(e) => {
                 eprintln!("Warning: Failed to parse config file: {}", e);
                 eprintln!("Using default configuration");
+                PARSE_FAILED.store(true, Ordering::Relaxed);
                 Ok(Config::default())
             }
         }
     } else {
         // Create default config file
-        create_default_config(&config_path)?;
+        create_default_config(&config_path).map_err(|e| config_error(&config_path, e))?;
         Ok(Config::default())
     }
 }
 
 /// Create a default configuration file with helpful comments
 pub fn create_default_config(path: &Path) -> Result<(), io::Error> {
-    let config = Config::default();
-    let toml_string =
-        toml::to_string_pretty(&config).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    fs::write(path, commented_toml(&Config::default())?)?;
+    println!("Created default config file at: {}", path.display());
+    Ok(())
+}
+
+/// Write settings changed in-game back to the config file that was loaded
+/// (hand-written comments in that file are not preserved).
+/// Refuses while that file is unreadable, rather than overwriting it.
+pub fn save_config(config: &Config) -> Result<(), P2PongError> {
+    let path = LOADED_PATH.get().cloned().unwrap_or_else(get_config_path);
+    if PARSE_FAILED.load(Ordering::Relaxed) {
+        return Err(P2PongError::Config(format!(
+            "{} could not be parsed, so it was left untouched (fix it and restart to save settings)",
+            path.display()
+        )));
+    }
+    commented_toml(config)
+        .and_then(|toml| fs::write(&path, toml))
+        .map_err(|e| config_error(&path, e))
+}
+
+/// A config file I/O failure, naming the file
+fn config_error(path: &Path, e: io::Error) -> P2PongError {
+    P2PongError::Config(format!("{}: {}", path.display(), e))
+}
+
+/// Serialize a config with helpful header comments
+fn commented_toml(config: &Config) -> Result<String, io::Error> {
+    let toml_string = toml::to_string_pretty(config).map_err(io::Error::other)?;
 
-    // Add helpful header comments
-    let commented_toml = format!(
+    Ok(format!(
         "# P2Pong Configuration File\n\
          # Edit this file to customize game behavior\n\
          # After editing, restart the game for changes to take effect\n\
@@ -55,19 +369,17 @@ pub fn create_default_config(path: &Path) -> Result<(), io::Error> {
          #\n\
          # Colors: RGB values from 0-255\n\
          #\n\
-         # AI difficulties: \"easy\", \"medium\", \"hard\"\n\n\
+         # Default bot (ai.difficulty): \"easy\", \"hard\", \"adaptive\", \"offensive\", \"custom\",\n\
+         #   \"backboard\" (tune \"custom\" under [ai.custom])\n\n\
          {}",
         toml_string
-    );
-
-    fs::write(path, commented_toml)?;
-    println!("Created default config file at: {}", path.display());
-    Ok(())
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::state::Obstacle;
 
     #[test]
     fn test_default_config_serialization() {
@@ -90,6 +402,17 @@ mod tests {
         assert_eq!(parsed.ai.difficulty, config.ai.difficulty);
     }
 
+    #[test]
+    fn test_commented_toml_parses() {
+        let mut config = Config::default();
+        config.physics.winning_score = 11;
+        config.display.theme.name = "amber".to_string();
+
+        let parsed: Config = toml::from_str(&commented_toml(&config).unwrap()).unwrap();
+        assert_eq!(parsed.physics.winning_score, 11);
+        assert_eq!(parsed.display.theme.name, "amber");
+    }
+
     #[test]
     fn test_partial_config_with_defaults() {
         // Should be able to parse partial config with #[serde(default)]
@@ -107,4 +430,247 @@ mod tests {
         assert_eq!(config.physics.paddle_height, 90.0);
         assert_eq!(config.keybindings.left_paddle_up, "W");
     }
+
+    #[test]
+    fn test_ice_server_validation() {
+        let config: Config = toml::from_str(
+            r#"
+            [[network.ice_servers]]
+            urls = ["stun:stun.example.com:3478", "http://example.com"]
+
+            [[network.ice_servers]]
+            urls = ["turn:turn.example.com"]
+            username = "user"
+            credential = "secret"
+
+            [[network.ice_servers]]
+            urls = ["stun.example.com"]
+            "#,
+        )
+        .unwrap();
+
+        let mut servers = config.network.ice_servers;
+        validate_ice_servers(&mut servers);
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].urls, vec!["stun:stun.example.com:3478"]);
+    }
+
+    #[test]
+    fn test_signaling_server_validation() {
+        let default = NetworkConfig::default().signaling_server;
+        for (url, expected) in [
+            ("ws://localhost:8080", "ws://localhost:8080"),
+            ("wss://signal.example.com", "wss://signal.example.com"),
+            ("https://signal.example.com", default.as_str()),
+            ("localhost:8080", default.as_str()),
+            ("", default.as_str()),
+        ] {
+            let mut server = url.to_string();
+            validate_signaling_server(&mut server);
+            assert_eq!(server, expected);
+        }
+    }
+
+    #[test]
+    fn test_keybinding_validation() {
+        let mut config: Config = toml::from_str(
+            r#"
+            [keybindings]
+            player_paddle_up = "J"
+            player_paddle_down = "K"
+            rematch = "K"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            binding_problems(&config.keybindings),
+            vec![r#""K" is bound to both rematch and player_paddle_down"#]
+        );
+        validate_keybindings(&mut config.keybindings);
+        assert_eq!(config.keybindings.player_paddle_up, "W");
+
+        // The same key may be reused across modes (local 2P vs single-player)
+        let mut bindings = KeyBindings {
+            player_paddle_up: "Up".to_string(),
+            ..KeyBindings::default()
+        };
+        validate_keybindings(&mut bindings);
+        assert_eq!(bindings.player_paddle_up, "Up");
+    }
+
+    #[test]
+    fn test_custom_bot_validation() {
+        let config: Config = toml::from_str(
+            r#"
+            [ai.custom]
+            error_stddev = -5.0
+            miss_rate = 0.3
+            reaction_delay_ms = 50
+            "#,
+        )
+        .unwrap();
+
+        let mut custom = config.ai.custom;
+        validate_custom_bot(&mut custom);
+        assert_eq!(custom.error_stddev, CustomBotConfig::default().error_stddev);
+        assert_eq!(custom.miss_rate, 0.3);
+        assert_eq!(custom.reaction_delay_ms, 50);
+
+        custom.miss_rate = 1.5;
+        validate_custom_bot(&mut custom);
+        assert_eq!(custom.miss_rate, CustomBotConfig::default().miss_rate);
+    }
+
+    #[test]
+    fn test_launch_speed_validation() {
+        let default = PhysicsConfig::default().ball_initial_speed;
+        for (speed, expected) in [(900.0, 900.0), (-10.0, default), (f32::NAN, default)] {
+            let mut physics = PhysicsConfig {
+                ball_initial_speed: speed,
+                ..PhysicsConfig::default()
+            };
+            validate_launch_speed(&mut physics);
+            assert_eq!(physics.ball_initial_speed, expected);
+        }
+
+        // Above the cap: the default, itself held to a cap below it
+        for (cap, expected) in [(2000.0, default), (default / 2.0, default / 2.0)] {
+            let mut physics = PhysicsConfig {
+                ball_initial_speed: cap + 100.0,
+                max_ball_speed: cap,
+                ..PhysicsConfig::default()
+            };
+            validate_launch_speed(&mut physics);
+            assert_eq!(physics.ball_initial_speed, expected);
+        }
+    }
+
+    #[test]
+    fn test_max_speed_validation() {
+        let default = PhysicsConfig::default().max_ball_speed;
+        for (speed, expected) in [(2000.0, 2000.0), (1.0e6, default), (0.0, default)] {
+            let mut physics = PhysicsConfig {
+                max_ball_speed: speed,
+                ..PhysicsConfig::default()
+            };
+            validate_max_speed(&mut physics);
+            assert_eq!(physics.max_ball_speed, expected);
+        }
+    }
+
+    #[test]
+    fn test_paddle_validation() {
+        let default = PhysicsConfig::default();
+        let mut physics = PhysicsConfig {
+            paddle_height: 150.0,
+            paddle_tap_distance: 25.0,
+            ..PhysicsConfig::default()
+        };
+        validate_paddle(&mut physics);
+        assert_eq!(physics.paddle_height, 150.0);
+        assert_eq!(physics.paddle_tap_distance, 25.0);
+
+        let mut physics = PhysicsConfig {
+            paddle_height: 1.0e6,
+            paddle_tap_distance: -5.0,
+            ..PhysicsConfig::default()
+        };
+        validate_paddle(&mut physics);
+        assert_eq!(physics.paddle_height, default.paddle_height);
+        assert_eq!(physics.paddle_tap_distance, default.paddle_tap_distance);
+    }
+
+    #[test]
+    fn test_obstacle_validation() {
+        let mut physics = PhysicsConfig {
+            obstacles: vec![
+                Obstacle {
+                    x: 10.0,
+                    y: 0.0,
+                    width: 40.0,
+                    height: 40.0,
+                }, // In the paddle lane
+                Obstacle {
+                    x: 550.0,
+                    y: 250.0,
+                    width: 100.0,
+                    height: 100.0,
+                }, // Over the serve
+                Obstacle {
+                    x: 300.0,
+                    y: 560.0,
+                    width: 50.0,
+                    height: 80.0,
+                }, // Off the field
+                Obstacle {
+                    x: 300.0,
+                    y: 50.0,
+                    width: 50.0,
+                    height: 80.0,
+                },
+            ],
+            ..PhysicsConfig::default()
+        };
+        let kept = physics.obstacles[3];
+        validate_obstacles(&mut physics);
+        assert_eq!(physics.obstacles, vec![kept]);
+    }
+
+    #[test]
+    fn test_theme_validation() {
+        let mut theme = ThemeConfig {
+            name: "neon".to_string(),
+            ball_color: Some("#ff0000".to_string()),
+            border_color: Some("not a color".to_string()),
+            ..ThemeConfig::default()
+        };
+        validate_theme(&mut theme);
+        assert_eq!(theme.name, "classic");
+        assert_eq!(theme.ball_color.as_deref(), Some("#ff0000"));
+        assert_eq!(theme.border_color, None);
+    }
+
+    #[test]
+    fn test_backboard_speed_validation() {
+        let default = AIConfig::default().backboard_max_paddle_speed;
+        for (speed, expected) in [(900.0, 900.0), (-5.0, default), (f32::INFINITY, default)] {
+            let mut ai = AIConfig {
+                backboard_max_paddle_speed: speed,
+                ..AIConfig::default()
+            };
+            validate_backboard_speed(&mut ai);
+            assert_eq!(ai.backboard_max_paddle_speed, expected);
+        }
+    }
+
+    #[test]
+    fn test_target_fps_validation() {
+        let default = DisplayConfig::default().target_fps;
+        for (fps, expected) in [(30, 30), (120, 120), (0, default), (1000, default)] {
+            let mut value = fps;
+            validate_target_fps(&mut value);
+            assert_eq!(value, expected);
+        }
+    }
+
+    #[test]
+    fn test_config_override_path() {
+        assert_eq!(override_config_path(None), None);
+
+        // Missing file falls back to the default location
+        let missing = std::env::temp_dir().join("p2pong-no-such-config.toml");
+        assert_eq!(override_config_path(Some(missing.into_os_string())), None);
+
+        let path = std::env::temp_dir().join(format!(
+            "p2pong-config-override-{}.toml",
+            std::process::id()
+        ));
+        fs::write(&path, "").unwrap();
+        assert_eq!(
+            override_config_path(Some(path.clone().into_os_string())),
+            Some(path.clone())
+        );
+        fs::remove_file(&path).unwrap();
+    }
 }
@@ -5,4 +5,4 @@ pub mod loader;
 pub mod types;
 
 pub use loader::load_config;
-pub use types::{Config, PhysicsConfig};
+pub use types::{Config, KeyBindings, PhysicsConfig};
@@ -3,7 +3,14 @@
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+use crate::debug::Level;
+use crate::game::physics::{PaddleMode, ServePolicy};
+use crate::game::state::Obstacle;
+use crate::game::{OpposingKeys, Player};
+use crate::network::NetMode;
+use crate::ui::overlay::OverlayPosition;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct Config {
     #[serde(default)]
     pub keybindings: KeyBindings,
@@ -15,43 +22,135 @@ pub struct Config {
     pub display: DisplayConfig,
     #[serde(default)]
     pub network: NetworkConfig,
-}
-
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            keybindings: KeyBindings::default(),
-            physics: PhysicsConfig::default(),
-            ai: AIConfig::default(),
-            display: DisplayConfig::default(),
-            network: NetworkConfig::default(),
-        }
-    }
+    #[serde(default)]
+    pub replay: ReplayConfig,
+    #[serde(default)]
+    pub logging: LoggingConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct KeyBindings {
     // Player paddle controls (single-player modes: AI, Network)
     pub player_paddle_up: String,
     pub player_paddle_down: String,
+    #[serde(default = "default_player_catch")]
+    pub player_catch: String, // Only used when physics.catch_enabled
 
     // Left paddle controls (local 2-player mode - left player)
     pub left_paddle_up: String,
     pub left_paddle_down: String,
+    #[serde(default = "default_left_catch")]
+    pub left_catch: String,
 
     // Right paddle controls (local 2-player mode - right player)
     pub right_paddle_up: String,
     pub right_paddle_down: String,
+    #[serde(default = "default_right_catch")]
+    pub right_catch: String,
+
+    // Local 2-player: swap which keys move which paddle, for switching seats mid-match
+    #[serde(default = "default_swap_sides")]
+    pub swap_sides: String,
 
     // Game controls
     pub quit: String,
-    pub pause: String, // Future: pause functionality
+    pub pause: String, // Local and vs-AI games only (networked play never pauses)
+    #[serde(default = "default_rematch")]
+    pub rematch: String,
+    // Networked games: save the match's decisive point from the game-over screen
+    #[serde(default = "default_save_highlight")]
+    pub save_highlight: String,
+    // Networked games: open the chat prompt
+    #[serde(default = "default_chat")]
+    pub chat: String,
+    // Networked games: show the host's peer ID and link quality
+    #[serde(default = "default_connection_info")]
+    pub connection_info: String,
+    // Practice: change the ball speed
+    #[serde(default = "default_ball_faster")]
+    pub ball_faster: String,
+    #[serde(default = "default_ball_slower")]
+    pub ball_slower: String,
+    // Practice and unranked local games: set up the score for a drill
+    #[serde(default = "default_left_score_down")]
+    pub left_score_down: String,
+    #[serde(default = "default_left_score_up")]
+    pub left_score_up: String,
+    #[serde(default = "default_right_score_down")]
+    pub right_score_down: String,
+    #[serde(default = "default_right_score_up")]
+    pub right_score_up: String,
 
     // Menu controls
     pub menu_up: String,
     pub menu_down: String,
     pub menu_select: String,
     pub menu_back: String,
+
+    // Simultaneous up+down on one paddle: "both" (each tap applied in order),
+    // "last-wins" or "neutral" (cancel out)
+    #[serde(default)]
+    pub opposing_keys: OpposingKeys,
+
+    // Steer your paddle toward the mouse pointer (vs-AI and networked games)
+    pub mouse_control: bool,
+}
+
+fn default_player_catch() -> String {
+    "D".to_string()
+}
+
+fn default_left_catch() -> String {
+    "D".to_string()
+}
+
+fn default_right_catch() -> String {
+    "Left".to_string()
+}
+
+fn default_swap_sides() -> String {
+    "Tab".to_string()
+}
+
+fn default_rematch() -> String {
+    "R".to_string()
+}
+
+fn default_save_highlight() -> String {
+    "V".to_string()
+}
+
+fn default_chat() -> String {
+    "T".to_string()
+}
+
+fn default_connection_info() -> String {
+    "I".to_string()
+}
+
+fn default_ball_faster() -> String {
+    "+".to_string()
+}
+
+fn default_ball_slower() -> String {
+    "-".to_string()
+}
+
+fn default_left_score_down() -> String {
+    "1".to_string()
+}
+
+fn default_left_score_up() -> String {
+    "2".to_string()
+}
+
+fn default_right_score_down() -> String {
+    "9".to_string()
+}
+
+fn default_right_score_up() -> String {
+    "0".to_string()
 }
 
 impl Default for KeyBindings {
@@ -59,16 +158,32 @@ impl Default for KeyBindings {
         Self {
             player_paddle_up: "W".to_string(),
             player_paddle_down: "S".to_string(),
+            player_catch: default_player_catch(),
             left_paddle_up: "W".to_string(),
             left_paddle_down: "S".to_string(),
+            left_catch: default_left_catch(),
             right_paddle_up: "Up".to_string(),
             right_paddle_down: "Down".to_string(),
+            right_catch: default_right_catch(),
+            swap_sides: default_swap_sides(),
             quit: "Q".to_string(),
-            pause: "P".to_string(),
+            pause: "Space".to_string(),
+            rematch: default_rematch(),
+            save_highlight: default_save_highlight(),
+            chat: default_chat(),
+            connection_info: default_connection_info(),
+            ball_faster: default_ball_faster(),
+            ball_slower: default_ball_slower(),
+            left_score_down: default_left_score_down(),
+            left_score_up: default_left_score_up(),
+            right_score_down: default_right_score_down(),
+            right_score_up: default_right_score_up(),
             menu_up: "Up".to_string(),
             menu_down: "Down".to_string(),
             menu_select: "Enter".to_string(),
             menu_back: "Esc".to_string(),
+            opposing_keys: OpposingKeys::Both,
+            mouse_control: false,
         }
     }
 }
@@ -76,7 +191,8 @@ impl Default for KeyBindings {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct PhysicsConfig {
-    // Ball speed in virtual units per second
+    // Serve (launch) speed in virtual units per second, applied on every serve.
+    // Independent of ball_speed_multiplier, which only scales speed on paddle hits.
     pub ball_initial_speed: f32,
 
     // Paddle height in virtual units
@@ -85,15 +201,79 @@ pub struct PhysicsConfig {
     // Paddle movement distance per tap
     pub paddle_tap_distance: f32,
 
+    // Paddle control: "tap" (fixed jump per key press) or "smooth" (each press
+    // accelerates the paddle, which glides and slows down; holding a key uses key-repeat)
+    pub paddle_mode: PaddleMode,
+
+    // Seed for reproducible serves: when set, each serve gets a small random tilt drawn
+    // from this seed, so the same seed always replays the same serves. Unset = flat serves.
+    // In networked games the host's seed is used by both peers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub serve_seed: Option<u64>,
+
+    // Who gets served to, at the start of a game and after each goal: "snake" (tennis-style:
+    // left once, then two serves to each side in turn), "toward_loser" (the player just
+    // scored on), "alternate" (switch every point), "left" or "right" (always that side).
+    // In networked games the host's policy is used by both peers.
+    pub serve_policy: ServePolicy,
+
     // Score required to win
     pub winning_score: u8,
 
+    // Deuce rule: a game can only be won with a two-point lead
+    pub win_by_two: bool,
+
+    // Timed games: when set, a game also ends after this many seconds of play and the higher
+    // score wins; a tie goes to sudden death (next goal wins). Unset = no time limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_limit_secs: Option<u64>,
+
+    // Match length: the first player to win this many games takes the match (1 = single games).
+    // In networked games the host's match length is used by both peers.
+    pub games_to_win: u8,
+
     // Ball speed increase multiplier on paddle hit (1.1 = 10% increase)
     pub ball_speed_multiplier: f32,
 
+    // Serve at ball_initial_speed after every goal (true), or carry the rally's speed
+    // over to the next serve so the ball keeps accelerating until the game ends (false)
+    pub reset_speed_each_round: bool,
+
+    // Rally speed cap in virtual units per second (never above the 4000 hard limit)
+    pub max_ball_speed: f32,
+
+    // Multi-ball: balls in play at once (1-4); any ball crossing a goal line scores and
+    // re-serves them all. The catch variant only works with a single ball.
+    pub ball_count: u8,
+
+    // Spin: share of the paddle's vertical speed passed on to the ball on a hit, so hitting
+    // while moving steepens the bounce in that direction (0 = hit position only)
+    pub spin_factor: f32,
+
     // Virtual field dimensions (changing these affects game feel)
     pub virtual_width: f32,
     pub virtual_height: f32,
+
+    // Blocks in the field that balls bounce off, as [[physics.obstacles]] tables with
+    // x, y (top-left corner), width and height in virtual units. Blocks reaching into
+    // the paddle lanes, outside the field or over the serve spot in the center are ignored.
+    pub obstacles: Vec<Obstacle>,
+
+    // Power-ups: pickups appear in the middle of the field every few seconds, and the
+    // player who sends a ball through one gets its effect (their paddle grows, the
+    // opponent's shrinks, or the ball speeds up)
+    pub powerups: bool,
+
+    // Physics ticks per second, independent of display.target_fps (10-240).
+    // In networked games the host's tick rate is used by both peers.
+    pub tick_rate_hz: u32,
+
+    // Catch variant: press the catch key just before contact to hold the ball on the paddle,
+    // move the paddle to aim, press again (or wait out the hold limit) to release
+    pub catch_enabled: bool,
+
+    // Longest a caught ball can be held before it auto-releases, in seconds
+    pub catch_max_hold_secs: f32,
 }
 
 impl Default for PhysicsConfig {
@@ -102,10 +282,25 @@ impl Default for PhysicsConfig {
             ball_initial_speed: 600.0,
             paddle_height: 90.0,
             paddle_tap_distance: 40.0,
+            paddle_mode: PaddleMode::Tap,
+            serve_seed: None,
+            serve_policy: ServePolicy::Snake,
             winning_score: 5,
+            win_by_two: false,
+            time_limit_secs: None,
+            games_to_win: 1,
             ball_speed_multiplier: 1.1,
+            reset_speed_each_round: true,
+            max_ball_speed: 4000.0,
+            spin_factor: 0.25,
+            ball_count: 1,
             virtual_width: 1200.0,
             virtual_height: 600.0,
+            obstacles: Vec::new(),
+            powerups: false,
+            tick_rate_hz: 60,
+            catch_enabled: false,
+            catch_max_hold_secs: 1.0,
         }
     }
 }
@@ -113,22 +308,50 @@ impl Default for PhysicsConfig {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct AIConfig {
-    // AI difficulty: "easy", "medium", "hard"
+    // Bot preselected when picking an opponent: "easy", "hard", "adaptive", "offensive",
+    // "custom" or "backboard"
     pub difficulty: String,
 
-    // AI reaction delay in milliseconds (higher = easier to beat)
-    pub reaction_delay_ms: u64,
+    // Fastest the Backboard bot (and the practice wall) moves its paddle, in virtual
+    // units per second, so a fast enough ball gets past it (0 = instant, unbeatable;
+    // e.g. 900 for a wall that can be beaten)
+    pub backboard_max_paddle_speed: f32,
 
-    // AI prediction error (0.0 = perfect, 1.0 = very inaccurate)
-    pub prediction_error: f32,
+    // Tuning for the Custom bot
+    pub custom: CustomBotConfig,
 }
 
 impl Default for AIConfig {
     fn default() -> Self {
         Self {
-            difficulty: "medium".to_string(),
-            reaction_delay_ms: 100,
-            prediction_error: 0.2,
+            difficulty: "easy".to_string(),
+            backboard_max_paddle_speed: 0.0,
+            custom: CustomBotConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CustomBotConfig {
+    // Standard deviation of where the bot thinks the ball will arrive, in virtual units
+    // (Easy uses 35, Hard 18; 0 = perfect)
+    pub error_stddev: f32,
+
+    // Chance (0.0 to 1.0) the bot gives up on a shot entirely (Easy 0.12, Hard 0.05)
+    pub miss_rate: f32,
+
+    // Milliseconds between the bot's moves (Easy 200, Hard 120)
+    pub reaction_delay_ms: u64,
+}
+
+impl Default for CustomBotConfig {
+    fn default() -> Self {
+        // Halfway between Easy and Hard
+        Self {
+            error_stddev: 26.0,
+            miss_rate: 0.08,
+            reaction_delay_ms: 160,
         }
     }
 }
@@ -136,7 +359,8 @@ impl Default for AIConfig {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct DisplayConfig {
-    // Target frames per second
+    // Target frames per second (10-240). Only the render rate: physics runs at
+    // physics.tick_rate_hz whatever this is set to.
     pub target_fps: u64,
 
     // Score display color (RGB values 0-255)
@@ -150,6 +374,45 @@ pub struct DisplayConfig {
 
     // Center line color
     pub center_line_color: [u8; 3],
+
+    // Dim the ball on the network client while it is only dead-reckoned (no recent sync)
+    pub show_prediction_indicator: bool,
+
+    // In-game overlay placement: "center", "top" or "bottom"
+    pub overlay_position: OverlayPosition,
+
+    // Darken the game behind in-game overlays
+    pub overlay_dim_background: bool,
+
+    // Glow a marker at each goal as the ball approaches it
+    pub show_danger_zone: bool,
+
+    // Draw a fading trail behind the ball
+    pub ball_trail: bool,
+
+    // Show an averaged FPS counter in the top-left corner
+    pub show_fps: bool,
+
+    // Game field colors ([display.theme])
+    pub theme: ThemeConfig,
+
+    // Show the host's peer ID as a QR code while waiting for a connection
+    // (turn off if your terminal renders Braille poorly)
+    pub show_peer_qr: bool,
+
+    // Ring the terminal bell on paddle hits, wall bounces and goals
+    pub sound_enabled: bool,
+
+    // Keep the field's 2:1 shape with margins instead of stretching it to the terminal
+    pub preserve_aspect: bool,
+
+    // Assist mode for beginners: dot the path the ball will take to your paddle
+    // (local, practice and vs-AI games only; never shown in networked play)
+    pub assist: bool,
+
+    // Slow the game down for a moment after each goal, before the next serve (local,
+    // practice and vs-AI games; networked games always run at full speed)
+    pub goal_slow_motion: bool,
 }
 
 impl Default for DisplayConfig {
@@ -160,17 +423,102 @@ impl Default for DisplayConfig {
             paddle_color: [255, 255, 255],      // White
             ball_color: [255, 255, 255],        // White
             center_line_color: [100, 100, 100], // Gray
+            show_prediction_indicator: false,
+            overlay_position: OverlayPosition::Center,
+            overlay_dim_background: false,
+            show_danger_zone: false,
+            ball_trail: false,
+            show_fps: false,
+            theme: ThemeConfig::default(),
+            show_peer_qr: true,
+            sound_enabled: false,
+            preserve_aspect: false,
+            assist: false,
+            goal_slow_motion: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    // Built-in theme: "classic", "amber" or "matrix" (unknown names use classic, with a
+    // warning at startup)
+    pub name: String,
+
+    // Optional per-color overrides as hex strings, e.g. "#00ffff"
+    // (values that don't parse are ignored, with a warning at startup)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ball_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left_paddle_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right_paddle_color: Option<String>,
+    // Your paddle and your opponent's in AI and network games (left/right are
+    // used in local 2-player)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub your_paddle_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opponent_paddle_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+    // Goal danger markers (display.show_danger_zone)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub danger_zone_color: Option<String>,
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            name: "classic".to_string(),
+            ball_color: None,
+            left_paddle_color: None,
+            right_paddle_color: None,
+            your_paddle_color: None,
+            opponent_paddle_color: None,
+            border_color: None,
+            background_color: None,
+            danger_zone_color: None,
         }
     }
 }
 
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ReplayConfig {
+    // Record local and vs-AI games to last_replay.p2r next to config.toml
+    // (watch with `p2pong --replay <file>`)
+    pub record: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    // Write a debug log (the same as starting with --debug)
+    pub enabled: bool,
+
+    // Least severe messages kept: "error", "warn", "info" or "debug"
+    pub level: Level,
+
+    // Log file (default: p2pong-debug-<pid>.log in the OS temp directory, one per running
+    // instance; the path in use is printed at startup)
+    pub path: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct NetworkConfig {
-    // Signaling server WebSocket URL
+    // Signaling server WebSocket URL ("ws://" or "wss://"; others fall back to the default)
     pub signaling_server: String,
 
-    // Network sync interval in frames (default: 3 frames = ~50ms @ 60fps)
+    // Only connect to the signaling server over TLS: a ws:// URL is refused instead of
+    // sending peer IDs and connection details unencrypted (wss:// certificates are
+    // always verified)
+    pub require_tls: bool,
+
+    // Backup ball sync interval in physics ticks (default: 3 ticks = ~50ms @ 60Hz)
     pub backup_sync_interval: u64,
 
     // Connection timeout in seconds
@@ -178,15 +526,70 @@ pub struct NetworkConfig {
 
     // Heartbeat interval in milliseconds
     pub heartbeat_interval_ms: u64,
+
+    // How long to wait for ICE to recover after the connection drops before
+    // ending the match (0 = end immediately)
+    pub reconnect_timeout_secs: u64,
+
+    // Roughly how long the peer can go silent before ICE calls the connection dropped
+    // (0 = WebRTC default, about 8 seconds). Raise it on flaky links.
+    pub ice_disconnect_timeout_secs: u64,
+
+    // End a networked session after this many minutes without input from either
+    // player, e.g. both away at the game-over screen (0 = never)
+    pub idle_timeout_mins: u64,
+
+    // Client only: how far the opponent's paddle moves toward where their taps put it each
+    // 60th of a second, as a share of the remaining distance (1.0 = jump straight there).
+    // Lower is smoother but lags more. Smooth paddle mode already glides and isn't affected.
+    pub remote_paddle_smoothing: f32,
+
+    // Which paddle the host controls: "left" or "right" (client takes the other)
+    pub host_side: Player,
+
+    // Host only: let others watch the match by joining with your peer ID (up to 8)
+    pub allow_spectators: bool,
+
+    // Sync model, chosen by the host: "host_authoritative" (host simulates, client
+    // smooths ball updates) or "lockstep" (both simulate, only inputs are exchanged;
+    // smoother for the client but adds a few frames of input delay for both players)
+    pub net_mode: NetMode,
+
+    // ICE servers as [[network.ice_servers]] tables; empty = Cloudflare public STUN.
+    // URLs must start with "stun:" (others are dropped at load; TURN relays are not
+    // supported yet).
+    pub ice_servers: Vec<IceServerConfig>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct IceServerConfig {
+    // e.g. ["stun:stun.example.com:3478"]
+    pub urls: Vec<String>,
+
+    // TURN credentials (kept for when TURN is supported)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential: Option<String>,
 }
 
 impl Default for NetworkConfig {
     fn default() -> Self {
         Self {
             signaling_server: "wss://p2pong-production.up.railway.app".to_string(),
+            require_tls: false,
             backup_sync_interval: 3,
             connection_timeout_secs: 300, // 5 minutes - plenty of time for STUN/ICE negotiation
             heartbeat_interval_ms: 2000,
+            reconnect_timeout_secs: 10,
+            ice_disconnect_timeout_secs: 0,
+            idle_timeout_mins: 15,
+            remote_paddle_smoothing: 0.5,
+            host_side: Player::Left,
+            allow_spectators: false,
+            net_mode: NetMode::HostAuthoritative,
+            ice_servers: Vec::new(),
         }
     }
 }
@@ -1,60 +1,136 @@
 // Debug logging module for P2Pong
-// Provides file-based logging that can be enabled via --debug flag
+// Provides leveled, file-based logging that can be enabled via --debug or [logging] in config
 
+use serde::{Deserialize, Serialize};
 use std::fs::OpenOptions;
 use std::io::{self, Write};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use std::time::SystemTime;
 
-// Global flag to track whether debug logging is enabled
-static DEBUG_ENABLED: AtomicBool = AtomicBool::new(false);
+use crate::config::types::LoggingConfig;
 
-const LOG_FILE_PATH: &str = "/tmp/p2pong-debug.log";
+/// Log file used when [logging] doesn't set a path (in the OS temp directory);
+/// named per process, so instances running side by side don't clobber each other
+fn default_log_file() -> String {
+    format!("p2pong-debug-{}.log", std::process::id())
+}
+
+/// Severity of a log message; a logger set to a level keeps that level and everything
+/// more severe
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    #[default]
+    Debug,
+}
+
+impl Level {
+    fn label(self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+        }
+    }
+}
+
+/// Where and how much to log (set once by init; unset means logging is off)
+struct Logger {
+    path: PathBuf,
+    level: Level,
+}
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+/// The log file for `config`: its path if set, otherwise the default in the OS temp directory
+pub fn log_path(config: &LoggingConfig) -> PathBuf {
+    config
+        .path
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| std::env::temp_dir().join(default_log_file()))
+}
+
+/// The file being logged to, if logging is on
+pub fn active_path() -> Option<&'static Path> {
+    LOGGER.get().map(|logger| logger.path.as_path())
+}
 
 /// Initialize debug logging to file
 ///
 /// # Arguments
-/// * `enabled` - Whether debug logging should be enabled (controlled by --debug flag)
+/// * `config` - The [logging] settings (path and level)
+/// * `enabled` - Whether logging should be enabled (--debug flag or logging.enabled)
 ///
 /// # Behavior
-/// - Stores enabled state globally for log() to check
-/// - If enabled=false: Returns immediately, no file created
-/// - If enabled=true: Creates/truncates log file and writes header
-pub fn init(enabled: bool) -> io::Result<()> {
-    // Store the enabled state globally
-    DEBUG_ENABLED.store(enabled, Ordering::Relaxed);
-
+/// - If enabled=false: Returns immediately, no file created, log() stays a no-op
+/// - If enabled=true: Creates/truncates the log file and writes a header
+pub fn init(config: &LoggingConfig, enabled: bool) -> io::Result<()> {
     if !enabled {
         return Ok(());
     }
 
+    let path = log_path(config);
     let mut file = OpenOptions::new()
         .create(true)
         .write(true)
         .truncate(true)
-        .open(LOG_FILE_PATH)?;
+        .open(&path)?;
 
     writeln!(file, "=== P2Pong Debug Log ===")?;
     writeln!(file, "Session started: {:?}", SystemTime::now())?;
-    writeln!(file, "To monitor: tail -f {}", LOG_FILE_PATH)?;
+    writeln!(file, "Level: {}", config.level.label())?;
+    writeln!(file, "To monitor: tail -f {}", path.display())?;
     writeln!(file, "========================================\n")?;
 
+    let _ = LOGGER.set(Logger {
+        path,
+        level: config.level,
+    });
     Ok(())
 }
 
-/// Log a debug message to file
+/// Log a debug-level message to file
 ///
 /// # Arguments
 /// * `category` - Log category (e.g., "GAME_START", "NETWORK", "WEBRTC")
 /// * `message` - Log message content
+pub fn log(category: &str, message: &str) {
+    log_at(Level::Debug, category, message);
+}
+
+/// Log an info-level message (see `log`)
+pub fn info(category: &str, message: &str) {
+    log_at(Level::Info, category, message);
+}
+
+/// Log a warning (see `log`)
+pub fn warn(category: &str, message: &str) {
+    log_at(Level::Warn, category, message);
+}
+
+/// Log an error (see `log`)
+pub fn error(category: &str, message: &str) {
+    log_at(Level::Error, category, message);
+}
+
+/// Log a message at `level`
 ///
 /// # Behavior
-/// - If debug not enabled: Returns immediately (no-op)
-/// - If enabled: Appends to log file with format: [timestamp] [CATEGORY] message
+/// - If logging is off, or `level` is less severe than the configured one: no-op
+/// - Otherwise: Appends to the log file with format: [timestamp] [LEVEL] [CATEGORY] message
 /// - Thread-safe through file system append operation
-pub fn log(category: &str, message: &str) {
-    // Early return if debug logging is not enabled
-    if !DEBUG_ENABLED.load(Ordering::Relaxed) {
+pub fn log_at(level: Level, category: &str, message: &str) {
+    // Early return if logging is off or filtered out
+    let Some(logger) = LOGGER.get() else {
+        return;
+    };
+    if level > logger.level {
         return;
     }
 
@@ -66,8 +142,45 @@ pub fn log(category: &str, message: &str) {
     if let Ok(mut file) = OpenOptions::new()
         .create(true)
         .append(true)
-        .open(LOG_FILE_PATH)
+        .open(&logger.path)
     {
-        let _ = writeln!(file, "[{:013}] [{}] {}", timestamp, category, message);
+        let _ = writeln!(file, "{}", format_line(timestamp, level, category, message));
+    }
+}
+
+fn format_line(timestamp: u128, level: Level, category: &str, message: &str) -> String {
+    format!(
+        "[{:013}] [{}] [{}] {}",
+        timestamp,
+        level.label(),
+        category,
+        message
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_and_log_path() {
+        // Configured level keeps itself and anything more severe
+        assert!(Level::Error < Level::Warn && Level::Info < Level::Debug);
+        assert_eq!(
+            format_line(42, Level::Warn, "NET", "slow"),
+            "[0000000000042] [WARN] [NET] slow"
+        );
+
+        let mut config = LoggingConfig::default();
+        let default = log_path(&config);
+        assert_eq!(default.parent(), Some(std::env::temp_dir().as_path()));
+        assert!(default
+            .to_string_lossy()
+            .ends_with(&format!("-{}.log", std::process::id())));
+        config.path = Some("/var/log/p2pong.log".to_string());
+        assert_eq!(log_path(&config), PathBuf::from("/var/log/p2pong.log"));
+
+        let parsed: LoggingConfig = toml::from_str("level = \"warn\"").unwrap();
+        assert_eq!(parsed.level, Level::Warn);
     }
 }
@@ -0,0 +1,25 @@
+// Crate-wide error type for P2Pong
+// Keeps terminal/file I/O failures apart from network, config and clipboard ones,
+// so callers (main in particular) can react to each differently
+
+use std::io;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum P2PongError {
+    /// Terminal or file I/O
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    /// The connection or the network thread failed
+    #[error("Network error: {0}")]
+    Network(String),
+
+    /// The config file couldn't be read or written
+    #[error("Config error: {0}")]
+    Config(String),
+
+    /// The terminal refused the clipboard sequence
+    #[error("Clipboard error: {0}")]
+    Clipboard(#[source] io::Error),
+}
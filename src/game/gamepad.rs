@@ -0,0 +1,126 @@
+// Controller input (built with --features gamepad)
+// Polled alongside the keyboard by the poll_input_* functions. Left stick and D-pad move
+// the paddle, South (A / Cross) asks for a rematch, Start or Select quits.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use gilrs::{Axis, Button, EventType, Gamepad, GamepadId, Gilrs};
+
+use super::input::InputAction;
+use super::state::Player;
+use crate::debug;
+
+/// Stick deflection needed to move the paddle
+const STICK_DEADZONE: f32 = 0.5;
+
+/// Paddle moves repeat at this interval while the stick or D-pad is held (like key repeat)
+const REPEAT_INTERVAL: Duration = Duration::from_millis(40);
+
+#[derive(Clone, Copy, PartialEq)]
+enum Direction {
+    Up,
+    Down,
+}
+
+struct Gamepads {
+    gilrs: Gilrs,
+    /// Direction each pad is holding and when it last moved the paddle
+    held: HashMap<GamepadId, (Direction, Instant)>,
+}
+
+thread_local! {
+    // Opened on first poll; None if the platform has no gamepad support
+    static GAMEPADS: RefCell<Option<Gamepads>> = RefCell::new(open());
+}
+
+fn open() -> Option<Gamepads> {
+    match Gilrs::new() {
+        Ok(gilrs) => Some(Gamepads {
+            gilrs,
+            held: HashMap::new(),
+        }),
+        Err(e) => {
+            debug::log("GAMEPAD", &format!("Gamepad support unavailable: {}", e));
+            None
+        }
+    }
+}
+
+/// Actions from all connected gamepads. Pads are assigned to `sides` in connection
+/// order, wrapping around (so with one side, every pad controls it).
+pub fn poll(sides: &[Player]) -> Vec<InputAction> {
+    GAMEPADS.with(|gamepads| {
+        gamepads
+            .borrow_mut()
+            .as_mut()
+            .map(|gamepads| gamepads.poll(sides))
+            .unwrap_or_default()
+    })
+}
+
+impl Gamepads {
+    fn poll(&mut self, sides: &[Player]) -> Vec<InputAction> {
+        let mut actions = Vec::new();
+
+        // Events also keep gilrs' cached pad state current
+        while let Some(event) = self.gilrs.next_event() {
+            match event.event {
+                EventType::Connected => debug::log(
+                    "GAMEPAD",
+                    &format!("Connected: {}", self.gilrs.gamepad(event.id).name()),
+                ),
+                EventType::Disconnected => {
+                    self.held.remove(&event.id);
+                    debug::log("GAMEPAD", &format!("Disconnected: {}", event.id));
+                }
+                EventType::ButtonPressed(Button::South, _) => actions.push(InputAction::Rematch),
+                EventType::ButtonPressed(Button::Start | Button::Select, _) => {
+                    actions.push(InputAction::Quit)
+                }
+                _ => {}
+            }
+        }
+
+        let now = Instant::now();
+        for (index, (id, pad)) in self.gilrs.gamepads().enumerate() {
+            let Some(direction) = held_direction(&pad) else {
+                self.held.remove(&id);
+                continue;
+            };
+
+            // Move at once on a fresh press, then at the repeat interval
+            let due = match self.held.get(&id) {
+                Some(&(held, since)) if held == direction => now >= since + REPEAT_INTERVAL,
+                _ => true,
+            };
+            if due {
+                self.held.insert(id, (direction, now));
+                actions.push(paddle_action(sides[index % sides.len()], direction));
+            }
+        }
+
+        actions
+    }
+}
+
+fn held_direction(pad: &Gamepad) -> Option<Direction> {
+    let stick = pad.value(Axis::LeftStickY); // Positive is up
+    if pad.is_pressed(Button::DPadUp) || stick > STICK_DEADZONE {
+        Some(Direction::Up)
+    } else if pad.is_pressed(Button::DPadDown) || stick < -STICK_DEADZONE {
+        Some(Direction::Down)
+    } else {
+        None
+    }
+}
+
+fn paddle_action(side: Player, direction: Direction) -> InputAction {
+    match (side, direction) {
+        (Player::Left, Direction::Up) => InputAction::LeftPaddleUp,
+        (Player::Left, Direction::Down) => InputAction::LeftPaddleDown,
+        (Player::Right, Direction::Up) => InputAction::RightPaddleUp,
+        (Player::Right, Direction::Down) => InputAction::RightPaddleDown,
+    }
+}
@@ -1,8 +1,9 @@
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, MouseEvent, MouseEventKind};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use crate::config::Config;
+use super::state::Player;
+use crate::config::{Config, KeyBindings};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InputAction {
@@ -12,18 +13,94 @@ pub enum InputAction {
     LeftPaddleDown,
     RightPaddleUp,
     RightPaddleDown,
+    LeftCatch,
+    RightCatch,
+    ToggleHelp,
+    Pause,
+    OpenChat,
+    BallFaster,
+    BallSlower,
+    SwapSides,
+    LeftScoreUp,
+    LeftScoreDown,
+    RightScoreUp,
+    RightScoreDown,
+    ToggleConnectionInfo,
+    SaveHighlight,
 }
 
-/// Parse a key binding string (e.g., "W", "Up", "Esc") into a KeyCode
-fn parse_key_binding(key_str: &str) -> Option<KeyCode> {
-    match key_str.to_lowercase().as_str() {
-        "w" => Some(KeyCode::Char('w')),
-        "s" => Some(KeyCode::Char('s')),
-        "a" => Some(KeyCode::Char('a')),
-        "d" => Some(KeyCode::Char('d')),
-        "q" => Some(KeyCode::Char('q')),
-        "r" => Some(KeyCode::Char('r')),
-        "p" => Some(KeyCode::Char('p')),
+impl InputAction {
+    /// The same action for the other paddle (other actions are unchanged)
+    pub fn mirrored(self) -> Self {
+        match self {
+            InputAction::LeftPaddleUp => InputAction::RightPaddleUp,
+            InputAction::LeftPaddleDown => InputAction::RightPaddleDown,
+            InputAction::RightPaddleUp => InputAction::LeftPaddleUp,
+            InputAction::RightPaddleDown => InputAction::LeftPaddleDown,
+            InputAction::LeftCatch => InputAction::RightCatch,
+            InputAction::RightCatch => InputAction::LeftCatch,
+            other => other,
+        }
+    }
+
+    /// Whether this is one of the score adjustment keys (practice and local drills only)
+    pub fn adjusts_score(self) -> bool {
+        matches!(
+            self,
+            InputAction::LeftScoreUp
+                | InputAction::LeftScoreDown
+                | InputAction::RightScoreUp
+                | InputAction::RightScoreDown
+        )
+    }
+}
+
+/// How to resolve up and down taps for the same paddle arriving in one poll
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OpposingKeys {
+    /// Every tap is applied in order, as if pressed one after the other
+    #[default]
+    Both,
+    /// The most recently pressed direction wins
+    LastWins,
+    /// Opposing directions cancel out (no movement)
+    Neutral,
+}
+
+/// Resolve simultaneous up+down presses per paddle according to `mode`
+fn resolve_opposing_keys(actions: Vec<InputAction>, mode: OpposingKeys) -> Vec<InputAction> {
+    let pairs = [
+        (InputAction::LeftPaddleUp, InputAction::LeftPaddleDown),
+        (InputAction::RightPaddleUp, InputAction::RightPaddleDown),
+    ];
+
+    let mut resolved = actions;
+    for (up, down) in pairs {
+        if !(resolved.contains(&up) && resolved.contains(&down)) {
+            continue;
+        }
+        match mode {
+            OpposingKeys::Both => {}
+            OpposingKeys::LastWins => {
+                let last = *resolved
+                    .iter()
+                    .rev()
+                    .find(|a| **a == up || **a == down)
+                    .unwrap();
+                resolved.retain(|a| (*a != up && *a != down) || *a == last);
+            }
+            OpposingKeys::Neutral => resolved.retain(|a| *a != up && *a != down),
+        }
+    }
+    resolved
+}
+
+/// Parse a key binding string (e.g., "W", "Up", "Esc") into a KeyCode.
+/// Any single character is accepted; letters are stored lowercase.
+pub fn parse_key_binding(key_str: &str) -> Option<KeyCode> {
+    let key = key_str.to_lowercase();
+    match key.as_str() {
         "up" => Some(KeyCode::Up),
         "down" => Some(KeyCode::Down),
         "left" => Some(KeyCode::Left),
@@ -31,115 +108,607 @@ fn parse_key_binding(key_str: &str) -> Option<KeyCode> {
         "esc" | "escape" => Some(KeyCode::Esc),
         "enter" => Some(KeyCode::Enter),
         "space" => Some(KeyCode::Char(' ')),
-        _ => None,
+        "tab" => Some(KeyCode::Tab),
+        "backspace" => Some(KeyCode::Backspace),
+        _ => {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(KeyCode::Char(c)),
+                _ => None,
+            }
+        }
     }
 }
 
-/// Check if a KeyCode matches a config key binding string
+/// Check if a KeyCode matches a config key binding string (letters ignore Shift/Caps Lock)
 fn matches_key(code: &KeyCode, binding: &str) -> bool {
-    parse_key_binding(binding) == Some(*code)
+    let code = match code {
+        KeyCode::Char(c) => KeyCode::Char(c.to_lowercase().next().unwrap_or(*c)),
+        other => *other,
+    };
+    parse_key_binding(binding) == Some(code)
 }
 
-/// Poll input for local 2-player mode (asymmetric controls)
-pub fn poll_input_local_2p(config: &Config) -> Result<Vec<InputAction>, std::io::Error> {
-    let bindings = &config.keybindings;
-    let mut actions = Vec::new();
-
-    while event::poll(Duration::from_millis(0))? {
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                // System actions
-                if matches_key(&key.code, &bindings.quit) || key.code == KeyCode::Esc {
-                    actions.push(InputAction::Quit);
-                }
+/// Keys with a fixed meaning in every mode, as (action, key) pairs
+const RESERVED_KEYS: [(&str, &str); 3] = [("quit", "Esc"), ("help", "?"), ("help", "H")];
 
-                // Rematch (always 'R' for now)
-                if key.code == KeyCode::Char('r') || key.code == KeyCode::Char('R') {
-                    actions.push(InputAction::Rematch);
-                }
+/// Problems with the configured key bindings: unknown key names, and keys
+/// bound to two different actions within the same game mode
+pub fn binding_problems(bindings: &KeyBindings) -> Vec<String> {
+    let shared = [
+        ("quit", bindings.quit.as_str()),
+        ("pause", bindings.pause.as_str()),
+        ("rematch", bindings.rematch.as_str()),
+    ];
+    let local_2p = [
+        ("left_paddle_up", bindings.left_paddle_up.as_str()),
+        ("left_paddle_down", bindings.left_paddle_down.as_str()),
+        ("left_catch", bindings.left_catch.as_str()),
+        ("right_paddle_up", bindings.right_paddle_up.as_str()),
+        ("right_paddle_down", bindings.right_paddle_down.as_str()),
+        ("right_catch", bindings.right_catch.as_str()),
+        ("swap_sides", bindings.swap_sides.as_str()),
+    ];
+    let single_player = [
+        ("player_paddle_up", bindings.player_paddle_up.as_str()),
+        ("player_paddle_down", bindings.player_paddle_down.as_str()),
+        ("player_catch", bindings.player_catch.as_str()),
+        ("save_highlight", bindings.save_highlight.as_str()),
+        ("chat", bindings.chat.as_str()),
+        ("connection_info", bindings.connection_info.as_str()),
+        ("ball_faster", bindings.ball_faster.as_str()),
+        ("ball_slower", bindings.ball_slower.as_str()),
+    ];
+    // Used in local games and practice, so they count with both groups
+    let score_keys = [
+        ("left_score_down", bindings.left_score_down.as_str()),
+        ("left_score_up", bindings.left_score_up.as_str()),
+        ("right_score_down", bindings.right_score_down.as_str()),
+        ("right_score_up", bindings.right_score_up.as_str()),
+    ];
 
-                // Left paddle
-                if matches_key(&key.code, &bindings.left_paddle_up) {
-                    actions.push(InputAction::LeftPaddleUp);
-                }
-                if matches_key(&key.code, &bindings.left_paddle_down) {
-                    actions.push(InputAction::LeftPaddleDown);
-                }
+    let mut problems = Vec::new();
+    let all = shared
+        .iter()
+        .chain(&local_2p)
+        .chain(&single_player)
+        .chain(&score_keys);
+    for (action, key) in all {
+        if parse_key_binding(key).is_none() {
+            problems.push(format!("{} = {:?} is not a known key", action, key));
+        }
+    }
 
-                // Right paddle
-                if matches_key(&key.code, &bindings.right_paddle_up) {
-                    actions.push(InputAction::RightPaddleUp);
-                }
-                if matches_key(&key.code, &bindings.right_paddle_down) {
-                    actions.push(InputAction::RightPaddleDown);
+    for group in [&local_2p[..], &single_player[..]] {
+        let bound: Vec<_> = RESERVED_KEYS
+            .iter()
+            .chain(&shared)
+            .chain(group)
+            .chain(&score_keys)
+            .collect();
+        for (i, (action, key)) in bound.iter().enumerate() {
+            let Some(code) = parse_key_binding(key) else {
+                continue;
+            };
+            for (other, other_key) in &bound[..i] {
+                if other != action && parse_key_binding(other_key) == Some(code) {
+                    let problem = format!("{:?} is bound to both {} and {}", key, other, action);
+                    if !problems.contains(&problem) {
+                        problems.push(problem);
+                    }
                 }
             }
         }
     }
+    problems
+}
 
-    Ok(actions)
+/// Remember the pointer row from a mouse move, drag or click
+fn track_mouse(mouse: MouseEvent, mouse_row: &mut Option<u16>) {
+    if matches!(
+        mouse.kind,
+        MouseEventKind::Moved | MouseEventKind::Drag(_) | MouseEventKind::Down(_)
+    ) {
+        *mouse_row = Some(mouse.row);
+    }
 }
 
-/// Poll input for single-player modes where player controls LEFT paddle
-pub fn poll_input_player_left(config: &Config) -> Result<Vec<InputAction>, std::io::Error> {
-    let bindings = &config.keybindings;
-    let mut actions = Vec::new();
+/// Score adjustment keys (only mapped for practice and local games, to set up a
+/// situation to drill; ranked local games ignore them)
+fn score_key(code: &KeyCode, bindings: &KeyBindings) -> Option<InputAction> {
+    [
+        (&bindings.left_score_down, InputAction::LeftScoreDown),
+        (&bindings.left_score_up, InputAction::LeftScoreUp),
+        (&bindings.right_score_down, InputAction::RightScoreDown),
+        (&bindings.right_score_up, InputAction::RightScoreUp),
+    ]
+    .into_iter()
+    .find(|(binding, _)| matches_key(code, binding))
+    .map(|(_, action)| action)
+}
 
+/// Drain the terminal events waiting right now, without blocking
+fn read_pending_events() -> Result<Vec<Event>, std::io::Error> {
+    let mut events = Vec::new();
     while event::poll(Duration::from_millis(0))? {
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                // System actions
-                if matches_key(&key.code, &bindings.quit) || key.code == KeyCode::Esc {
-                    actions.push(InputAction::Quit);
-                }
+        events.push(event::read()?);
+    }
+    Ok(events)
+}
 
-                // Rematch (always 'R' for now)
-                if key.code == KeyCode::Char('r') || key.code == KeyCode::Char('R') {
-                    actions.push(InputAction::Rematch);
-                }
+/// Key codes of the key presses among `events` (releases and repeats are ignored)
+fn pressed_keys(events: &[Event]) -> impl Iterator<Item = &KeyCode> {
+    events.iter().filter_map(|event| match event {
+        Event::Key(key) if key.kind == KeyEventKind::Press => Some(&key.code),
+        _ => None,
+    })
+}
 
-                // Player paddle (maps to LEFT paddle actions)
-                if matches_key(&key.code, &bindings.player_paddle_up) {
-                    actions.push(InputAction::LeftPaddleUp);
-                }
-                if matches_key(&key.code, &bindings.player_paddle_down) {
-                    actions.push(InputAction::LeftPaddleDown);
-                }
-            }
-        }
+/// Actions for one key press in local 2-player mode (asymmetric controls)
+fn local_2p_key_actions(code: &KeyCode, bindings: &KeyBindings) -> Vec<InputAction> {
+    let mut actions = Vec::new();
+
+    // System actions
+    if matches_key(code, &bindings.quit) || *code == KeyCode::Esc {
+        actions.push(InputAction::Quit);
     }
 
-    Ok(actions)
+    // Rematch
+    if matches_key(code, &bindings.rematch) {
+        actions.push(InputAction::Rematch);
+    }
+
+    // Help overlay (always '?' or 'H')
+    if matches!(code, KeyCode::Char('?' | 'h' | 'H')) {
+        actions.push(InputAction::ToggleHelp);
+    }
+
+    // Pause (ignored by networked games)
+    if matches_key(code, &bindings.pause) {
+        actions.push(InputAction::Pause);
+    }
+
+    if matches_key(code, &bindings.swap_sides) {
+        actions.push(InputAction::SwapSides);
+    }
+
+    actions.extend(score_key(code, bindings));
+
+    // Left paddle
+    if matches_key(code, &bindings.left_paddle_up) {
+        actions.push(InputAction::LeftPaddleUp);
+    }
+    if matches_key(code, &bindings.left_paddle_down) {
+        actions.push(InputAction::LeftPaddleDown);
+    }
+
+    // Right paddle
+    if matches_key(code, &bindings.right_paddle_up) {
+        actions.push(InputAction::RightPaddleUp);
+    }
+    if matches_key(code, &bindings.right_paddle_down) {
+        actions.push(InputAction::RightPaddleDown);
+    }
+
+    // Catch keys (ignored by physics unless catch is enabled)
+    if matches_key(code, &bindings.left_catch) {
+        actions.push(InputAction::LeftCatch);
+    }
+    if matches_key(code, &bindings.right_catch) {
+        actions.push(InputAction::RightCatch);
+    }
+
+    actions
 }
 
-/// Poll input for single-player modes where player controls RIGHT paddle
-pub fn poll_input_player_right(config: &Config) -> Result<Vec<InputAction>, std::io::Error> {
-    let bindings = &config.keybindings;
+/// Actions for one key press in single-player modes, with the player's paddle as the
+/// LEFT one (mirror them for a player on the right). The score keys are only mapped
+/// with `score_keys` (practice).
+fn player_key_actions(
+    code: &KeyCode,
+    bindings: &KeyBindings,
+    score_keys: bool,
+) -> Vec<InputAction> {
     let mut actions = Vec::new();
 
-    while event::poll(Duration::from_millis(0))? {
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                // System actions
-                if matches_key(&key.code, &bindings.quit) || key.code == KeyCode::Esc {
-                    actions.push(InputAction::Quit);
-                }
+    // System actions
+    if matches_key(code, &bindings.quit) || *code == KeyCode::Esc {
+        actions.push(InputAction::Quit);
+    }
 
-                // Rematch (always 'R' for now)
-                if key.code == KeyCode::Char('r') || key.code == KeyCode::Char('R') {
-                    actions.push(InputAction::Rematch);
-                }
+    // Rematch
+    if matches_key(code, &bindings.rematch) {
+        actions.push(InputAction::Rematch);
+    }
 
-                // Player paddle (maps to RIGHT paddle actions)
-                if matches_key(&key.code, &bindings.player_paddle_up) {
-                    actions.push(InputAction::RightPaddleUp);
-                }
-                if matches_key(&key.code, &bindings.player_paddle_down) {
-                    actions.push(InputAction::RightPaddleDown);
-                }
+    // Help overlay (always '?' or 'H')
+    if matches!(code, KeyCode::Char('?' | 'h' | 'H')) {
+        actions.push(InputAction::ToggleHelp);
+    }
+
+    // Pause (ignored by networked games)
+    if matches_key(code, &bindings.pause) {
+        actions.push(InputAction::Pause);
+    }
+
+    // Chat (only used by networked games)
+    if matches_key(code, &bindings.chat) {
+        actions.push(InputAction::OpenChat);
+    }
+
+    // Peer ID and connection info (only used by networked games)
+    if matches_key(code, &bindings.connection_info) {
+        actions.push(InputAction::ToggleConnectionInfo);
+    }
+
+    // Save the decisive point (only used by networked games)
+    if matches_key(code, &bindings.save_highlight) {
+        actions.push(InputAction::SaveHighlight);
+    }
+
+    // Ball speed (only used by practice)
+    if matches_key(code, &bindings.ball_faster) {
+        actions.push(InputAction::BallFaster);
+    }
+    if matches_key(code, &bindings.ball_slower) {
+        actions.push(InputAction::BallSlower);
+    }
+    if score_keys {
+        actions.extend(score_key(code, bindings));
+    }
+
+    // Player paddle
+    if matches_key(code, &bindings.player_paddle_up) {
+        actions.push(InputAction::LeftPaddleUp);
+    }
+    if matches_key(code, &bindings.player_paddle_down) {
+        actions.push(InputAction::LeftPaddleDown);
+    }
+    if matches_key(code, &bindings.player_catch) {
+        actions.push(InputAction::LeftCatch);
+    }
+
+    actions
+}
+
+/// Local 2-player actions for a batch of terminal events (before controller input and
+/// opposing-key resolution). With `swapped`, the left player's keys move the right
+/// paddle and vice versa.
+fn local_2p_actions(events: &[Event], bindings: &KeyBindings, swapped: bool) -> Vec<InputAction> {
+    let actions = pressed_keys(events).flat_map(|code| local_2p_key_actions(code, bindings));
+    if swapped {
+        actions.map(InputAction::mirrored).collect()
+    } else {
+        actions.collect()
+    }
+}
+
+/// Single-player actions for a batch of terminal events, for the player's paddle on
+/// `side` (before controller input and opposing-key resolution). With mouse_control
+/// enabled, `mouse_row` is updated to the latest pointer row.
+fn player_actions(
+    events: &[Event],
+    bindings: &KeyBindings,
+    side: Player,
+    score_keys: bool,
+    mouse_row: &mut Option<u16>,
+) -> Vec<InputAction> {
+    let mut actions = Vec::new();
+    for event in events {
+        match event {
+            Event::Mouse(mouse) if bindings.mouse_control => track_mouse(*mouse, mouse_row),
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                actions.extend(player_key_actions(&key.code, bindings, score_keys));
             }
+            _ => {}
         }
     }
+    if side == Player::Right {
+        actions = actions.into_iter().map(InputAction::mirrored).collect();
+    }
+    actions
+}
+
+/// Poll input for local 2-player mode (asymmetric controls)
+/// With `swapped`, the left player's keys move the right paddle and vice versa
+pub fn poll_input_local_2p(
+    config: &Config,
+    swapped: bool,
+) -> Result<Vec<InputAction>, std::io::Error> {
+    let bindings = &config.keybindings;
+    #[allow(unused_mut)] // Only extended with the gamepad feature
+    let mut actions = local_2p_actions(&read_pending_events()?, bindings, swapped);
+
+    // Controllers stay with their paddle (they move seats with the players)
+    #[cfg(feature = "gamepad")]
+    actions.extend(super::gamepad::poll(&[Player::Left, Player::Right]));
+
+    Ok(resolve_opposing_keys(actions, bindings.opposing_keys))
+}
+
+/// Poll input for single-player modes where player controls LEFT paddle.
+/// With mouse_control enabled, `mouse_row` is updated to the latest pointer row.
+pub fn poll_input_player_left(
+    config: &Config,
+    mouse_row: &mut Option<u16>,
+) -> Result<Vec<InputAction>, std::io::Error> {
+    poll_input_player(config, Player::Left, false, mouse_row)
+}
+
+/// Poll input for single-player modes where player controls RIGHT paddle.
+/// With mouse_control enabled, `mouse_row` is updated to the latest pointer row.
+pub fn poll_input_player_right(
+    config: &Config,
+    mouse_row: &mut Option<u16>,
+) -> Result<Vec<InputAction>, std::io::Error> {
+    poll_input_player(config, Player::Right, false, mouse_row)
+}
+
+/// Poll input for practice: the player controls the LEFT paddle, and the score
+/// adjustment keys are mapped too.
+/// With mouse_control enabled, `mouse_row` is updated to the latest pointer row.
+pub fn poll_input_practice(
+    config: &Config,
+    mouse_row: &mut Option<u16>,
+) -> Result<Vec<InputAction>, std::io::Error> {
+    poll_input_player(config, Player::Left, true, mouse_row)
+}
+
+fn poll_input_player(
+    config: &Config,
+    side: Player,
+    score_keys: bool,
+    mouse_row: &mut Option<u16>,
+) -> Result<Vec<InputAction>, std::io::Error> {
+    let bindings = &config.keybindings;
+    #[allow(unused_mut)] // Only extended with the gamepad feature
+    let mut actions = player_actions(
+        &read_pending_events()?,
+        bindings,
+        side,
+        score_keys,
+        mouse_row,
+    );
+
+    #[cfg(feature = "gamepad")]
+    actions.extend(super::gamepad::poll(&[side]));
+
+    Ok(resolve_opposing_keys(actions, bindings.opposing_keys))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opposing_keys_last_wins() {
+        let actions = vec![
+            InputAction::LeftPaddleUp,
+            InputAction::LeftPaddleDown,
+            InputAction::LeftPaddleUp,
+            InputAction::RightPaddleDown,
+        ];
+        let resolved = resolve_opposing_keys(actions, OpposingKeys::LastWins);
+        // Every tap of the winning direction still counts
+        assert_eq!(
+            resolved,
+            vec![
+                InputAction::LeftPaddleUp,
+                InputAction::LeftPaddleUp,
+                InputAction::RightPaddleDown
+            ]
+        );
+    }
+
+    #[test]
+    fn test_opposing_keys_both_applied_by_default() {
+        let actions = vec![InputAction::LeftPaddleUp, InputAction::LeftPaddleDown];
+        let resolved = resolve_opposing_keys(actions.clone(), OpposingKeys::default());
+        assert_eq!(resolved, actions);
+    }
+
+    #[test]
+    fn test_opposing_keys_neutral() {
+        let actions = vec![
+            InputAction::Quit,
+            InputAction::LeftPaddleDown,
+            InputAction::LeftPaddleUp,
+            InputAction::RightPaddleUp,
+        ];
+        let resolved = resolve_opposing_keys(actions, OpposingKeys::Neutral);
+        assert_eq!(
+            resolved,
+            vec![InputAction::Quit, InputAction::RightPaddleUp]
+        );
+    }
+
+    #[test]
+    fn test_any_single_key_binds() {
+        assert_eq!(parse_key_binding("I"), Some(KeyCode::Char('i')));
+        assert_eq!(parse_key_binding(";"), Some(KeyCode::Char(';')));
+        assert_eq!(parse_key_binding("Escape"), Some(KeyCode::Esc));
+        assert_eq!(parse_key_binding("PageUp"), None);
+
+        // Shifted letters still match
+        assert!(matches_key(&KeyCode::Char('I'), "i"));
+        assert!(matches_key(&KeyCode::Char('i'), "I"));
+        assert!(!matches_key(&KeyCode::Char('k'), "I"));
+    }
+
+    #[test]
+    fn test_mirrored_actions() {
+        assert_eq!(
+            InputAction::LeftPaddleUp.mirrored(),
+            InputAction::RightPaddleUp
+        );
+        assert_eq!(InputAction::RightCatch.mirrored(), InputAction::LeftCatch);
+        assert_eq!(InputAction::Quit.mirrored(), InputAction::Quit);
+        assert_eq!(
+            InputAction::RightPaddleDown.mirrored().mirrored(),
+            InputAction::RightPaddleDown
+        );
+    }
+
+    fn press(code: KeyCode) -> Event {
+        Event::Key(crossterm::event::KeyEvent::new(
+            code,
+            crossterm::event::KeyModifiers::NONE,
+        ))
+    }
+
+    #[test]
+    fn test_local_2p_key_mapping() {
+        let bindings = KeyBindings::default();
+        let mut release = crossterm::event::KeyEvent::new(
+            KeyCode::Char('w'),
+            crossterm::event::KeyModifiers::NONE,
+        );
+        release.kind = KeyEventKind::Release;
+        let events = [
+            press(KeyCode::Char('w')),
+            press(KeyCode::Down),
+            Event::Key(release),
+            press(KeyCode::Char('2')),
+        ];
 
-    Ok(actions)
+        assert_eq!(
+            local_2p_actions(&events, &bindings, false),
+            vec![
+                InputAction::LeftPaddleUp,
+                InputAction::RightPaddleDown,
+                InputAction::LeftScoreUp
+            ]
+        );
+        // Swapped seats move the other paddles; the score keys stay with the screen sides
+        assert_eq!(
+            local_2p_actions(&events, &bindings, true),
+            vec![
+                InputAction::RightPaddleUp,
+                InputAction::LeftPaddleDown,
+                InputAction::LeftScoreUp
+            ]
+        );
+    }
+
+    #[test]
+    fn test_player_key_mapping_and_mouse() {
+        let mut bindings = KeyBindings::default();
+        let events = [
+            press(KeyCode::Char('s')),
+            press(KeyCode::Char('t')),
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Moved,
+                column: 3,
+                row: 7,
+                modifiers: crossterm::event::KeyModifiers::NONE,
+            }),
+            press(KeyCode::Char('d')),
+            press(KeyCode::Char('0')), // Score keys are practice-only, on either side
+        ];
+
+        let mut mouse_row = None;
+        assert_eq!(
+            player_actions(&events, &bindings, Player::Left, false, &mut mouse_row),
+            vec![
+                InputAction::LeftPaddleDown,
+                InputAction::OpenChat,
+                InputAction::LeftCatch
+            ]
+        );
+        assert_eq!(mouse_row, None); // Mouse control is off
+
+        bindings.mouse_control = true;
+        assert_eq!(
+            player_actions(&events, &bindings, Player::Right, false, &mut mouse_row),
+            vec![
+                InputAction::RightPaddleDown,
+                InputAction::OpenChat,
+                InputAction::RightCatch
+            ]
+        );
+        assert_eq!(mouse_row, Some(7));
+    }
+
+    #[test]
+    fn test_score_keys_are_bindings_mapped_only_in_practice_and_local() {
+        let mut bindings = KeyBindings {
+            left_score_up: "=".to_string(),
+            ..KeyBindings::default()
+        };
+        let events = [press(KeyCode::Char('=')), press(KeyCode::Char('2'))];
+        let mut mouse_row = None;
+
+        assert_eq!(
+            player_actions(&events, &bindings, Player::Left, true, &mut mouse_row),
+            vec![InputAction::LeftScoreUp]
+        );
+        assert_eq!(
+            local_2p_actions(&events, &bindings, false),
+            vec![InputAction::LeftScoreUp]
+        );
+        // Never in the other single-player modes
+        assert!(player_actions(&events, &bindings, Player::Left, false, &mut mouse_row).is_empty());
+
+        bindings.left_score_up = "W".to_string();
+        let problems = binding_problems(&bindings);
+        assert!(problems
+            .contains(&r#""W" is bound to both left_paddle_up and left_score_up"#.to_string()));
+        assert!(problems
+            .contains(&r#""W" is bound to both player_paddle_up and left_score_up"#.to_string()));
+    }
+
+    #[test]
+    fn test_default_bindings_have_no_problems() {
+        assert!(binding_problems(&KeyBindings::default()).is_empty());
+    }
+
+    #[test]
+    fn test_chat_key_is_a_binding() {
+        let mut bindings = KeyBindings {
+            chat: "Y".to_string(),
+            ..KeyBindings::default()
+        };
+        assert_eq!(
+            player_key_actions(&KeyCode::Char('y'), &bindings, false),
+            vec![InputAction::OpenChat]
+        );
+        assert!(player_key_actions(&KeyCode::Char('t'), &bindings, false).is_empty());
+
+        bindings.chat = "W".to_string();
+        assert_eq!(
+            binding_problems(&bindings),
+            vec![r#""W" is bound to both player_paddle_up and chat"#]
+        );
+    }
+
+    #[test]
+    fn test_connection_info_key_is_a_binding() {
+        let bindings = KeyBindings {
+            connection_info: "O".to_string(),
+            ..KeyBindings::default()
+        };
+        assert_eq!(
+            player_key_actions(&KeyCode::Char('o'), &bindings, false),
+            vec![InputAction::ToggleConnectionInfo]
+        );
+        assert!(player_key_actions(&KeyCode::Char('i'), &bindings, false).is_empty());
+    }
+
+    #[test]
+    fn test_ball_speed_keys_are_bindings() {
+        let bindings = KeyBindings {
+            ball_faster: "]".to_string(),
+            ball_slower: "[".to_string(),
+            ..KeyBindings::default()
+        };
+        assert_eq!(
+            player_key_actions(&KeyCode::Char(']'), &bindings, false),
+            vec![InputAction::BallFaster]
+        );
+        assert_eq!(
+            player_key_actions(&KeyCode::Char('['), &bindings, false),
+            vec![InputAction::BallSlower]
+        );
+        assert!(player_key_actions(&KeyCode::Char('+'), &bindings, false).is_empty());
+    }
 }
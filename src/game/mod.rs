@@ -1,9 +1,12 @@
+#[cfg(feature = "gamepad")]
+mod gamepad;
 pub mod input;
 pub mod physics;
 pub mod state;
 
 pub use input::{
-    poll_input_local_2p, poll_input_player_left, poll_input_player_right, InputAction,
+    poll_input_local_2p, poll_input_player_left, poll_input_player_right, poll_input_practice,
+    InputAction, OpposingKeys,
 };
 pub use physics::update_with_events;
 pub use state::{GameState, Player};
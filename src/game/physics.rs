@@ -1,4 +1,8 @@
-use super::state::{GameState, Player};
+use serde::{Deserialize, Serialize};
+
+use super::state::{
+    Ball, GameState, HeldBall, Obstacle, Paddle, Player, PowerUpKind, SLOW_MOTION_SECS,
+};
 
 // All constants now in virtual coordinates (3x resolution: 1200×600)
 pub const PADDLE_MARGIN: f32 = 18.0; // Distance from edge in virtual coords
@@ -6,7 +10,65 @@ pub const PADDLE_WIDTH: f32 = 20.0; // Width in virtual coords (thicker paddles)
 pub const BALL_SIZE: f32 = 20.0; // Ball diameter in virtual coords (ball.x/y is center)
 const BALL_RADIUS: f32 = BALL_SIZE / 2.0; // Ball radius for collision detection
                                           // Ball speed limits
-const MAX_BALL_SPEED: f32 = 4000.0; // Hard limit - physics breaks beyond this
+pub const MAX_BALL_SPEED: f32 = 4000.0; // Hard limit on the configurable speed cap
+const MAX_BOUNCE_ANGLE: f32 = std::f32::consts::PI / 3.0; // 60 degrees
+
+// Catch variant: how long a catch key press waits for paddle contact, in seconds
+const CATCH_WINDOW: f32 = 0.15;
+
+// Power-up effects
+const POWERUP_EFFECT_TIME: f32 = 8.0; // Seconds a paddle size change lasts
+const POWERUP_GROW: f32 = 1.5; // Paddle height multiplier for GrowPaddle
+const POWERUP_SHRINK: f32 = 0.6; // Paddle height multiplier for ShrinkPaddle
+const POWERUP_SPEED_BOOST: f32 = 1.3; // Ball speed multiplier for SpeedBoost
+
+// Smooth paddle mode tuning (virtual units per second)
+const SMOOTH_PADDLE_IMPULSE: f32 = 400.0; // Velocity added per key press
+const SMOOTH_PADDLE_MAX_SPEED: f32 = 1200.0;
+const SMOOTH_PADDLE_FRICTION: f32 = 8.0; // Exponential decay rate per second
+
+/// How paddle key presses move the paddle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaddleMode {
+    /// Each press jumps the paddle by the tap distance
+    #[default]
+    Tap,
+    /// Each press adds velocity; the paddle glides and slows down
+    Smooth,
+}
+
+/// Which way the ball is served, at the start of a game and after each goal
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServePolicy {
+    /// Tennis-style: one serve to the left, then two to each side in turn
+    #[default]
+    Snake,
+    /// Toward the player who was just scored on
+    TowardLoser,
+    /// Switch sides every point
+    Alternate,
+    /// Always toward the left player
+    Left,
+    /// Always toward the right player
+    Right,
+}
+
+impl ServePolicy {
+    /// Whether serve number `serve_count` of the game (0 = the opening serve) goes
+    /// to the left, `scorer` having won the point before it
+    pub fn serves_left(self, serve_count: u8, scorer: Option<Player>) -> bool {
+        match self {
+            // Serves 1-2: right, 3-4: left, 5-6: right, etc.
+            ServePolicy::Snake => serve_count == 0 || ((serve_count - 1) / 2) % 2 == 1,
+            ServePolicy::TowardLoser => scorer != Some(Player::Left),
+            ServePolicy::Alternate => serve_count.is_multiple_of(2),
+            ServePolicy::Left => true,
+            ServePolicy::Right => false,
+        }
+    }
+}
 
 /// Physics events that should trigger immediate network sync
 #[derive(Debug, Default, Clone, Copy)]
@@ -14,138 +76,455 @@ pub struct PhysicsEvents {
     pub paddle_collision: bool,
     pub wall_collision: bool,
     pub goal_scored: bool,
+    pub powerup_changed: bool, // Spawned, collected or worn off
 }
 
 impl PhysicsEvents {
+    /// Whether the ball bounced or a goal was scored (power-up changes aren't counted:
+    /// they have their own sync and no sound)
     pub fn any(&self) -> bool {
         self.paddle_collision || self.wall_collision || self.goal_scored
     }
+
+    /// Combine events from several ticks
+    pub fn merge(&mut self, other: PhysicsEvents) {
+        self.paddle_collision |= other.paddle_collision;
+        self.wall_collision |= other.wall_collision;
+        self.goal_scored |= other.goal_scored;
+        self.powerup_changed |= other.powerup_changed;
+    }
 }
 
+/// Advance the game by `dt` seconds: paddles, balls, collisions, goals, power-ups and
+/// the game clock. Does nothing while paused; after a game ends it only counts down
+/// to the next game of an undecided match.
 pub fn update_with_events(state: &mut GameState, dt: f32) -> PhysicsEvents {
     let mut events = PhysicsEvents::default();
+    if state.paused {
+        return events;
+    }
+    state.slow_motion = (state.slow_motion - dt).max(0.0);
     if state.game_over {
+        // Between the games of a match, the next one starts after a short break
+        if state.match_state.winner().is_none() {
+            state.next_game_in -= dt;
+            if state.next_game_in <= 0.0 {
+                state.next_game();
+                events.powerup_changed = state.powerups_enabled; // Cleared for the new game
+            }
+        }
         return events;
     }
 
-    // Paddles now move only on tap input, not during physics update
+    // Timed games end when the clock runs out, unless tied (sudden death)
+    state.elapsed += dt;
+    state.match_stats.duration += dt;
+    if state.time_up() && state.left_score != state.right_score {
+        let leader = if state.left_score > state.right_score {
+            Player::Left
+        } else {
+            Player::Right
+        };
+        state.finish_game(leader);
+        return events;
+    }
+
+    // After a goal the ball runs on into the goal in slow motion; the serve waits for it
+    if let Some(scorer) = state.pending_serve {
+        if state.slow_motion > 0.0 {
+            update_paddles(state, dt);
+            let (width, height) = (state.field_width, state.field_height);
+            let ball = &mut state.ball;
+            ball.x = (ball.x + ball.vx * dt).clamp(0.0, width);
+            ball.y = (ball.y + ball.vy * dt).clamp(0.0, height);
+            state.push_ball_trail();
+            return events;
+        }
+        state.pending_serve = None;
+        state.reset_ball(scorer);
+    }
+
+    // Tap-mode paddles move only on input; smooth-mode paddles glide here
+    update_paddles(state, dt);
+    state.left_paddle.track_motion(dt);
+    state.right_paddle.track_motion(dt);
+    state.push_ball_trail();
+
+    // A caught ball rides on its paddle until released
+    if state.catch.held.is_some() {
+        events.paddle_collision = update_held_ball(state, dt);
+        track_match_stats(state, &events);
+        return events;
+    }
+    state.catch.left_armed = (state.catch.left_armed - dt).max(0.0);
+    state.catch.right_armed = (state.catch.right_armed - dt).max(0.0);
+
+    // Substep fast balls so no step moves further than half a paddle width,
+    // otherwise a ball can tunnel through a paddle between ticks
+    let max_step = PADDLE_WIDTH / 2.0;
+    // (obstacles can be hit from above or below, so vertical speed counts with them)
+    let step_speed = |ball: &Ball| {
+        if state.obstacles.is_empty() {
+            ball.vx.abs()
+        } else {
+            ball.vx.abs().max(ball.vy.abs())
+        }
+    };
+    let fastest = state.balls().map(step_speed).fold(0.0, f32::max);
+    let substeps = ((fastest * dt) / max_step).ceil().max(1.0) as u32;
+    let step_dt = dt / substeps as f32;
 
-    // Update ball position
-    state.ball.x += state.ball.vx * dt;
-    state.ball.y += state.ball.vy * dt;
+    for _ in 0..substeps {
+        step_ball(state, step_dt, &mut events);
+        if events.goal_scored || state.catch.held.is_some() {
+            break;
+        }
+        step_extra_balls(state, step_dt, &mut events);
+        if events.goal_scored {
+            break;
+        }
+    }
 
-    // Check wall collisions (top and bottom) - account for ball radius
-    if state.ball.y - BALL_RADIUS <= 0.0 {
-        state.ball.y = BALL_RADIUS;
-        state.ball.vy = state.ball.vy.abs();
-        events.wall_collision = true;
-    } else if state.ball.y + BALL_RADIUS >= state.field_height {
-        state.ball.y = state.field_height - BALL_RADIUS;
-        state.ball.vy = -state.ball.vy.abs();
-        events.wall_collision = true;
+    if state.powerups_enabled && !state.game_over {
+        events.powerup_changed = update_powerups(state, dt);
     }
 
+    track_match_stats(state, &events);
+    events
+}
+
+/// Count this tick's paddle hits and goals, and the ball speeds reached, in the
+/// match stats
+fn track_match_stats(state: &mut GameState, events: &PhysicsEvents) {
+    let fastest = state
+        .balls()
+        .map(|ball| (ball.vx * ball.vx + ball.vy * ball.vy).sqrt())
+        .fold(0.0, f32::max);
+    let stats = &mut state.match_stats;
+    stats.top_speed = stats.top_speed.max(fastest);
+    if events.paddle_collision {
+        stats.hits += 1;
+        stats.current_rally += 1;
+        stats.longest_rally = stats.longest_rally.max(stats.current_rally);
+    }
+    if events.goal_scored {
+        stats.current_rally = 0;
+    }
+}
+
+/// Spawn power-ups, apply any a ball has passed through and wear off paddle size
+/// changes; true if any of that happened
+fn update_powerups(state: &mut GameState, dt: f32) -> bool {
+    let mut changed = state.tick_powerup_spawn(dt);
+
+    for player in [Player::Left, Player::Right] {
+        let (height, field_height) = (state.base_paddle_height(player), state.field_height);
+        let paddle = state.paddle_mut(player);
+        if paddle.resized_for > 0.0 {
+            paddle.resized_for -= dt;
+            if paddle.resized_for <= 0.0 {
+                paddle.resize(height, 0.0, field_height);
+                changed = true;
+            }
+        }
+    }
+
+    let mut i = 0;
+    while i < state.powerups.len() {
+        let powerup = state.powerups[i];
+        let collected_by = state.balls().position(|ball| powerup.touches(ball));
+        match collected_by {
+            Some(ball_index) => {
+                state.powerups.remove(i);
+                apply_powerup(state, powerup.kind, ball_index);
+                changed = true;
+            }
+            None => i += 1,
+        }
+    }
+
+    changed
+}
+
+/// Give the effect of a power-up collected by ball `ball_index` to the player who sent it
+fn apply_powerup(state: &mut GameState, kind: PowerUpKind, ball_index: usize) {
+    let max_speed = state.max_ball_speed;
+    let Some(ball) = state.ball_mut(ball_index) else {
+        return;
+    };
+    let collector = if ball.vx > 0.0 {
+        Player::Left
+    } else {
+        Player::Right
+    };
+
+    match kind {
+        PowerUpKind::SpeedBoost => {
+            let speed = (ball.vx * ball.vx + ball.vy * ball.vy).sqrt();
+            if speed > 0.0 {
+                let scale = (speed * POWERUP_SPEED_BOOST).min(max_speed) / speed;
+                ball.vx *= scale;
+                ball.vy *= scale;
+            }
+        }
+        PowerUpKind::GrowPaddle | PowerUpKind::ShrinkPaddle => {
+            let (player, factor) = if kind == PowerUpKind::GrowPaddle {
+                (collector, POWERUP_GROW)
+            } else {
+                (collector.opponent(), POWERUP_SHRINK)
+            };
+            let height = state.base_paddle_height(player) * factor;
+            let field_height = state.field_height;
+            state
+                .paddle_mut(player)
+                .resize(height, POWERUP_EFFECT_TIME, field_height);
+        }
+    }
+}
+
+/// Advance the ball one (sub)step and resolve walls, paddles and goals
+fn step_ball(state: &mut GameState, dt: f32, events: &mut PhysicsEvents) {
+    events.wall_collision |= move_ball(&mut state.ball, dt, state.field_height);
+    events.wall_collision |= bounce_off_obstacles(&mut state.ball, &state.obstacles);
+
     // Check paddle collisions
-    if check_paddle_collision(state, state.speed_increase_factor) {
+    if check_paddle_collision(state) {
         events.paddle_collision = true;
     }
 
-    // Check goals - ball is out when its center crosses the boundary
-    if state.ball.x - BALL_RADIUS <= 0.0 {
-        // Right player scores
-        state.right_score += 1;
-        events.goal_scored = true;
-        if state.right_score >= state.winning_score {
-            state.game_over = true;
-            state.winner = Some(Player::Right);
-        } else {
-            state.reset_ball(Player::Right);
-        }
-    } else if state.ball.x + BALL_RADIUS >= state.field_width {
-        // Left player scores
-        state.left_score += 1;
-        events.goal_scored = true;
-        if state.left_score >= state.winning_score {
-            state.game_over = true;
-            state.winner = Some(Player::Left);
+    if let Some(scorer) = goal_scorer(&state.ball, state.field_width) {
+        score_goal(state, scorer, events);
+    }
+}
+
+/// Advance the extra balls (multi-ball) one (sub)step: like the main ball, minus catching
+fn step_extra_balls(state: &mut GameState, dt: f32, events: &mut PhysicsEvents) {
+    let (field_width, field_height) = (state.field_width, state.field_height);
+    let paddles = [
+        (Player::Left, &state.left_paddle),
+        (Player::Right, &state.right_paddle),
+    ];
+
+    for ball in &mut state.extra_balls {
+        events.wall_collision |= move_ball(ball, dt, field_height);
+        events.wall_collision |= bounce_off_obstacles(ball, &state.obstacles);
+
+        for (side, paddle) in paddles {
+            if touching_paddle(ball, paddle, side, field_width) {
+                bounce_off_paddle(
+                    ball,
+                    paddle,
+                    side == Player::Left,
+                    state.speed_increase_factor,
+                    state.max_ball_speed,
+                    state.spin_factor,
+                );
+                ball.x = ball_x_off_paddle(side, field_width);
+                events.paddle_collision = true;
+            }
+        }
+    }
+
+    let scorer = state
+        .extra_balls
+        .iter()
+        .find_map(|ball| goal_scorer(ball, field_width));
+    if let Some(scorer) = scorer {
+        score_goal(state, scorer, events);
+    }
+}
+
+/// Move a ball and bounce it off the top and bottom walls; true if it hit one
+fn move_ball(ball: &mut Ball, dt: f32, field_height: f32) -> bool {
+    ball.x += ball.vx * dt;
+    ball.y += ball.vy * dt;
+
+    // Account for ball radius
+    if ball.y - BALL_RADIUS <= 0.0 {
+        ball.y = BALL_RADIUS;
+        ball.vy = ball.vy.abs();
+        true
+    } else if ball.y + BALL_RADIUS >= field_height {
+        ball.y = field_height - BALL_RADIUS;
+        ball.vy = -ball.vy.abs();
+        true
+    } else {
+        false
+    }
+}
+
+/// Push a ball out of any obstacle it overlaps through the nearest face, reflecting it
+/// off that face; true if it hit one
+fn bounce_off_obstacles(ball: &mut Ball, obstacles: &[Obstacle]) -> bool {
+    let mut hit = false;
+    for obstacle in obstacles {
+        // Penetration depth through each face (all positive means overlapping)
+        let from_left = ball.x + BALL_RADIUS - obstacle.x;
+        let from_right = obstacle.right() - (ball.x - BALL_RADIUS);
+        let from_top = ball.y + BALL_RADIUS - obstacle.y;
+        let from_bottom = obstacle.bottom() - (ball.y - BALL_RADIUS);
+        let depth = from_left.min(from_right).min(from_top).min(from_bottom);
+        if depth <= 0.0 {
+            continue;
+        }
+
+        if depth == from_left {
+            ball.x = obstacle.x - BALL_RADIUS;
+            ball.vx = -ball.vx.abs();
+        } else if depth == from_right {
+            ball.x = obstacle.right() + BALL_RADIUS;
+            ball.vx = ball.vx.abs();
+        } else if depth == from_top {
+            ball.y = obstacle.y - BALL_RADIUS;
+            ball.vy = -ball.vy.abs();
         } else {
-            state.reset_ball(Player::Left);
+            ball.y = obstacle.bottom() + BALL_RADIUS;
+            ball.vy = ball.vy.abs();
         }
+        hit = true;
     }
+    hit
+}
 
-    events
+/// Who scores with this ball, if it's out (its edge crossed a goal line)
+fn goal_scorer(ball: &Ball, field_width: f32) -> Option<Player> {
+    if ball.x - BALL_RADIUS <= 0.0 {
+        Some(Player::Right)
+    } else if ball.x + BALL_RADIUS >= field_width {
+        Some(Player::Left)
+    } else {
+        None
+    }
 }
 
-// Removed update_paddle - paddles move instantly on tap, not via velocity
+/// Count a goal for `scorer` and serve again (or end the game)
+fn score_goal(state: &mut GameState, scorer: Player, events: &mut PhysicsEvents) {
+    events.goal_scored = true;
 
-fn check_paddle_collision(state: &mut GameState, speed_increase_factor: f32) -> bool {
-    let mut collision_occurred = false;
-    // Left paddle collision (in virtual coordinates)
-    // Ball center is at ball.x, ball.y; ball edges extend by BALL_RADIUS
-    let left_paddle_left = PADDLE_MARGIN;
-    let left_paddle_right = PADDLE_MARGIN + PADDLE_WIDTH;
-
-    // Check if ball's right edge overlaps with paddle
-    if state.ball.x - BALL_RADIUS <= left_paddle_right
-        && state.ball.x + BALL_RADIUS >= left_paddle_left
-        && state.ball.y + BALL_RADIUS >= state.left_paddle.y
-        && state.ball.y - BALL_RADIUS <= state.left_paddle.y + state.left_paddle.height
-    {
-        bounce_off_paddle(
-            &mut state.ball,
-            state.left_paddle.y,
-            state.left_paddle.height,
-            true,
-            speed_increase_factor,
-        );
-        // Move ball just outside paddle
-        state.ball.x = left_paddle_right + BALL_RADIUS;
-        collision_occurred = true;
+    // Practice: no scoring, just serve again
+    if state.practice {
+        serve_after_goal(state, scorer);
+        return;
     }
 
-    // Right paddle collision (in virtual coordinates)
-    let right_paddle_left = state.field_width - PADDLE_MARGIN - PADDLE_WIDTH;
-    let right_paddle_right = state.field_width - PADDLE_MARGIN;
-
-    // Check if ball's left edge overlaps with paddle
-    if state.ball.x + BALL_RADIUS >= right_paddle_left
-        && state.ball.x - BALL_RADIUS <= right_paddle_right
-        && state.ball.y + BALL_RADIUS >= state.right_paddle.y
-        && state.ball.y - BALL_RADIUS <= state.right_paddle.y + state.right_paddle.height
-    {
-        bounce_off_paddle(
-            &mut state.ball,
-            state.right_paddle.y,
-            state.right_paddle.height,
-            false,
-            speed_increase_factor,
-        );
+    let (score, other_score) = match scorer {
+        Player::Left => {
+            state.left_score += 1;
+            (state.left_score, state.right_score)
+        }
+        Player::Right => {
+            state.right_score += 1;
+            (state.right_score, state.left_score)
+        }
+    };
+    if has_won(state, score, other_score) {
+        state.finish_game(scorer);
+    } else {
+        serve_after_goal(state, scorer);
+    }
+}
+
+/// Serve again after a goal, once its slow motion (if on) has played out
+fn serve_after_goal(state: &mut GameState, scorer: Player) {
+    if state.goal_slow_motion {
+        state.slow_motion = SLOW_MOTION_SECS;
+        state.pending_serve = Some(scorer);
+    } else {
+        state.reset_ball(scorer);
+    }
+}
+
+/// Whether `score` wins against `other_score` (two-point lead required under deuce;
+/// in sudden death after a timed game runs out, any lead wins)
+fn has_won(state: &GameState, score: u8, other_score: u8) -> bool {
+    if state.time_up() {
+        return score > other_score;
+    }
+
+    let lead_needed = if state.win_by_two { 2 } else { 1 };
+    score >= state.winning_score && score.saturating_sub(other_score) >= lead_needed
+}
+
+fn check_paddle_collision(state: &mut GameState) -> bool {
+    let mut collision_occurred = false;
+
+    for side in [Player::Left, Player::Right] {
+        let (paddle, armed) = match side {
+            Player::Left => (&state.left_paddle, state.catch.left_armed),
+            Player::Right => (&state.right_paddle, state.catch.right_armed),
+        };
+        if !touching_paddle(&state.ball, paddle, side, state.field_width) {
+            continue;
+        }
+
+        if state.catch_enabled && armed > 0.0 {
+            catch_ball(state, side);
+        } else {
+            bounce_off_paddle(
+                &mut state.ball,
+                paddle,
+                side == Player::Left,
+                state.speed_increase_factor,
+                state.max_ball_speed,
+                state.spin_factor,
+            );
+        }
         // Move ball just outside paddle
-        state.ball.x = right_paddle_left - BALL_RADIUS;
+        state.ball.x = ball_x_off_paddle(side, state.field_width);
         collision_occurred = true;
     }
 
     collision_occurred
 }
 
+/// Whether the ball overlaps `side`'s paddle (ball.x/y is the center; edges extend by
+/// BALL_RADIUS)
+fn touching_paddle(ball: &Ball, paddle: &Paddle, side: Player, field_width: f32) -> bool {
+    let paddle_left = match side {
+        Player::Left => PADDLE_MARGIN,
+        Player::Right => field_width - PADDLE_MARGIN - PADDLE_WIDTH,
+    };
+    let paddle_right = paddle_left + PADDLE_WIDTH;
+
+    ball.x - BALL_RADIUS <= paddle_right
+        && ball.x + BALL_RADIUS >= paddle_left
+        && ball.y + BALL_RADIUS >= paddle.y
+        && ball.y - BALL_RADIUS <= paddle.y + paddle.height
+}
+
+/// Ball x that puts it just in front of `side`'s paddle
+fn ball_x_off_paddle(side: Player, field_width: f32) -> f32 {
+    match side {
+        Player::Left => PADDLE_MARGIN + PADDLE_WIDTH + BALL_RADIUS,
+        Player::Right => field_width - PADDLE_MARGIN - PADDLE_WIDTH - BALL_RADIUS,
+    }
+}
+
 fn bounce_off_paddle(
-    ball: &mut super::state::Ball,
-    paddle_y: f32,
-    paddle_height: f32,
+    ball: &mut Ball,
+    paddle: &Paddle,
     is_left: bool,
     speed_increase_factor: f32,
+    max_speed: f32,
+    spin_factor: f32,
 ) {
     // Calculate where on the paddle the ball hit (0.0 = top, 1.0 = bottom)
-    let hit_pos = (ball.y - paddle_y) / paddle_height;
+    let hit_pos = (ball.y - paddle.y) / paddle.height;
 
     // Map hit position to angle (-60 to 60 degrees)
     // Center hits go straight, edge hits go at steep angles
-    let max_angle = std::f32::consts::PI / 3.0; // 60 degrees
-    let angle = (hit_pos - 0.5) * 2.0 * max_angle;
+    let angle = (hit_pos - 0.5) * 2.0 * MAX_BOUNCE_ANGLE;
 
     // Calculate speed and increase it on each hit
     let current_speed = (ball.vx * ball.vx + ball.vy * ball.vy).sqrt();
-    let speed = (current_speed * speed_increase_factor).min(MAX_BALL_SPEED);
+    let speed = (current_speed * speed_increase_factor).min(max_speed);
+
+    // Spin: a moving paddle adds some of its speed to the outgoing vy,
+    // steepening the angle (still within the 60 degree limit)
+    let spin_vy = angle.sin() * speed + paddle.motion * spin_factor;
+    let angle = spin_vy
+        .atan2(angle.cos() * speed)
+        .clamp(-MAX_BOUNCE_ANGLE, MAX_BOUNCE_ANGLE);
 
     // Set new velocity based on angle
     if is_left {
@@ -157,6 +536,52 @@ fn bounce_off_paddle(
     }
 }
 
+/// Apply a paddle key press for `player` according to the paddle mode
+pub fn paddle_input(state: &mut GameState, player: Player, down: bool) {
+    let (field_height, tap_distance, mode) =
+        (state.field_height, state.tap_distance, state.paddle_mode);
+    let paddle = match player {
+        Player::Left => &mut state.left_paddle,
+        Player::Right => &mut state.right_paddle,
+    };
+
+    match (mode, down) {
+        (PaddleMode::Tap, false) => move_paddle_up(paddle, tap_distance),
+        (PaddleMode::Tap, true) => move_paddle_down(paddle, field_height, tap_distance),
+        (PaddleMode::Smooth, _) => {
+            let impulse = if down {
+                SMOOTH_PADDLE_IMPULSE
+            } else {
+                -SMOOTH_PADDLE_IMPULSE
+            };
+            paddle.vy =
+                (paddle.vy + impulse).clamp(-SMOOTH_PADDLE_MAX_SPEED, SMOOTH_PADDLE_MAX_SPEED);
+        }
+    }
+}
+
+/// Integrate smooth-mode paddle velocity (no-op in tap mode).
+/// Also run by the network client, which doesn't simulate the ball.
+pub fn update_paddles(state: &mut GameState, dt: f32) {
+    if state.paddle_mode != PaddleMode::Smooth {
+        return;
+    }
+
+    let decay = (-SMOOTH_PADDLE_FRICTION * dt).exp();
+    let field_height = state.field_height;
+    for paddle in [&mut state.left_paddle, &mut state.right_paddle] {
+        paddle.y += paddle.vy * dt;
+        paddle.vy *= decay;
+
+        // Stop dead at the walls
+        let max_y = field_height - paddle.height;
+        if paddle.y <= 0.0 || paddle.y >= max_y {
+            paddle.y = paddle.y.clamp(0.0, max_y);
+            paddle.vy = 0.0;
+        }
+    }
+}
+
 pub fn move_paddle_up(paddle: &mut super::state::Paddle, tap_distance: f32) {
     paddle.y -= tap_distance;
     paddle.y = paddle.y.max(0.0);
@@ -166,3 +591,628 @@ pub fn move_paddle_down(paddle: &mut super::state::Paddle, field_height: f32, ta
     paddle.y += tap_distance;
     paddle.y = paddle.y.min(field_height - paddle.height);
 }
+
+fn paddle_of(state: &GameState, player: Player) -> &Paddle {
+    match player {
+        Player::Left => &state.left_paddle,
+        Player::Right => &state.right_paddle,
+    }
+}
+
+/// Handle a catch key press: release the ball if this paddle holds it, otherwise arm a catch
+pub fn press_catch(state: &mut GameState, player: Player) {
+    if !state.catch_enabled || state.game_over {
+        return;
+    }
+
+    match &state.catch.held {
+        Some(held) if held.by == player => release_ball(state),
+        Some(_) => {} // Opponent is holding the ball
+        None => match player {
+            Player::Left => state.catch.left_armed = CATCH_WINDOW,
+            Player::Right => state.catch.right_armed = CATCH_WINDOW,
+        },
+    }
+}
+
+fn catch_ball(state: &mut GameState, player: Player) {
+    let speed = (state.ball.vx * state.ball.vx + state.ball.vy * state.ball.vy).sqrt();
+    let paddle = paddle_of(state, player);
+
+    state.catch.held = Some(HeldBall {
+        by: player,
+        held_for: 0.0,
+        offset_y: state.ball.y - paddle.y,
+        caught_center: paddle.y + paddle.height / 2.0,
+        speed,
+    });
+    state.catch.left_armed = 0.0;
+    state.catch.right_armed = 0.0;
+    state.ball.vx = 0.0;
+    state.ball.vy = 0.0;
+}
+
+/// Keep a held ball pinned to its paddle; returns true if it was auto-released
+fn update_held_ball(state: &mut GameState, dt: f32) -> bool {
+    let Some(held) = state.catch.held.as_mut() else {
+        return false;
+    };
+    held.held_for += dt;
+    let (by, offset_y, timed_out) = (
+        held.by,
+        held.offset_y,
+        held.held_for >= state.catch_max_hold,
+    );
+
+    let paddle_y = paddle_of(state, by).y;
+    state.ball.x = match by {
+        Player::Left => PADDLE_MARGIN + PADDLE_WIDTH + BALL_RADIUS,
+        Player::Right => state.field_width - PADDLE_MARGIN - PADDLE_WIDTH - BALL_RADIUS,
+    };
+    state.ball.y = (paddle_y + offset_y).clamp(BALL_RADIUS, state.field_height - BALL_RADIUS);
+
+    if timed_out {
+        release_ball(state);
+    }
+    timed_out
+}
+
+/// Launch a held ball; the paddle's movement since the catch sets the angle
+/// (one paddle height up or down = steepest angle)
+fn release_ball(state: &mut GameState) {
+    let Some(held) = state.catch.held.take() else {
+        return;
+    };
+
+    let paddle = paddle_of(state, held.by);
+    let center = paddle.y + paddle.height / 2.0;
+    let aim = ((center - held.caught_center) / paddle.height).clamp(-1.0, 1.0);
+    let angle = aim * MAX_BOUNCE_ANGLE;
+
+    // Releasing counts as a paddle hit for rally acceleration
+    let speed = (held.speed * state.speed_increase_factor).min(state.max_ball_speed);
+    let direction = match held.by {
+        Player::Left => 1.0,
+        Player::Right => -1.0,
+    };
+
+    state.ball.vx = direction * angle.cos() * speed;
+    state.ball.vy = angle.sin() * speed;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PhysicsConfig;
+    use crate::game::state::{MatchStats, PowerUp, NEXT_GAME_DELAY, SLOW_MOTION_RATE};
+
+    const DT: f32 = 1.0 / 60.0;
+
+    /// Ball just about to hit the center of the left paddle
+    fn catch_state() -> GameState {
+        let physics = PhysicsConfig {
+            catch_enabled: true,
+            catch_max_hold_secs: 1.0,
+            ..PhysicsConfig::default()
+        };
+        let mut state = GameState::new(80, 24, &physics);
+        let paddle_center = state.left_paddle.y + state.left_paddle.height / 2.0;
+        state.ball.x = PADDLE_MARGIN + PADDLE_WIDTH + BALL_RADIUS + 2.0;
+        state.ball.y = paddle_center;
+        state.ball.vx = -600.0;
+        state.ball.vy = 0.0;
+        state
+    }
+
+    /// Ball heading into the middle of the left paddle, hit on the next update
+    fn aim_at_left_paddle(state: &mut GameState) {
+        state.ball.x = PADDLE_MARGIN + PADDLE_WIDTH + BALL_RADIUS + 2.0;
+        state.ball.y = state.left_paddle.y + state.left_paddle.height / 2.0;
+        state.ball.vx = -600.0;
+        state.ball.vy = 0.0;
+    }
+
+    #[test]
+    fn test_moving_paddle_adds_spin() {
+        let mut still = GameState::new(80, 24, &PhysicsConfig::default());
+        aim_at_left_paddle(&mut still);
+        update_with_events(&mut still, DT);
+        assert!(still.ball.vx > 0.0);
+        assert!(still.ball.vy.abs() < 1.0);
+
+        // Tap the paddle upward for a few ticks, then take the same centre hit
+        let mut moving = GameState::new(80, 24, &PhysicsConfig::default());
+        for _ in 0..4 {
+            paddle_input(&mut moving, Player::Left, false);
+            update_with_events(&mut moving, DT);
+        }
+        assert!(moving.left_paddle.motion < 0.0);
+        aim_at_left_paddle(&mut moving);
+        update_with_events(&mut moving, DT);
+        assert!(moving.ball.vx > 0.0);
+        assert!(moving.ball.vy < still.ball.vy - 50.0);
+
+        // No spin when it's turned off
+        let physics = PhysicsConfig {
+            spin_factor: 0.0,
+            ..PhysicsConfig::default()
+        };
+        let mut plain = GameState::new(80, 24, &physics);
+        for _ in 0..4 {
+            paddle_input(&mut plain, Player::Left, false);
+            update_with_events(&mut plain, DT);
+        }
+        aim_at_left_paddle(&mut plain);
+        update_with_events(&mut plain, DT);
+        assert!(plain.ball.vy.abs() < 1.0);
+    }
+
+    #[test]
+    fn test_any_ball_scores_in_multi_ball() {
+        let physics = PhysicsConfig {
+            ball_count: 3,
+            catch_enabled: true,
+            ..PhysicsConfig::default()
+        };
+        let mut state = GameState::new(80, 24, &physics);
+        assert_eq!(state.balls().count(), 3);
+        assert!(!state.catch_enabled);
+
+        // Serves fan out: the first extra heads the other way from the main ball
+        assert!(state.ball.vx < 0.0 && state.extra_balls[0].vx > 0.0);
+        assert_ne!(state.extra_balls[0].vy, state.extra_balls[1].vy);
+
+        // An extra ball bounces off a paddle like the main one
+        let paddle_center = state.left_paddle.y + state.left_paddle.height / 2.0;
+        state.extra_balls[1].x = PADDLE_MARGIN + PADDLE_WIDTH + BALL_RADIUS + 2.0;
+        state.extra_balls[1].y = paddle_center;
+        state.extra_balls[1].vx = -600.0;
+        let events = update_with_events(&mut state, DT);
+        assert!(events.paddle_collision);
+        assert!(state.extra_balls[1].vx > 0.0);
+
+        // An extra ball past the right goal scores for the left player and re-serves all
+        state.extra_balls[0].x = state.field_width;
+        let events = update_with_events(&mut state, DT);
+        assert!(events.goal_scored);
+        assert_eq!((state.left_score, state.right_score), (1, 0));
+        assert!(state.balls().all(|ball| ball.x == state.field_width / 2.0));
+    }
+
+    #[test]
+    fn test_ball_reflects_off_each_obstacle_face() {
+        let block = Obstacle {
+            x: 500.0,
+            y: 100.0,
+            width: 100.0,
+            height: 100.0,
+        };
+        let physics = PhysicsConfig {
+            obstacles: vec![block],
+            ..PhysicsConfig::default()
+        };
+
+        // (start x, start y, vx, vy) just short of each face, heading into it
+        let approaches = [
+            (block.x - BALL_RADIUS - 1.0, 150.0, 600.0, 0.0), // Left face
+            (block.right() + BALL_RADIUS + 1.0, 150.0, -600.0, 0.0), // Right face
+            (550.0, block.y - BALL_RADIUS - 1.0, 0.0, 600.0), // Top face
+            (550.0, block.bottom() + BALL_RADIUS + 1.0, 0.0, -600.0), // Bottom face
+        ];
+        for (x, y, vx, vy) in approaches {
+            let mut state = GameState::new(80, 24, &physics);
+            assert_eq!(state.obstacles.len(), 1);
+            state.ball.x = x;
+            state.ball.y = y;
+            state.ball.vx = vx;
+            state.ball.vy = vy;
+
+            let events = update_with_events(&mut state, DT);
+            assert!(events.wall_collision);
+            assert_eq!((state.ball.vx, state.ball.vy), (-vx, -vy));
+
+            // Pushed back out to the face it hit
+            let outside = state.ball.x + BALL_RADIUS <= block.x
+                || state.ball.x - BALL_RADIUS >= block.right()
+                || state.ball.y + BALL_RADIUS <= block.y
+                || state.ball.y - BALL_RADIUS >= block.bottom();
+            assert!(outside);
+        }
+    }
+
+    fn powerup_state() -> GameState {
+        let physics = PhysicsConfig {
+            powerups: true,
+            ..PhysicsConfig::default()
+        };
+        GameState::new(80, 24, &physics)
+    }
+
+    /// Put a power-up right in front of the ball
+    fn place_powerup(state: &mut GameState, kind: PowerUpKind) {
+        state.powerups.push(PowerUp {
+            kind,
+            position: (state.ball.x, state.ball.y),
+        });
+    }
+
+    #[test]
+    fn test_powerups_resize_paddles_until_they_wear_off() {
+        let mut state = powerup_state();
+        let height = state.paddle_height;
+
+        // A ball on its way right was sent by the left player
+        state.ball.vx = 600.0;
+        place_powerup(&mut state, PowerUpKind::GrowPaddle);
+        let events = update_with_events(&mut state, DT);
+        assert!(events.powerup_changed);
+        assert!(state.powerups.is_empty());
+        assert_eq!(state.left_paddle.height, height * POWERUP_GROW);
+
+        state.ball.vx = -600.0;
+        place_powerup(&mut state, PowerUpKind::ShrinkPaddle);
+        update_with_events(&mut state, DT);
+        assert_eq!(state.left_paddle.height, height * POWERUP_SHRINK);
+        assert_eq!(state.right_paddle.height, height);
+
+        state.left_paddle.resized_for = DT / 2.0;
+        assert!(update_powerups(&mut state, DT));
+        assert_eq!(state.left_paddle.height, height);
+    }
+
+    #[test]
+    fn test_speed_boost_speeds_up_the_ball() {
+        let mut state = powerup_state();
+        state.ball.vx = 600.0;
+        state.ball.vy = 0.0;
+        place_powerup(&mut state, PowerUpKind::SpeedBoost);
+        update_with_events(&mut state, DT);
+        assert!((state.ball.vx - 600.0 * POWERUP_SPEED_BOOST).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_powerups_spawn_the_same_way_each_game() {
+        let mut state = powerup_state();
+        let mut first = Vec::new();
+        for _ in 0..3 {
+            state.powerup_spawn_in = 0.0;
+            state.tick_powerup_spawn(DT);
+            first = state.powerups.clone();
+        }
+        assert_eq!(first.len(), 2); // Capped on the field
+
+        state.next_game();
+        assert!(state.powerups.is_empty());
+        for _ in 0..2 {
+            state.powerup_spawn_in = 0.0;
+            state.tick_powerup_spawn(DT);
+        }
+        assert_eq!(state.powerups, first);
+
+        // Off by default
+        let mut plain = GameState::new(80, 24, &PhysicsConfig::default());
+        for _ in 0..600 {
+            update_with_events(&mut plain, DT);
+        }
+        assert!(plain.powerups.is_empty());
+    }
+
+    /// Put the ball past the right goal so the left player scores next update
+    fn score_left(state: &mut GameState, left: u8, right: u8) {
+        state.left_score = left;
+        state.right_score = right;
+        state.ball.x = state.field_width;
+        state.ball.y = 20.0; // Clear of the paddles
+        state.ball.vx = 100.0;
+        update_with_events(state, DT);
+    }
+
+    fn deuce_state() -> GameState {
+        let physics = PhysicsConfig {
+            win_by_two: true,
+            ..PhysicsConfig::default()
+        };
+        GameState::new(80, 24, &physics)
+    }
+
+    #[test]
+    fn test_win_by_two_with_lead() {
+        let mut state = deuce_state();
+        score_left(&mut state, 5, 4);
+        assert_eq!((state.left_score, state.right_score), (6, 4));
+        assert!(state.game_over);
+        assert_eq!(state.winner, Some(Player::Left));
+    }
+
+    #[test]
+    fn test_win_by_two_needs_two_point_lead() {
+        let mut state = deuce_state();
+        score_left(&mut state, 5, 5);
+        assert_eq!(state.left_score, 6);
+        assert!(!state.game_over);
+
+        // Stretching the lead to two ends it: 7-5
+        score_left(&mut state, 6, 5);
+        assert_eq!(state.left_score, 7);
+        assert!(state.game_over);
+    }
+
+    #[test]
+    fn test_without_deuce_first_to_winning_score_wins() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        score_left(&mut state, 4, 4);
+        assert!(state.game_over);
+    }
+
+    #[test]
+    fn test_match_plays_on_until_decided() {
+        let physics = PhysicsConfig {
+            games_to_win: 2,
+            ..PhysicsConfig::default()
+        };
+        let mut state = GameState::new(80, 24, &physics);
+        score_left(&mut state, 4, 0);
+        assert!(state.game_over && !state.match_over());
+        assert_eq!(state.match_state.label(), "Match 1-0");
+
+        // The next game starts by itself after the break, keeping the tally
+        for _ in 0..(NEXT_GAME_DELAY / DT).ceil() as usize + 1 {
+            update_with_events(&mut state, DT);
+        }
+        assert!(!state.game_over);
+        assert_eq!((state.left_score, state.right_score), (0, 0));
+
+        score_left(&mut state, 4, 3);
+        assert!(state.match_over());
+        assert_eq!(state.match_state.winner(), Some(Player::Left));
+
+        // A decided match waits for a rematch, which clears the tally
+        update_with_events(&mut state, NEXT_GAME_DELAY * 2.0);
+        assert!(state.game_over);
+        state.reset_game();
+        assert_eq!(state.match_state.games_played(), 0);
+    }
+
+    #[test]
+    fn test_time_limit_and_sudden_death() {
+        let physics = PhysicsConfig {
+            time_limit_secs: Some(1),
+            ..PhysicsConfig::default()
+        };
+
+        // Time runs out with the right player ahead
+        let mut state = GameState::new(80, 24, &physics);
+        state.right_score = 2;
+        state.elapsed = 0.99;
+        update_with_events(&mut state, DT);
+        assert!(state.game_over);
+        assert_eq!(state.winner, Some(Player::Right));
+
+        // Tied at the buzzer: play on until the next goal
+        let mut state = GameState::new(80, 24, &physics);
+        state.elapsed = 1.0;
+        update_with_events(&mut state, DT);
+        assert!(!state.game_over && state.time_up());
+        score_left(&mut state, 1, 1);
+        assert_eq!(state.winner, Some(Player::Left));
+
+        // A rematch restarts the clock
+        state.reset_game();
+        assert_eq!(state.time_left(), Some(1.0));
+    }
+
+    #[test]
+    fn test_fast_ball_does_not_tunnel_through_paddle() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        let paddle_center = state.left_paddle.y + state.left_paddle.height / 2.0;
+
+        // At max speed and a 30 Hz tick the ball travels ~133 units per tick,
+        // far more than the paddle is wide
+        state.ball.x = PADDLE_MARGIN + PADDLE_WIDTH + BALL_RADIUS + 60.0;
+        state.ball.y = paddle_center;
+        state.ball.vx = -MAX_BALL_SPEED;
+        state.ball.vy = 0.0;
+
+        let events = update_with_events(&mut state, 1.0 / 30.0);
+
+        assert!(events.paddle_collision);
+        assert!(!events.goal_scored);
+        assert_eq!(state.right_score, 0);
+        assert!(state.ball.vx > 0.0);
+    }
+
+    #[test]
+    fn test_bounce_respects_configured_speed_cap() {
+        let physics = PhysicsConfig {
+            max_ball_speed: 700.0,
+            ..PhysicsConfig::default()
+        };
+        let mut state = GameState::new(80, 24, &physics);
+        let paddle_center = state.left_paddle.y + state.left_paddle.height / 2.0;
+        state.ball.x = PADDLE_MARGIN + PADDLE_WIDTH + BALL_RADIUS + 2.0;
+        state.ball.y = paddle_center;
+        state.ball.vx = -690.0;
+        state.ball.vy = 0.0;
+
+        update_with_events(&mut state, DT);
+
+        let speed = (state.ball.vx * state.ball.vx + state.ball.vy * state.ball.vy).sqrt();
+        assert!((speed - 700.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_match_stats_track_rallies() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        let hit = |state: &mut GameState| {
+            let paddle_center = state.left_paddle.y + state.left_paddle.height / 2.0;
+            state.ball.x = PADDLE_MARGIN + PADDLE_WIDTH + BALL_RADIUS + 2.0;
+            state.ball.y = paddle_center;
+            state.ball.vx = -600.0;
+            state.ball.vy = 0.0;
+            assert!(update_with_events(state, DT).paddle_collision);
+        };
+        let goal = |state: &mut GameState| {
+            state.ball.x = state.field_width - BALL_RADIUS - 1.0;
+            state.ball.vx = 600.0;
+            assert!(update_with_events(state, DT).goal_scored);
+        };
+
+        hit(&mut state);
+        hit(&mut state);
+        hit(&mut state);
+        goal(&mut state);
+        hit(&mut state);
+        let stats = state.match_stats;
+        assert_eq!(
+            (stats.hits, stats.longest_rally, stats.current_rally),
+            (4, 3, 1)
+        );
+        assert!((stats.top_speed - 600.0 * state.speed_increase_factor).abs() < 0.1);
+        assert!((stats.duration - 5.0 * DT).abs() < 1e-4);
+
+        // A rematch starts them over
+        state.reset_game();
+        assert_eq!(state.match_stats, MatchStats::default());
+    }
+
+    #[test]
+    fn test_goal_starts_slow_motion() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        state.ball.x = BALL_RADIUS + 1.0;
+        state.ball.vx = -600.0;
+        update_with_events(&mut state, DT);
+        assert_eq!(state.time_scale(), 1.0); // Off unless the mode asks for it
+
+        state.goal_slow_motion = true;
+        state.ball.x = BALL_RADIUS + 1.0;
+        state.ball.vx = -600.0;
+        assert!(update_with_events(&mut state, DT).goal_scored);
+        assert_eq!(state.time_scale(), SLOW_MOTION_RATE);
+
+        // The ball runs on into the goal and isn't served until it wears off in game time
+        assert_eq!(state.pending_serve, Some(Player::Right));
+        update_with_events(&mut state, DT);
+        assert!(state.ball.vx < 0.0);
+        assert_eq!(state.ball.x, 0.0);
+        for _ in 0..(SLOW_MOTION_SECS / DT).ceil() as u32 {
+            update_with_events(&mut state, DT);
+        }
+        assert_eq!(state.time_scale(), 1.0);
+        assert_eq!(state.pending_serve, None);
+        assert!((state.ball.x - state.field_width / 2.0).abs() < state.field_width / 4.0);
+    }
+
+    #[test]
+    fn test_paused_game_does_not_advance() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        state.toggle_pause();
+        let (x, y) = (state.ball.x, state.ball.y);
+
+        for _ in 0..60 {
+            update_with_events(&mut state, DT);
+        }
+        assert_eq!((state.ball.x, state.ball.y), (x, y));
+
+        state.toggle_pause();
+        update_with_events(&mut state, DT);
+        assert_ne!(state.ball.x, x);
+    }
+
+    #[test]
+    fn test_smooth_paddle_glides_and_slows() {
+        let physics = PhysicsConfig {
+            paddle_mode: PaddleMode::Smooth,
+            ..PhysicsConfig::default()
+        };
+        let mut state = GameState::new(80, 24, &physics);
+        state.game_over = true; // Freeze the ball; paddles still integrate
+        let start = state.left_paddle.y;
+
+        // A press doesn't jump the paddle, it sets it moving
+        paddle_input(&mut state, Player::Left, true);
+        assert_eq!(state.left_paddle.y, start);
+
+        update_paddles(&mut state, DT);
+        let after_one = state.left_paddle.y;
+        assert!(after_one > start && after_one - start < state.tap_distance);
+
+        // Keeps gliding, but friction bleeds off speed until it stops
+        for _ in 0..120 {
+            update_paddles(&mut state, DT);
+        }
+        assert!(state.left_paddle.y > after_one);
+        assert!(state.left_paddle.vy.abs() < 1.0);
+    }
+
+    #[test]
+    fn test_tap_paddle_jumps_immediately() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        let start = state.left_paddle.y;
+        paddle_input(&mut state, Player::Left, false);
+        assert_eq!(state.left_paddle.y, start - state.tap_distance);
+        assert_eq!(state.left_paddle.vy, 0.0);
+    }
+
+    #[test]
+    fn test_armed_paddle_catches_ball() {
+        let mut state = catch_state();
+        press_catch(&mut state, Player::Left);
+        update_with_events(&mut state, DT);
+
+        let held = state.catch.held.as_ref().expect("ball should be caught");
+        assert_eq!(held.by, Player::Left);
+        assert_eq!((state.ball.vx, state.ball.vy), (0.0, 0.0));
+
+        // Held ball follows the paddle
+        move_paddle_down(&mut state.left_paddle, state.field_height, 40.0);
+        update_with_events(&mut state, DT);
+        let paddle_center = state.left_paddle.y + state.left_paddle.height / 2.0;
+        assert!((state.ball.y - paddle_center).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_unarmed_paddle_bounces() {
+        let mut state = catch_state();
+        update_with_events(&mut state, DT);
+        assert!(state.catch.held.is_none());
+        assert!(state.ball.vx > 0.0);
+    }
+
+    #[test]
+    fn test_held_ball_auto_releases_after_timeout() {
+        let mut state = catch_state();
+        press_catch(&mut state, Player::Left);
+        update_with_events(&mut state, DT);
+        assert!(state.catch.held.is_some());
+
+        let mut released = false;
+        for _ in 0..61 {
+            released |= update_with_events(&mut state, DT).paddle_collision;
+        }
+        assert!(released);
+        assert!(state.catch.held.is_none());
+        assert!(
+            state.ball.vx > 0.0,
+            "auto-release goes away from the holder"
+        );
+    }
+
+    #[test]
+    fn test_manual_release_angle_follows_paddle_movement() {
+        let mut state = catch_state();
+        press_catch(&mut state, Player::Left);
+        update_with_events(&mut state, DT);
+
+        // Move up half a paddle height, then release: aim half of max angle upward
+        let half = state.left_paddle.height / 2.0;
+        move_paddle_up(&mut state.left_paddle, half);
+        press_catch(&mut state, Player::Left);
+
+        assert!(state.catch.held.is_none());
+        let angle = state.ball.vy.atan2(state.ball.vx);
+        assert!((angle + MAX_BOUNCE_ANGLE / 2.0).abs() < 0.01);
+
+        let speed = (state.ball.vx * state.ball.vx + state.ball.vy * state.ball.vy).sqrt();
+        assert!((speed - 600.0 * state.speed_increase_factor).abs() < 0.5);
+    }
+}
@@ -1,7 +1,56 @@
+use std::collections::VecDeque;
 use std::f32::consts::PI;
 
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::physics::{PaddleMode, ServePolicy, BALL_SIZE};
 use crate::config::PhysicsConfig;
 
+/// Number of recent ball positions kept for the trail effect
+pub const BALL_TRAIL_LEN: usize = 8;
+
+/// Largest tilt away from horizontal for a seeded serve, in radians
+const MAX_SERVE_TILT: f32 = PI / 12.0;
+
+/// Break between the games of a match before the next one starts, in seconds
+pub const NEXT_GAME_DELAY: f32 = 3.0;
+
+/// Most balls in play at once (multi-ball)
+pub const MAX_BALLS: u8 = 4;
+
+/// Angle between the fanned-out serves of extra balls, in radians
+const EXTRA_BALL_SPREAD: f32 = PI / 10.0;
+
+/// Seconds between power-up spawns
+const POWERUP_SPAWN_INTERVAL: f32 = 5.0;
+
+/// Most power-ups waiting on the field at once
+const MAX_POWERUPS: usize = 2;
+
+/// Power-up size in virtual units (a square around its position)
+pub const POWERUP_SIZE: f32 = 30.0;
+
+/// Power-up spawn seed for matches without a serve seed (lockstep peers and replays
+/// must see the same spawns)
+const DEFAULT_POWERUP_SEED: u64 = 0x7032_706f_6e67;
+
+/// Speed the game runs at during the slow motion after a goal
+pub const SLOW_MOTION_RATE: f32 = 0.3;
+
+/// Length of the slow motion after a goal, in game seconds (about half a second of
+/// real time at SLOW_MOTION_RATE)
+pub const SLOW_MOTION_SECS: f32 = 0.15;
+
+/// Time constant for smoothing paddle speed, in seconds (evens out tap jumps)
+const PADDLE_MOTION_SMOOTHING: f32 = 0.1;
+
+/// Generator for power-up spawns: the serve seed if there is one, else a fixed seed
+fn powerup_rng(serve_seed: Option<u64>) -> StdRng {
+    StdRng::seed_from_u64(serve_seed.unwrap_or(DEFAULT_POWERUP_SEED))
+}
+
 #[derive(Debug, Clone)]
 pub struct Ball {
     pub x: f32,
@@ -28,130 +77,756 @@ impl Ball {
     }
 }
 
+/// A fixed block in the field that balls bounce off (virtual units, top-left corner)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Obstacle {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Obstacle {
+    pub fn right(&self) -> f32 {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> f32 {
+        self.y + self.height
+    }
+
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.right() && y >= self.y && y <= self.bottom()
+    }
+}
+
+/// What a power-up does to the player who sent the ball through it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PowerUpKind {
+    GrowPaddle,   // Their paddle grows for a while
+    ShrinkPaddle, // The opponent's paddle shrinks for a while
+    SpeedBoost,   // The ball speeds up
+}
+
+const POWERUP_KINDS: [PowerUpKind; 3] = [
+    PowerUpKind::GrowPaddle,
+    PowerUpKind::ShrinkPaddle,
+    PowerUpKind::SpeedBoost,
+];
+
+/// A power-up waiting on the field
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PowerUp {
+    pub kind: PowerUpKind,
+    pub position: (f32, f32), // Center in virtual units
+}
+
+impl PowerUp {
+    /// Whether `ball` overlaps the power-up
+    pub fn touches(&self, ball: &Ball) -> bool {
+        let reach = (POWERUP_SIZE + BALL_SIZE) / 2.0;
+        (ball.x - self.position.0).abs() < reach && (ball.y - self.position.1).abs() < reach
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Paddle {
     pub y: f32,
     pub height: f32,
+    pub vy: f32,          // Velocity in smooth paddle mode (unused in tap mode)
+    pub motion: f32,      // Smoothed vertical speed from any movement, for spin
+    pub resized_for: f32, // Seconds until a power-up size change wears off
+    prev_y: f32,          // Position at the previous tick
 }
 
 impl Paddle {
     pub fn new(y: f32, height: f32) -> Self {
-        Self { y, height }
+        Self {
+            y,
+            height,
+            vy: 0.0,
+            motion: 0.0,
+            resized_for: 0.0,
+            prev_y: y,
+        }
+    }
+
+    /// Change the height for `duration` seconds, keeping the paddle centered where it is
+    pub fn resize(&mut self, height: f32, duration: f32, field_height: f32) {
+        let center = self.y + self.height / 2.0;
+        self.height = height.min(field_height);
+        self.y = (center - self.height / 2.0).clamp(0.0, field_height - self.height);
+        self.resized_for = duration;
+    }
+
+    /// Fold this tick's movement into `motion` (taps and glides alike)
+    pub fn track_motion(&mut self, dt: f32) {
+        let speed = (self.y - self.prev_y) / dt;
+        let blend = 1.0 - (-dt / PADDLE_MOTION_SMOOTHING).exp();
+        self.motion += (speed - self.motion) * blend;
+        self.prev_y = self.y;
     }
 }
 
+/// A ball currently held on a paddle (catch variant)
+#[derive(Debug, Clone)]
+pub struct HeldBall {
+    pub by: Player,
+    pub held_for: f32,      // Seconds held so far
+    pub offset_y: f32,      // Ball center relative to the paddle top
+    pub caught_center: f32, // Paddle center when caught; moving away from it aims the release
+    pub speed: f32,         // Ball speed at the moment of the catch
+}
+
+/// Games won so far in a first-to-N match
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchState {
+    pub games_to_win: u8,
+    pub left_games: u8,
+    pub right_games: u8,
+}
+
+impl MatchState {
+    pub fn new(games_to_win: u8) -> Self {
+        Self {
+            games_to_win: games_to_win.max(1),
+            left_games: 0,
+            right_games: 0,
+        }
+    }
+
+    /// Whether every game is its own match (no tally shown)
+    pub fn is_single_game(&self) -> bool {
+        self.games_to_win == 1
+    }
+
+    pub fn record(&mut self, winner: Player) {
+        match winner {
+            Player::Left => self.left_games += 1,
+            Player::Right => self.right_games += 1,
+        }
+    }
+
+    /// The match winner, once someone has won enough games
+    pub fn winner(&self) -> Option<Player> {
+        if self.left_games >= self.games_to_win {
+            Some(Player::Left)
+        } else if self.right_games >= self.games_to_win {
+            Some(Player::Right)
+        } else {
+            None
+        }
+    }
+
+    /// Games played so far
+    pub fn games_played(&self) -> u8 {
+        self.left_games + self.right_games
+    }
+
+    /// Tally for display, e.g. "Match 2-1"
+    pub fn label(&self) -> String {
+        format!("Match {}-{}", self.left_games, self.right_games)
+    }
+}
+
+/// Rally figures for the whole match, shown when it ends
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MatchStats {
+    pub hits: u32,          // Paddle hits in all rallies
+    pub longest_rally: u32, // Most paddle hits between two goals
+    pub current_rally: u32, // Paddle hits since the last goal
+    pub top_speed: f32,     // Fastest ball, in virtual units per second
+    pub duration: f32,      // Seconds of play (breaks between games excluded)
+}
+
+impl MatchStats {
+    /// Lines for the match-over overlay (none if nothing was played)
+    pub fn summary(&self, serve_speed: f32) -> Vec<String> {
+        if self.duration <= 0.0 {
+            return Vec::new();
+        }
+        let secs = self.duration as u32;
+        vec![
+            format!(
+                "Paddle hits: {}  Longest rally: {}",
+                self.hits, self.longest_rally
+            ),
+            format!("Top speed: {:.1}x serve", self.top_speed / serve_speed),
+            format!("Time played: {}:{:02}", secs / 60, secs % 60),
+        ]
+    }
+}
+
+/// Catch mechanic state (only used when catch is enabled)
+#[derive(Debug, Clone, Default)]
+pub struct CatchState {
+    pub left_armed: f32,  // Seconds left in the left paddle's catch window
+    pub right_armed: f32, // Seconds left in the right paddle's catch window
+    pub held: Option<HeldBall>,
+}
+
+/// Everything the simulation evolves, in virtual coordinates (see `field_width` and
+/// `field_height`); built from a `PhysicsConfig` and stepped by `update_with_events`
 #[derive(Debug, Clone)]
 pub struct GameState {
     pub ball: Ball,
+    pub extra_balls: Vec<Ball>, // Multi-ball: balls beyond the first (empty by default)
     pub left_paddle: Paddle,
     pub right_paddle: Paddle,
     pub left_score: u8,
     pub right_score: u8,
     pub game_over: bool,
+    pub paused: bool, // Physics frozen (local and vs-AI games only)
     pub winner: Option<Player>,
+    pub match_state: MatchState,
+    pub match_stats: MatchStats,
+    pub next_game_in: f32, // Seconds until the next game of an undecided match starts
     pub field_width: f32,
     pub field_height: f32,
-    pub serve_count: u8,            // Track serves for tennis tiebreak pattern
-    pub ball_speed: f32,            // Initial ball speed in virtual units per second
-    pub winning_score: u8,          // Score required to win
-    pub tap_distance: f32,          // Paddle movement distance per tap
+    pub obstacles: Vec<Obstacle>, // Blocks in the field that balls bounce off
+    pub powerups_enabled: bool,   // Power-up variant toggle
+    pub powerups: Vec<PowerUp>,   // Power-ups waiting on the field
+    pub powerup_spawn_in: f32,    // Seconds until the next power-up spawn
+    pub paddle_height: f32,       // Configured paddle height (power-ups change it for a while)
+    pub paddle_scales: (f32, f32), // Left and right paddle height multipliers (bot handicaps)
+    pub serve_count: u8,          // Serves so far this game, for the serve policy
+    pub serve_policy: ServePolicy, // Which way each serve goes
+    pub ball_speed: f32,          // Serve speed in virtual units per second
+    pub winning_score: u8,        // Score required to win
+    pub win_by_two: bool,         // Require a two-point lead to win (deuce)
+    pub time_limit: Option<f32>,  // Seconds of play per game, if timed
+    pub elapsed: f32,             // Seconds of play so far this game (pauses excluded)
+    pub tap_distance: f32,        // Paddle movement distance per tap
+    pub paddle_mode: PaddleMode,  // Tap (jump per press) or smooth (velocity-based)
     pub speed_increase_factor: f32, // Ball speed multiplier on each paddle hit
+    pub reset_speed_each_round: bool, // Serve at ball_speed after a goal (else keep rally speed)
+    pub max_ball_speed: f32,      // Cap on rally speed in virtual units per second
+    pub spin_factor: f32,         // Share of paddle speed passed on to the ball on a hit
+    pub catch_enabled: bool,      // Catch variant toggle
+    pub practice: bool,           // Balls past a paddle are served again without scoring
+    pub goal_slow_motion: bool,   // Slow the game down briefly after each goal
+    pub slow_motion: f32,         // Game seconds of goal slow motion left
+    pub pending_serve: Option<Player>, // Scorer whose serve waits for the slow motion to end
+    pub catch_max_hold: f32,      // Seconds before a held ball auto-releases
+    pub catch: CatchState,
+    pub ball_trail: VecDeque<(f32, f32)>, // Recent ball positions, oldest first
+    pub serve_seed: Option<u64>,          // Seed for reproducible serve tilts (None = flat serves)
+    serve_rng: Option<StdRng>,            // Seeded from serve_seed; restarted on rematch
+    powerup_rng: StdRng,                  // Power-up spawns; restarted each game
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Player {
     Left,
     Right,
 }
 
+impl Player {
+    /// The player on the other side of the field
+    pub fn opponent(self) -> Self {
+        match self {
+            Player::Left => Player::Right,
+            Player::Right => Player::Left,
+        }
+    }
+}
+
 impl GameState {
     pub fn new(_width: u16, _height: u16, physics: &PhysicsConfig) -> Self {
         let field_width = physics.virtual_width;
         let field_height = physics.virtual_height;
-        let ball_speed = physics.ball_initial_speed;
+        let max_ball_speed = physics.max_ball_speed;
+        let ball_speed = physics.ball_initial_speed.min(max_ball_speed);
         let paddle_height = physics.paddle_height;
-        let winning_score = physics.winning_score;
+        let winning_score = physics.winning_score.max(1);
         let tap_distance = physics.paddle_tap_distance;
         let speed_increase_factor = physics.ball_speed_multiplier;
-
-        let mut ball = Ball::new(field_width / 2.0, field_height / 2.0);
-
-        // Initial serve towards left player (ball will be frozen during countdown)
-        ball.reset(field_width / 2.0, field_height / 2.0, PI, ball_speed);
+        let catch_max_hold = physics.catch_max_hold_secs.max(0.0);
 
         let center_y = field_height / 2.0 - paddle_height / 2.0;
+        let ball_count = physics.ball_count.clamp(1, MAX_BALLS);
+        let center_ball = Ball::new(field_width / 2.0, field_height / 2.0);
 
-        Self {
-            ball,
+        let mut state = Self {
+            ball: center_ball.clone(),
+            extra_balls: vec![center_ball; ball_count as usize - 1],
             left_paddle: Paddle::new(center_y, paddle_height),
             right_paddle: Paddle::new(center_y, paddle_height),
             left_score: 0,
             right_score: 0,
             game_over: false,
+            paused: false,
             winner: None,
+            match_state: MatchState::new(physics.games_to_win),
+            match_stats: MatchStats::default(),
+            next_game_in: 0.0,
             field_width,
             field_height,
-            serve_count: 1, // Start at 1 since initial serve was to left (counts as serve 0)
+            obstacles: physics.obstacles.clone(),
+            powerups_enabled: physics.powerups,
+            powerups: Vec::new(),
+            powerup_spawn_in: POWERUP_SPAWN_INTERVAL,
+            paddle_height,
+            paddle_scales: (1.0, 1.0),
+            serve_count: 1, // Start at 1 since the initial serve (below) counts as serve 0
+            serve_policy: physics.serve_policy,
             ball_speed,
             winning_score,
+            win_by_two: physics.win_by_two,
+            time_limit: physics
+                .time_limit_secs
+                .filter(|secs| *secs > 0)
+                .map(|secs| secs as f32),
+            elapsed: 0.0,
             tap_distance,
+            paddle_mode: physics.paddle_mode,
             speed_increase_factor,
+            reset_speed_each_round: physics.reset_speed_each_round,
+            max_ball_speed,
+            spin_factor: physics.spin_factor.max(0.0),
+            catch_enabled: physics.catch_enabled && ball_count == 1, // Catching needs one ball
+            practice: false,
+            goal_slow_motion: false,
+            slow_motion: 0.0,
+            pending_serve: None,
+            catch_max_hold,
+            catch: CatchState::default(),
+            ball_trail: VecDeque::with_capacity(BALL_TRAIL_LEN),
+            serve_seed: physics.serve_seed,
+            serve_rng: physics.serve_seed.map(StdRng::seed_from_u64),
+            powerup_rng: powerup_rng(physics.serve_seed),
+        };
+
+        // Initial serve (ball will be frozen during countdown)
+        state.serve(physics.serve_policy.serves_left(0, None), ball_speed);
+        state
+    }
+
+    /// Remember the current ball position for the trail, dropping the oldest
+    pub fn push_ball_trail(&mut self) {
+        if self.ball_trail.len() == BALL_TRAIL_LEN {
+            self.ball_trail.pop_front();
+        }
+        self.ball_trail.push_back((self.ball.x, self.ball.y));
+    }
+
+    /// All balls in play, the main one first
+    pub fn balls(&self) -> impl Iterator<Item = &Ball> {
+        std::iter::once(&self.ball).chain(&self.extra_balls)
+    }
+
+    pub fn balls_mut(&mut self) -> impl Iterator<Item = &mut Ball> {
+        std::iter::once(&mut self.ball).chain(&mut self.extra_balls)
+    }
+
+    /// Ball `index` of `balls()`
+    pub fn ball_mut(&mut self, index: usize) -> Option<&mut Ball> {
+        self.balls_mut().nth(index)
+    }
+
+    /// The ball `side` most needs to deal with: the one heading its way closest to its
+    /// goal, or the main ball if none is
+    pub fn ball_toward(&self, side: Player) -> &Ball {
+        let distance = |ball: &Ball| match side {
+            Player::Left => ball.x,
+            Player::Right => self.field_width - ball.x,
+        };
+        self.balls()
+            .filter(|ball| match side {
+                Player::Left => ball.vx < 0.0,
+                Player::Right => ball.vx > 0.0,
+            })
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+            .unwrap_or(&self.ball)
+    }
+
+    /// Put the ball in the center and launch it towards one side at `speed`
+    /// (extra balls fan out from the center too, alternating sides)
+    fn serve(&mut self, to_left: bool, speed: f32) {
+        let tilt = match &mut self.serve_rng {
+            Some(rng) => rng.gen_range(-MAX_SERVE_TILT..=MAX_SERVE_TILT),
+            None => 0.0,
+        };
+        let angle = if to_left { PI - tilt } else { tilt };
+
+        // A new serve starts without a trail so it doesn't streak from the goal
+        self.ball_trail.clear();
+
+        let (center_x, center_y) = (self.field_width / 2.0, self.field_height / 2.0);
+        self.ball.reset(center_x, center_y, angle, speed);
+
+        for (i, ball) in self.extra_balls.iter_mut().enumerate() {
+            let spread = EXTRA_BALL_SPREAD * (i / 2 + 1) as f32;
+            let (extra_to_left, tilt) = if i % 2 == 0 {
+                (!to_left, tilt + spread)
+            } else {
+                (to_left, tilt - spread)
+            };
+            let angle = if extra_to_left { PI - tilt } else { tilt };
+            ball.reset(center_x, center_y, angle, speed);
+        }
+    }
+
+    /// Pause or resume play (no effect once the game is over)
+    pub fn toggle_pause(&mut self) {
+        if !self.game_over {
+            self.paused = !self.paused;
         }
     }
 
-    /// Reset the entire game for a rematch (scores, game_over, winner, ball, paddles)
+    /// A player's score in the current game
+    pub fn score(&self, player: Player) -> u8 {
+        match player {
+            Player::Left => self.left_score,
+            Player::Right => self.right_score,
+        }
+    }
+
+    /// Raise or lower a player's score by one, for setting up a situation to practice.
+    /// Stays below the winning score so the next goal decides; ignored once the game is over.
+    pub fn adjust_score(&mut self, player: Player, up: bool) {
+        if self.game_over {
+            return;
+        }
+        let max = self.winning_score.saturating_sub(1);
+        let score = match player {
+            Player::Left => &mut self.left_score,
+            Player::Right => &mut self.right_score,
+        };
+        *score = if up {
+            score.saturating_add(1).min(max)
+        } else {
+            score.saturating_sub(1)
+        };
+    }
+
+    /// End the current game, counting it towards the match
+    pub fn finish_game(&mut self, winner: Player) {
+        self.game_over = true;
+        self.winner = Some(winner);
+        self.match_state.record(winner);
+        self.next_game_in = NEXT_GAME_DELAY;
+    }
+
+    /// Seconds left in a timed game (0 once time is up), or None without a time limit
+    pub fn time_left(&self) -> Option<f32> {
+        self.time_limit.map(|limit| (limit - self.elapsed).max(0.0))
+    }
+
+    /// Whether a timed game has run out of time (next goal wins if tied)
+    pub fn time_up(&self) -> bool {
+        self.time_left() == Some(0.0)
+    }
+
+    /// How fast game time should pass relative to real time (below 1 during the slow
+    /// motion after a goal)
+    pub fn time_scale(&self) -> f32 {
+        if self.slow_motion > 0.0 {
+            SLOW_MOTION_RATE
+        } else {
+            1.0
+        }
+    }
+
+    /// Whether the game is over and no further game of the match follows
+    pub fn match_over(&self) -> bool {
+        self.game_over && self.match_state.winner().is_some()
+    }
+
+    /// Reset the entire match for a rematch (game tally included)
     pub fn reset_game(&mut self) {
+        self.match_state = MatchState::new(self.match_state.games_to_win);
+        self.match_stats = MatchStats::default();
+        self.next_game();
+    }
+
+    /// Start the next game of the match (scores, game_over, winner, ball, paddles)
+    pub fn next_game(&mut self) {
         // Reset scores and game state
         self.left_score = 0;
         self.right_score = 0;
         self.game_over = false;
+        self.paused = false;
         self.winner = None;
+        self.elapsed = 0.0;
         self.serve_count = 1;
+        self.catch = CatchState::default();
+        self.pending_serve = None;
 
-        // Reset ball to center with initial serve
-        self.ball.reset(
-            self.field_width / 2.0,
-            self.field_height / 2.0,
-            PI, // Initial serve towards left player
-            self.ball_speed,
-        );
+        // Restart the serve sequence with the opening serve (each game starts at the
+        // base speed, whether or not rallies carry theirs over)
+        self.serve_rng = self.serve_seed.map(StdRng::seed_from_u64);
+        self.serve(self.serve_policy.serves_left(0, None), self.ball_speed);
 
-        // Reset paddles to center
-        let center_y = self.field_height / 2.0 - self.left_paddle.height / 2.0;
-        self.left_paddle.y = center_y;
-        self.right_paddle.y = center_y;
-    }
-
-    pub fn reset_ball(&mut self, _scored_player: Player) {
-        // Tennis snake serve pattern:
-        // Serve 0: Left (1 serve)
-        // Serves 1-2: Right, Right (2 serves)
-        // Serves 3-4: Left, Left (2 serves)
-        // Serves 5-6: Right, Right (2 serves)
-        // Pattern: L, R-R, L-L, R-R, L-L, ...
-
-        let serve_to_left = match self.serve_count {
-            0 => true, // First serve: left
-            n => {
-                // After first serve: alternate every 2 serves
-                // Serves 1-2: right, 3-4: left, 5-6: right, etc.
-                ((n - 1) / 2) % 2 == 1
-            }
+        // Clear power-ups and restart their spawns
+        self.powerups.clear();
+        self.powerup_spawn_in = POWERUP_SPAWN_INTERVAL;
+        self.powerup_rng = powerup_rng(self.serve_seed);
+
+        // Reset paddles to center (at their configured height)
+        for player in [Player::Left, Player::Right] {
+            let height = self.base_paddle_height(player);
+            let center_y = self.field_height / 2.0 - height / 2.0;
+            *self.paddle_mut(player) = Paddle::new(center_y, height);
+        }
+    }
+
+    /// Height `player`'s paddle returns to when no power-up is changing it
+    pub fn base_paddle_height(&self, player: Player) -> f32 {
+        let scale = match player {
+            Player::Left => self.paddle_scales.0,
+            Player::Right => self.paddle_scales.1,
         };
+        (self.paddle_height * scale).min(self.field_height)
+    }
+
+    /// Make the paddles taller or shorter than configured, (left, right), for good
+    pub fn set_paddle_scales(&mut self, scales: (f32, f32)) {
+        self.paddle_scales = (scales.0.max(0.1), scales.1.max(0.1));
+        for player in [Player::Left, Player::Right] {
+            let (height, field_height) = (self.base_paddle_height(player), self.field_height);
+            self.paddle_mut(player).resize(height, 0.0, field_height);
+        }
+    }
+
+    pub fn paddle(&self, player: Player) -> &Paddle {
+        match player {
+            Player::Left => &self.left_paddle,
+            Player::Right => &self.right_paddle,
+        }
+    }
+
+    pub fn paddle_mut(&mut self, player: Player) -> &mut Paddle {
+        match player {
+            Player::Left => &mut self.left_paddle,
+            Player::Right => &mut self.right_paddle,
+        }
+    }
+
+    /// Count down to the next power-up and place it at a random spot between the
+    /// paddle lanes; true if one appeared
+    pub fn tick_powerup_spawn(&mut self, dt: f32) -> bool {
+        self.powerup_spawn_in -= dt;
+        if self.powerup_spawn_in > 0.0 {
+            return false;
+        }
+        self.powerup_spawn_in = POWERUP_SPAWN_INTERVAL;
+        if self.powerups.len() >= MAX_POWERUPS {
+            return false;
+        }
 
-        let angle = if serve_to_left {
-            PI // Serve left
+        let rng = &mut self.powerup_rng;
+        let kind = POWERUP_KINDS[rng.gen_range(0..POWERUP_KINDS.len())];
+        let x = self.field_width * rng.gen_range(0.25..=0.75);
+        let y = POWERUP_SIZE / 2.0 + (self.field_height - POWERUP_SIZE).max(0.0) * rng.gen::<f32>();
+
+        // A spot inside an obstacle can't be reached; skip this spawn
+        if self.obstacles.iter().any(|o| o.contains(x, y)) {
+            return false;
+        }
+        self.powerups.push(PowerUp {
+            kind,
+            position: (x, y),
+        });
+        true
+    }
+
+    /// Serve again after `scored_player` won the point, in the direction the serve
+    /// policy picks
+    pub fn reset_ball(&mut self, scored_player: Player) {
+        let serve_to_left = self
+            .serve_policy
+            .serves_left(self.serve_count, Some(scored_player));
+
+        // Either back to the base speed or on at the speed the rally reached
+        let speed = if self.reset_speed_each_round {
+            self.ball_speed
         } else {
-            0.0 // Serve right
+            let rally_speed = (self.ball.vx * self.ball.vx + self.ball.vy * self.ball.vy).sqrt();
+            rally_speed.clamp(self.ball_speed, self.max_ball_speed)
+        };
+
+        self.serve_count = self.serve_count.saturating_add(1);
+        self.catch = CatchState::default();
+        self.serve(serve_to_left, speed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ball_speed(ball: &Ball) -> f32 {
+        (ball.vx * ball.vx + ball.vy * ball.vy).sqrt()
+    }
+
+    #[test]
+    fn test_adjust_score_stays_below_winning() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        let winning = state.winning_score;
+
+        state.adjust_score(Player::Left, false);
+        assert_eq!(state.left_score, 0);
+        for _ in 0..winning + 3 {
+            state.adjust_score(Player::Right, true);
+        }
+        assert_eq!(state.right_score, winning - 1);
+        assert!(!state.game_over);
+
+        state.finish_game(Player::Left);
+        state.adjust_score(Player::Right, false);
+        assert_eq!(state.right_score, winning - 1);
+    }
+
+    #[test]
+    fn test_serve_uses_configured_launch_speed() {
+        let physics = PhysicsConfig {
+            ball_initial_speed: 900.0,
+            ball_speed_multiplier: 1.5,
+            ..PhysicsConfig::default()
+        };
+        let mut state = GameState::new(80, 24, &physics);
+
+        // Speed up the rally, then serve again: launch speed must not inherit it
+        state.ball.vx *= state.speed_increase_factor;
+        state.reset_ball(Player::Left);
+
+        assert!((ball_speed(&state.ball) - 900.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_serve_keeps_rally_speed_without_reset() {
+        let physics = PhysicsConfig {
+            ball_initial_speed: 500.0,
+            reset_speed_each_round: false,
+            max_ball_speed: 1000.0,
+            ..PhysicsConfig::default()
+        };
+        let mut state = GameState::new(80, 24, &physics);
+        assert!((ball_speed(&state.ball) - 500.0).abs() < 0.01);
+
+        // The next serve goes on at the rally's speed, up to the cap
+        state.ball.vx *= 1.5;
+        state.reset_ball(Player::Left);
+        assert!((ball_speed(&state.ball) - 750.0).abs() < 0.01);
+        state.ball.vx *= 2.0;
+        state.reset_ball(Player::Left);
+        assert!((ball_speed(&state.ball) - 1000.0).abs() < 0.01);
+
+        // A new game starts over at the configured speed
+        state.reset_game();
+        assert!((ball_speed(&state.ball) - 500.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_same_seed_replays_same_serves() {
+        let physics = PhysicsConfig {
+            serve_seed: Some(42),
+            ..PhysicsConfig::default()
         };
+        let mut a = GameState::new(80, 24, &physics);
+        let mut b = GameState::new(80, 24, &physics);
+
+        let mut serves = Vec::new();
+        for _ in 0..6 {
+            assert_eq!((a.ball.vx, a.ball.vy), (b.ball.vx, b.ball.vy));
+            serves.push(a.ball.vy);
+            a.reset_ball(Player::Left);
+            b.reset_ball(Player::Left);
+        }
+
+        // Seeded serves are tilted, and a rematch replays the same sequence
+        assert!(serves.iter().any(|vy| *vy != 0.0));
+        a.reset_game();
+        assert_eq!(a.ball.vy, serves[0]);
+    }
+
+    #[test]
+    fn test_ball_trail_is_capped_and_cleared_on_serve() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        for i in 0..BALL_TRAIL_LEN + 3 {
+            state.ball.x = i as f32;
+            state.push_ball_trail();
+        }
+        assert_eq!(state.ball_trail.len(), BALL_TRAIL_LEN);
+        assert_eq!(state.ball_trail.front(), Some(&(3.0, state.ball.y)));
+
+        state.reset_ball(Player::Left);
+        assert!(state.ball_trail.is_empty());
+
+        state.push_ball_trail();
+        state.reset_game();
+        assert!(state.ball_trail.is_empty());
+    }
+
+    #[test]
+    fn test_serve_policies() {
+        use Player::{Left as L, Right as R};
+        let scorers = [L, L, R, L, R, R];
+        for (policy, expected) in [
+            // Opening serve, then one after each goal
+            (ServePolicy::Snake, "LRRLLRR"),
+            (ServePolicy::TowardLoser, "LRRLRLL"),
+            (ServePolicy::Alternate, "LRLRLRL"),
+            (ServePolicy::Left, "LLLLLLL"),
+            (ServePolicy::Right, "RRRRRRR"),
+        ] {
+            let physics = PhysicsConfig {
+                serve_policy: policy,
+                ..PhysicsConfig::default()
+            };
+            let mut state = GameState::new(80, 24, &physics);
+            let mut serves = String::new();
+            for scorer in scorers {
+                serves.push(if state.ball.vx < 0.0 { 'L' } else { 'R' });
+                state.reset_ball(scorer);
+            }
+            serves.push(if state.ball.vx < 0.0 { 'L' } else { 'R' });
+            assert_eq!(serves, expected, "{:?}", policy);
+
+            // A new game opens the same way
+            state.reset_game();
+            assert_eq!(state.ball.vx < 0.0, expected.starts_with('L'));
+        }
+    }
 
-        self.serve_count += 1;
+    #[test]
+    fn test_paddle_scales_last_through_rematches() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        state.set_paddle_scales((1.0, 1.5));
+        assert_eq!(state.left_paddle.height, 90.0);
+        assert_eq!(state.right_paddle.height, 135.0);
+        assert_eq!(state.base_paddle_height(Player::Right), 135.0);
 
-        self.ball.reset(
-            self.field_width / 2.0,
-            self.field_height / 2.0,
-            angle,
-            self.ball_speed,
+        state.reset_game();
+        assert_eq!(state.right_paddle.height, 135.0);
+        assert_eq!(
+            state.right_paddle.y + state.right_paddle.height / 2.0,
+            state.field_height / 2.0
         );
     }
+
+    #[test]
+    fn test_unseeded_serves_are_flat() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        for _ in 0..4 {
+            assert!(state.ball.vy.abs() < 1e-3);
+            state.reset_ball(Player::Left);
+        }
+    }
+
+    #[test]
+    fn test_paddle_settings_from_config() {
+        let physics = PhysicsConfig {
+            paddle_height: 150.0,
+            paddle_tap_distance: 25.0,
+            ..PhysicsConfig::default()
+        };
+        let state = GameState::new(80, 24, &physics);
+        assert_eq!(state.left_paddle.height, 150.0);
+        assert_eq!(state.right_paddle.height, 150.0);
+        assert_eq!(state.tap_distance, 25.0);
+    }
 }
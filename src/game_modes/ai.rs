@@ -1,4 +1,3 @@
-use std::io;
 use std::time::{Duration, Instant};
 
 use ratatui::Terminal;
@@ -6,105 +5,206 @@ use ratatui::Terminal;
 use crate::ai;
 use crate::config::Config;
 use crate::debug;
-use crate::game::{self, poll_input_player_left, GameState, InputAction};
+use crate::error::P2PongError;
+use crate::game::physics::PhysicsEvents;
+use crate::game::{self, poll_input_player_left, poll_input_player_right, GameState, InputAction};
+use crate::replay::Recorder;
+use crate::stats;
 use crate::ui;
-use crate::FIXED_TIMESTEP;
 
-use super::common::limit_frame_rate;
+use super::common::{
+    game_over_lines, help_overlay, limit_frame_rate, next_game_overlay, poll_any_key, rematch_hint,
+    terminal_too_small, Bell, FpsCounter, HelpLayout, MouseSteering, PhysicsClock,
+};
 
-/// Run single-player game against AI
+/// Run single-player game against AI, the player on `player_side`
 pub fn run_game_vs_ai<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     config: &Config,
     bot_type: ai::BotType,
-) -> Result<(), io::Error> {
+    player_side: game::Player,
+) -> Result<(), P2PongError> {
     debug::log(
         "GAME_START",
-        &format!("Single player vs AI mode: {:?}", bot_type),
+        &format!(
+            "Single player vs AI mode: {:?}, player on {:?}",
+            bot_type, player_side
+        ),
     );
+    let bot_side = player_side.opponent();
 
     let frame_duration = Duration::from_millis(1000 / config.display.target_fps);
 
     let size = terminal.size()?;
     let mut game_state = GameState::new(size.width, size.height, &config.physics);
+    game_state.goal_slow_motion = config.display.goal_slow_motion;
+    // Easier bots defend with a bigger paddle
+    let bot_scale = bot_type.paddle_height_multiplier();
+    game_state.set_paddle_scales(match bot_side {
+        game::Player::Left => (bot_scale, 1.0),
+        game::Player::Right => (1.0, bot_scale),
+    });
+    let mut clock = PhysicsClock::new(config.physics.tick_rate_hz);
+    let mut fps_counter = FpsCounter::new(config.display.show_fps);
+    let mut bell = Bell::new(config.display.sound_enabled);
+    let mut show_help = false; // Help overlay open (game paused)
+    let mut recorder = config.replay.record.then(|| {
+        Recorder::new(
+            &config.physics,
+            game_state.paddle_scales,
+            Some(player_side),
+            game_state.goal_slow_motion,
+        )
+    });
+
+    let mut stats = stats::load();
+    let mut result_recorded = false;
 
     // Create bot instance using factory
-    let mut bot = ai::create_bot(bot_type);
+    let mut bot = ai::create_bot(bot_type, bot_side, &config.ai);
+    let mut mouse_row = None;
+    let mut mouse_steering = MouseSteering::new();
 
     loop {
         let now = Instant::now();
+        let fps = fps_counter.frame();
 
-        // Handle player input (left paddle only)
-        let actions = poll_input_player_left(config)?;
+        // Handle player input (own paddle only); while help is open any key just closes it
+        let actions = if show_help {
+            show_help = !poll_any_key()?;
+            Vec::new()
+        } else {
+            let mut actions = match player_side {
+                game::Player::Left => poll_input_player_left(config, &mut mouse_row)?,
+                game::Player::Right => poll_input_player_right(config, &mut mouse_row)?,
+            };
+            let size = terminal.size()?;
+            let field = ui::FieldArea::new(
+                size.width,
+                size.height,
+                &game_state,
+                config.display.preserve_aspect,
+            );
+            actions.extend(mouse_steering.steer(&game_state, player_side, mouse_row, &field));
+            actions
+        };
 
         for action in &actions {
+            // While paused only system keys are handled
+            if game_state.paused
+                && !matches!(
+                    action,
+                    InputAction::Quit | InputAction::Pause | InputAction::ToggleHelp
+                )
+            {
+                continue;
+            }
+            if let Some(recorder) = &mut recorder {
+                recorder.input(*action);
+            }
+
             match action {
-                InputAction::Quit => return Ok(()),
-                InputAction::Rematch => {
-                    if game_state.game_over {
-                        game_state.reset_game();
-                        bot.reset();
+                InputAction::Quit => {
+                    if let Some(recorder) = &recorder {
+                        recorder.save();
                     }
+                    return Ok(());
                 }
-                InputAction::LeftPaddleUp => {
-                    game::physics::move_paddle_up(
-                        &mut game_state.left_paddle,
-                        game_state.tap_distance,
-                    );
+                InputAction::Rematch if game_state.match_over() => {
+                    game_state.reset_game();
+                    bot.reset();
+                    result_recorded = false;
                 }
-                InputAction::LeftPaddleDown => {
-                    game::physics::move_paddle_down(
-                        &mut game_state.left_paddle,
-                        game_state.field_height,
-                        game_state.tap_distance,
-                    );
+                // The player's input only ever moves their own paddle
+                InputAction::LeftPaddleUp
+                | InputAction::LeftPaddleDown
+                | InputAction::RightPaddleUp
+                | InputAction::RightPaddleDown => apply_paddle_action(&mut game_state, *action),
+                InputAction::LeftCatch | InputAction::RightCatch => {
+                    game::physics::press_catch(&mut game_state, player_side);
                 }
-                _ => {} // Ignore right paddle inputs
+                InputAction::ToggleHelp => show_help = true,
+                InputAction::Pause => game_state.toggle_pause(),
+                _ => {}
             }
         }
 
-        // Bot input (right paddle)
-        if let Some(bot_action) = bot.get_action(&game_state, FIXED_TIMESTEP) {
-            match bot_action {
-                InputAction::RightPaddleUp => {
-                    game::physics::move_paddle_up(
-                        &mut game_state.right_paddle,
-                        game_state.tap_distance,
-                    );
-                }
-                InputAction::RightPaddleDown => {
-                    game::physics::move_paddle_down(
-                        &mut game_state.right_paddle,
-                        game_state.field_height,
-                        game_state.tap_distance,
-                    );
-                }
-                _ => {} // Bot should only move right paddle
+        // Physics ticks due this frame (the clock is drained while paused or the
+        // terminal is too small, so resuming doesn't replay that time)
+        clock.set_rate(game_state.time_scale());
+        let ticks = clock.ticks();
+
+        // Bot input (other paddle), once per rendered frame so its pace doesn't depend on
+        // tick rate; it's given the real time since the last frame, and waits out the slow
+        // motion after a goal
+        let held = show_help || terminal_too_small(terminal)?;
+        let bot_action = if held || game_state.paused || game_state.pending_serve.is_some() {
+            None
+        } else {
+            bot.get_action(&game_state, clock.frame_secs())
+        };
+        if let Some(bot_action) = bot_action {
+            apply_paddle_action(&mut game_state, bot_action);
+            if let Some(recorder) = &mut recorder {
+                recorder.input(bot_action);
             }
         }
 
-        // Update physics
-        let events = game::update_with_events(&mut game_state, FIXED_TIMESTEP);
+        // Update physics at the fixed tick rate
+        if !held {
+            let mut frame_events = PhysicsEvents::default();
+            for _ in 0..ticks {
+                let events = game::update_with_events(&mut game_state, clock.timestep());
+                frame_events.merge(events);
+                if let Some(recorder) = &mut recorder {
+                    recorder.tick();
+                }
 
-        // Reset bot state on new round (but keep rendering game over state)
-        if events.goal_scored && !game_state.game_over {
-            bot.reset();
+                // Reset bot state on new round (but keep rendering game over state)
+                if events.goal_scored && !game_state.game_over {
+                    bot.reset();
+                }
+            }
+            bell.play(&frame_events)?;
         }
 
-        // Create overlay message if game is over
-        let overlay = if game_state.game_over {
-            let winner_text = match game_state
+        // Create overlay message if help is open or the game is over
+        let overlay = if show_help {
+            Some(help_overlay(config, HelpLayout::SinglePlayer, true))
+        } else if game_state.game_over && !game_state.match_over() {
+            let winner_name = if game_state.winner == Some(player_side) {
+                "you"
+            } else {
+                "bot"
+            };
+            Some(next_game_overlay(&game_state, winner_name).with_display_config(&config.display))
+        } else if game_state.game_over {
+            let winner = game_state
                 .winner
-                .expect("game_over is true but winner is None")
-            {
-                game::Player::Left => "YOU WIN!",
-                game::Player::Right => "BOT WINS",
+                .expect("game_over is true but winner is None");
+
+            // Record the result once per match
+            if !result_recorded {
+                stats.record_result(bot_type, winner == player_side);
+                if let Err(e) = stats::save(&stats) {
+                    debug::warn("STATS", &format!("Failed to save stats: {}", e));
+                }
+                result_recorded = true;
+            }
+
+            let winner_text = if winner == player_side {
+                "YOU WIN!"
+            } else {
+                "BOT WINS"
             };
-            Some(ui::OverlayMessage::info(vec![
-                winner_text.to_string(),
-                "".to_string(),
-                "R to Rematch  |  Q to Quit".to_string(),
-            ]))
+            Some(
+                ui::OverlayMessage::info(game_over_lines(
+                    &game_state,
+                    winner_text.to_string(),
+                    rematch_hint(config),
+                ))
+                .with_display_config(&config.display),
+            )
         } else {
             None
         };
@@ -115,7 +215,12 @@ pub fn run_game_vs_ai<B: ratatui::backend::Backend>(
                 &game_state,
                 None,
                 overlay.as_ref(),
-                Some(game::Player::Left),
+                Some(player_side),
+                ui::RenderOptions {
+                    fps,
+                    assist: config.display.assist,
+                    ..ui::RenderOptions::from_display(&config.display)
+                },
             )
         })?;
 
@@ -123,3 +228,132 @@ pub fn run_game_vs_ai<B: ratatui::backend::Backend>(
         limit_frame_rate(now, frame_duration);
     }
 }
+
+/// How long the demo shows the result before starting a new game
+const DEMO_RESTART_DELAY: Duration = Duration::from_secs(3);
+
+/// Run AI-vs-AI demo (attract mode); any key returns to the menu
+pub fn run_game_ai_vs_ai<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    config: &Config,
+    left_bot_type: ai::BotType,
+    right_bot_type: ai::BotType,
+) -> Result<(), P2PongError> {
+    debug::log(
+        "GAME_START",
+        &format!("AI vs AI demo: {:?} vs {:?}", left_bot_type, right_bot_type),
+    );
+
+    let frame_duration = Duration::from_millis(1000 / config.display.target_fps);
+
+    let size = terminal.size()?;
+    let mut game_state = GameState::new(size.width, size.height, &config.physics);
+    game_state.goal_slow_motion = config.display.goal_slow_motion;
+    game_state.set_paddle_scales((
+        left_bot_type.paddle_height_multiplier(),
+        right_bot_type.paddle_height_multiplier(),
+    ));
+    let mut clock = PhysicsClock::new(config.physics.tick_rate_hz);
+    let mut fps_counter = FpsCounter::new(config.display.show_fps);
+
+    let mut left_bot = ai::create_bot(left_bot_type, game::Player::Left, &config.ai);
+    let mut right_bot = ai::create_bot(right_bot_type, game::Player::Right, &config.ai);
+    let mut game_over_since: Option<Instant> = None;
+
+    loop {
+        let now = Instant::now();
+        let fps = fps_counter.frame();
+
+        if poll_any_key()? {
+            return Ok(());
+        }
+
+        // Start a new match once the result has been shown for a while
+        if game_over_since.is_some_and(|at| at.elapsed() > DEMO_RESTART_DELAY) {
+            game_state.reset_game();
+            left_bot.reset();
+            right_bot.reset();
+            game_over_since = None;
+        }
+
+        // Both bots act once per rendered frame, given the real time since the last one
+        // (held while the terminal is too small or a goal's slow motion plays)
+        clock.set_rate(game_state.time_scale());
+        let ticks = clock.ticks();
+        if !terminal_too_small(terminal)? {
+            if game_state.pending_serve.is_none() {
+                for bot in [&mut left_bot, &mut right_bot] {
+                    if let Some(action) = bot.get_action(&game_state, clock.frame_secs()) {
+                        apply_paddle_action(&mut game_state, action);
+                    }
+                }
+            }
+
+            for _ in 0..ticks {
+                let events = game::update_with_events(&mut game_state, clock.timestep());
+                if events.goal_scored && !game_state.game_over {
+                    left_bot.reset();
+                    right_bot.reset();
+                }
+            }
+        }
+
+        let winner = match game_state.winner {
+            Some(game::Player::Left) => left_bot_type.display_name(),
+            _ => right_bot_type.display_name(),
+        };
+        let overlay = if game_state.game_over && !game_state.match_over() {
+            Some(
+                next_game_overlay(&game_state, &format!("{} bot", winner))
+                    .with_display_config(&config.display),
+            )
+        } else if game_state.game_over {
+            game_over_since.get_or_insert_with(Instant::now);
+            Some(
+                ui::OverlayMessage::info(game_over_lines(
+                    &game_state,
+                    format!("{} BOT WINS", winner.to_uppercase()),
+                    "Press any key to return to menu".to_string(),
+                ))
+                .with_display_config(&config.display),
+            )
+        } else {
+            None
+        };
+
+        terminal.draw(|f| {
+            ui::render(
+                f,
+                &game_state,
+                None,
+                overlay.as_ref(),
+                None,
+                ui::RenderOptions {
+                    fps,
+                    ..ui::RenderOptions::from_display(&config.display)
+                },
+            )
+        })?;
+
+        limit_frame_rate(now, frame_duration);
+    }
+}
+
+/// Apply a paddle move (a bot's, or the player's against one) to the game state
+pub(super) fn apply_paddle_action(game_state: &mut GameState, action: InputAction) {
+    match action {
+        InputAction::LeftPaddleUp => {
+            game::physics::paddle_input(game_state, game::Player::Left, false);
+        }
+        InputAction::LeftPaddleDown => {
+            game::physics::paddle_input(game_state, game::Player::Left, true);
+        }
+        InputAction::RightPaddleUp => {
+            game::physics::paddle_input(game_state, game::Player::Right, false);
+        }
+        InputAction::RightPaddleDown => {
+            game::physics::paddle_input(game_state, game::Player::Right, true);
+        }
+        _ => {} // Bots only move paddles
+    }
+}
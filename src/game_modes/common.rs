@@ -3,8 +3,17 @@
 //! This module contains helper functions used by local, AI, and network game modes
 //! to avoid code duplication and improve maintainability.
 
+use std::collections::VecDeque;
+use std::io::{self, Write};
 use std::time::{Duration, Instant};
 
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+
+use crate::config::{Config, KeyBindings};
+use crate::game::physics::{PaddleMode, PhysicsEvents};
+use crate::game::{GameState, InputAction, Player};
+use crate::ui::{self, OverlayMessage};
+
 /// Apply frame rate limiting to maintain consistent game speed.
 ///
 /// This function should be called at the end of each game loop iteration.
@@ -20,3 +29,571 @@ pub fn limit_frame_rate(frame_start: Instant, frame_duration: Duration) {
         std::thread::sleep(frame_duration - elapsed);
     }
 }
+
+/// Whether the terminal is too small to draw the game; play is held until it's resized
+pub fn terminal_too_small<B: ratatui::backend::Backend>(
+    terminal: &ratatui::Terminal<B>,
+) -> io::Result<bool> {
+    let size = terminal.size()?;
+    Ok(ui::is_too_small(size.width, size.height))
+}
+
+/// Which paddle controls the help overlay lists
+pub enum HelpLayout {
+    /// Local hotseat: both paddles on one keyboard, each player's keys (`swapped` = seats
+    /// switched, `score_keys` = unranked game, so the score adjustment keys work)
+    TwoPlayer { swapped: bool, score_keys: bool },
+    /// AI and network modes: the player's own paddle
+    SinglePlayer,
+    /// Practice: the player's own paddle, plus the ball speed and score adjustment keys
+    Practice,
+}
+
+/// Help line for the score adjustment keys
+pub fn score_keys_help(keys: &KeyBindings) -> String {
+    format!(
+        "Set score (debug): {} / {} left, {} / {} right",
+        keys.left_score_down, keys.left_score_up, keys.right_score_down, keys.right_score_up
+    )
+}
+
+/// Build the in-game help overlay from the effective key bindings
+pub fn help_overlay(config: &Config, layout: HelpLayout, pauses: bool) -> OverlayMessage {
+    let keys = &config.keybindings;
+    let catch = config.physics.catch_enabled;
+
+    let paddle_line = |label: &str, up: &str, down: &str, catch_key: &str| {
+        if catch {
+            format!("{}: {} / {}   Catch: {}", label, up, down, catch_key)
+        } else {
+            format!("{}: {} / {}", label, up, down)
+        }
+    };
+
+    let mut lines = vec!["CONTROLS".to_string(), "".to_string()];
+    match layout {
+        HelpLayout::TwoPlayer {
+            swapped,
+            score_keys,
+        } => {
+            let (left_label, right_label) = if swapped {
+                ("Right paddle", "Left paddle")
+            } else {
+                ("Left paddle", "Right paddle")
+            };
+            let mut paddles = [
+                paddle_line(
+                    left_label,
+                    &keys.left_paddle_up,
+                    &keys.left_paddle_down,
+                    &keys.left_catch,
+                ),
+                paddle_line(
+                    right_label,
+                    &keys.right_paddle_up,
+                    &keys.right_paddle_down,
+                    &keys.right_catch,
+                ),
+            ];
+            if swapped {
+                paddles.reverse();
+            }
+            lines.extend(paddles);
+            lines.push(format!("Swap sides: {}", keys.swap_sides));
+            if score_keys {
+                lines.push(score_keys_help(keys));
+            }
+        }
+        HelpLayout::SinglePlayer | HelpLayout::Practice => {
+            lines.push(paddle_line(
+                "Paddle",
+                &keys.player_paddle_up,
+                &keys.player_paddle_down,
+                &keys.player_catch,
+            ));
+            if matches!(layout, HelpLayout::Practice) {
+                lines.push(format!(
+                    "Ball speed: {} / {}",
+                    keys.ball_faster, keys.ball_slower
+                ));
+                lines.push(score_keys_help(keys));
+            }
+        }
+    }
+    lines.push(format!(
+        "Quit: {} / Esc   Rematch: {}   Help: ?",
+        keys.quit, keys.rematch
+    ));
+    if pauses {
+        lines.push(format!("Pause: {}", keys.pause));
+    }
+    lines.push("".to_string());
+    lines.push(if pauses {
+        "Paused - press any key to resume".to_string()
+    } else {
+        "Press any key to close".to_string()
+    });
+
+    OverlayMessage::info(lines)
+}
+
+/// Pointer-to-paddle gain in smooth mode: target speed per unit of distance (1/s)
+const MOUSE_SMOOTH_GAIN: f32 = 6.0;
+
+/// Paddle steps per second toward the mouse pointer (the pace one step per frame
+/// had at the default 60 FPS)
+const MOUSE_STEPS_PER_SEC: f32 = 60.0;
+
+/// Most steps owed at once, so a stalled frame doesn't make the paddle jump
+const MOUSE_MAX_OWED_STEPS: f32 = 4.0;
+
+/// Up/down action moving `player`'s paddle toward the mouse pointer, if it isn't there yet.
+/// The paddle centres on the pointer row.
+pub fn steer_to_mouse(
+    state: &GameState,
+    player: Player,
+    mouse_row: Option<u16>,
+    field: &ui::FieldArea,
+) -> Option<InputAction> {
+    let target = field.field_y_at_row(mouse_row?);
+    let paddle = match player {
+        Player::Left => &state.left_paddle,
+        Player::Right => &state.right_paddle,
+    };
+    let distance = target - (paddle.y + paddle.height / 2.0);
+    if distance.abs() <= state.tap_distance / 2.0 {
+        return None;
+    }
+
+    // In smooth mode only push again once the paddle is slower than the distance calls for
+    let down = distance > 0.0;
+    if state.paddle_mode == PaddleMode::Smooth {
+        let speed_toward = if down { paddle.vy } else { -paddle.vy };
+        if speed_toward >= distance.abs() * MOUSE_SMOOTH_GAIN {
+            return None;
+        }
+    }
+
+    Some(match (player, down) {
+        (Player::Left, false) => InputAction::LeftPaddleUp,
+        (Player::Left, true) => InputAction::LeftPaddleDown,
+        (Player::Right, false) => InputAction::RightPaddleUp,
+        (Player::Right, true) => InputAction::RightPaddleDown,
+    })
+}
+
+/// Steers a paddle toward the mouse pointer at a pace independent of the frame rate
+pub struct MouseSteering {
+    last_frame: Instant,
+    owed: f32, // Steps earned by elapsed time but not yet taken
+}
+
+impl MouseSteering {
+    pub fn new() -> Self {
+        Self {
+            last_frame: Instant::now(),
+            owed: 0.0,
+        }
+    }
+
+    /// Steps toward the pointer for the time since the last call (once per frame)
+    pub fn steer(
+        &mut self,
+        state: &GameState,
+        player: Player,
+        mouse_row: Option<u16>,
+        field: &ui::FieldArea,
+    ) -> Vec<InputAction> {
+        let elapsed = self.last_frame.elapsed().as_secs_f32();
+        self.last_frame = Instant::now();
+
+        let (Some(action), Some(row)) =
+            (steer_to_mouse(state, player, mouse_row, field), mouse_row)
+        else {
+            self.owed = 0.0;
+            return Vec::new();
+        };
+        // Tap steps are never more than reach the pointer; a smooth push is
+        // re-checked against the paddle's speed every frame
+        let needed = match state.paddle_mode {
+            PaddleMode::Tap => {
+                let paddle = match player {
+                    Player::Left => &state.left_paddle,
+                    Player::Right => &state.right_paddle,
+                };
+                let distance = field.field_y_at_row(row) - (paddle.y + paddle.height / 2.0);
+                (distance.abs() / state.tap_distance).round().max(1.0)
+            }
+            PaddleMode::Smooth => 1.0,
+        };
+        let steps = self.take_steps(elapsed, needed);
+        vec![action; steps]
+    }
+
+    /// Earn steps for `elapsed` seconds and spend up to `needed` of them
+    fn take_steps(&mut self, elapsed: f32, needed: f32) -> usize {
+        self.owed = (self.owed + elapsed * MOUSE_STEPS_PER_SEC).min(MOUSE_MAX_OWED_STEPS);
+        let steps = self.owed.floor().min(needed);
+        self.owed -= steps;
+        steps as usize
+    }
+}
+
+/// Overlay between the games of a match: who took the game, the tally and the countdown
+pub fn next_game_overlay(state: &GameState, winner_name: &str) -> OverlayMessage {
+    let match_state = &state.match_state;
+    OverlayMessage::info(vec![
+        format!(
+            "GAME {} TO {}",
+            match_state.games_played(),
+            winner_name.to_uppercase()
+        ),
+        "".to_string(),
+        match_state.label(),
+        format!("Next game in {}", state.next_game_in.ceil().max(1.0)),
+    ])
+}
+
+/// Lines for the final game-over overlay: the result, the match tally (first-to-N
+/// matches only), the match stats and a status line
+pub fn game_over_lines(state: &GameState, result: String, status: String) -> Vec<String> {
+    let mut lines = vec![result];
+    if !state.match_state.is_single_game() {
+        lines.push(state.match_state.label());
+    }
+    let summary = state.match_stats.summary(state.ball_speed);
+    if !summary.is_empty() {
+        lines.push("".to_string());
+        lines.extend(summary);
+    }
+    lines.push("".to_string());
+    lines.push(status);
+    lines
+}
+
+/// Game-over hint naming the configured rematch and quit keys
+pub fn rematch_hint(config: &Config) -> String {
+    let keys = &config.keybindings;
+    format!("{} to Rematch  |  {} to Quit", keys.rematch, keys.quit)
+}
+
+/// Drain pending terminal events; returns true if any key was pressed
+pub fn poll_any_key() -> Result<bool, io::Error> {
+    let mut pressed = false;
+    while event::poll(Duration::from_millis(0))? {
+        if let Event::Key(key) = event::read()? {
+            pressed |= key.kind == KeyEventKind::Press;
+        }
+    }
+    Ok(pressed)
+}
+
+/// Key presses relevant to a one-line text prompt
+pub enum TextKey {
+    Char(char),
+    Backspace,
+    Submit,
+    Cancel,
+}
+
+/// Drain pending terminal events as text prompt keys
+pub fn poll_text_input() -> Result<Vec<TextKey>, io::Error> {
+    let mut keys = Vec::new();
+    while event::poll(Duration::from_millis(0))? {
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char(c) => keys.push(TextKey::Char(c)),
+                KeyCode::Backspace => keys.push(TextKey::Backspace),
+                KeyCode::Enter => keys.push(TextKey::Submit),
+                KeyCode::Esc => keys.push(TextKey::Cancel),
+                _ => {}
+            }
+        }
+    }
+    Ok(keys)
+}
+
+/// Supported physics tick rates in Hz
+const MIN_TICK_RATE_HZ: u32 = 10;
+const MAX_TICK_RATE_HZ: u32 = 240;
+
+/// Longest frame time fed into the accumulator, so a stall can't trigger a burst of ticks
+const MAX_FRAME_TIME: Duration = Duration::from_millis(250);
+
+/// Fixed-timestep accumulator that decouples physics ticks from render frames.
+///
+/// Each frame, call `ticks()` and run that many physics steps of `timestep()` seconds.
+pub struct PhysicsClock {
+    timestep: f32,
+    accumulator: f32,
+    last_frame: Instant,
+    frame_secs: f32, // Real seconds between the last two calls to ticks()
+    rate: f32,       // Game seconds per real second
+}
+
+impl PhysicsClock {
+    /// Create a clock ticking at `tick_rate_hz` (clamped to a supported range)
+    pub fn new(tick_rate_hz: u32) -> Self {
+        let hz = tick_rate_hz.clamp(MIN_TICK_RATE_HZ, MAX_TICK_RATE_HZ);
+        Self {
+            timestep: 1.0 / hz as f32,
+            accumulator: 0.0,
+            last_frame: Instant::now(),
+            frame_secs: 0.0,
+            rate: 1.0,
+        }
+    }
+
+    /// Run ticks at `rate` times real time (for slow motion). Ticks keep their
+    /// length, so the simulation and any replay of it are unchanged; they just come
+    /// less often.
+    pub fn set_rate(&mut self, rate: f32) {
+        self.rate = rate.max(0.0);
+    }
+
+    /// Length of one physics tick in seconds
+    pub fn timestep(&self) -> f32 {
+        self.timestep
+    }
+
+    /// Number of physics ticks due since the previous call
+    pub fn ticks(&mut self) -> u32 {
+        let now = Instant::now();
+        let elapsed = now - self.last_frame;
+        self.last_frame = now;
+        self.frame_secs = elapsed.min(MAX_FRAME_TIME).as_secs_f32();
+        self.advance(elapsed)
+    }
+
+    /// Real seconds the last `ticks()` call covered, unaffected by slow motion
+    /// (for things paced per rendered frame, like the bots)
+    pub fn frame_secs(&self) -> f32 {
+        self.frame_secs
+    }
+
+    fn advance(&mut self, elapsed: Duration) -> u32 {
+        self.accumulator += elapsed.min(MAX_FRAME_TIME).as_secs_f32() * self.rate;
+        let ticks = (self.accumulator / self.timestep) as u32;
+        self.accumulator -= ticks as f32 * self.timestep;
+        ticks
+    }
+}
+
+/// Frames averaged by the FPS counter
+const FPS_SAMPLE_FRAMES: usize = 30;
+
+/// Shortest gap between bells, so a fast rally doesn't turn into one long buzz
+const BELL_MIN_GAP: Duration = Duration::from_millis(100);
+
+/// Terminal bell feedback for game events (display.sound_enabled).
+///
+/// Call `play()` once per frame with the physics events of that frame.
+pub struct Bell {
+    enabled: bool,
+    last_ring: Option<Instant>,
+}
+
+impl Bell {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            last_ring: None,
+        }
+    }
+
+    /// Ring for any event in `events` (BEL neither moves the cursor nor draws,
+    /// so it is safe to write between ratatui frames)
+    pub fn play(&mut self, events: &PhysicsEvents) -> Result<(), io::Error> {
+        if !self.due(events, Instant::now()) {
+            return Ok(());
+        }
+        let mut stdout = io::stdout();
+        stdout.write_all(b"\x07")?;
+        stdout.flush()
+    }
+
+    fn due(&mut self, events: &PhysicsEvents, now: Instant) -> bool {
+        if !self.enabled || !events.any() {
+            return false;
+        }
+        if self
+            .last_ring
+            .is_some_and(|at| now.duration_since(at) < BELL_MIN_GAP)
+        {
+            return false;
+        }
+        self.last_ring = Some(now);
+        true
+    }
+}
+
+/// Rolling average of frame-to-frame time for the optional FPS display.
+///
+/// Call `frame()` once per rendered frame; it returns `None` when disabled.
+pub struct FpsCounter {
+    enabled: bool,
+    samples: VecDeque<Duration>,
+    last_frame: Instant,
+}
+
+impl FpsCounter {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            samples: VecDeque::with_capacity(FPS_SAMPLE_FRAMES),
+            last_frame: Instant::now(),
+        }
+    }
+
+    /// Record a frame and return the averaged frames per second
+    pub fn frame(&mut self) -> Option<f32> {
+        if !self.enabled {
+            return None;
+        }
+        let now = Instant::now();
+        let elapsed = now - self.last_frame;
+        self.last_frame = now;
+        Some(self.record(elapsed))
+    }
+
+    fn record(&mut self, elapsed: Duration) -> f32 {
+        if self.samples.len() == FPS_SAMPLE_FRAMES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(elapsed);
+
+        let total: Duration = self.samples.iter().sum();
+        let average = total.as_secs_f32() / self.samples.len() as f32;
+        if average > 0.0 {
+            1.0 / average
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_physics_clock_accumulates_partial_ticks() {
+        let mut clock = PhysicsClock::new(120);
+
+        // Two 60 FPS frames at 120 Hz run two ticks each (allowing float slack)
+        let first = clock.advance(Duration::from_micros(16_667));
+        let second = clock.advance(Duration::from_micros(16_667));
+        assert_eq!(first + second, 4);
+
+        // A 5ms frame at 120 Hz (8.3ms tick) runs nothing until enough time builds up
+        let mut slow = PhysicsClock::new(120);
+        assert_eq!(slow.advance(Duration::from_millis(5)), 0);
+        assert_eq!(slow.advance(Duration::from_millis(5)), 1);
+    }
+
+    #[test]
+    fn test_physics_clock_slow_motion() {
+        let mut clock = PhysicsClock::new(60);
+        clock.set_rate(0.25);
+        assert_eq!(clock.advance(Duration::from_millis(50)), 0);
+        assert_eq!(clock.advance(Duration::from_millis(50)), 1);
+
+        clock.set_rate(1.0);
+        assert_eq!(clock.advance(Duration::from_millis(50)), 3);
+    }
+
+    #[test]
+    fn test_physics_clock_clamps_rate_and_stalls() {
+        let mut clock = PhysicsClock::new(1_000_000);
+        assert_eq!(clock.timestep(), 1.0 / MAX_TICK_RATE_HZ as f32);
+
+        // A multi-second stall is capped at MAX_FRAME_TIME worth of ticks
+        let ticks = clock.advance(Duration::from_secs(5));
+        assert!(ticks <= (MAX_TICK_RATE_HZ as f32 * MAX_FRAME_TIME.as_secs_f32()) as u32 + 1);
+    }
+
+    #[test]
+    fn test_fps_counter_averages_recent_frames() {
+        let mut fps = FpsCounter::new(true);
+        for _ in 0..FPS_SAMPLE_FRAMES {
+            fps.record(Duration::from_millis(20));
+        }
+        assert!((fps.record(Duration::from_millis(20)) - 50.0).abs() < 0.1);
+
+        // One slow frame only drags the average down a little
+        let after_hitch = fps.record(Duration::from_millis(100));
+        assert!(after_hitch > 40.0 && after_hitch < 50.0);
+
+        assert_eq!(FpsCounter::new(false).frame(), None);
+    }
+
+    #[test]
+    fn test_steer_to_mouse() {
+        let mut state = GameState::new(80, 24, &crate::config::PhysicsConfig::default());
+        let rows = 24;
+        let field = ui::FieldArea::new(80, rows, &state, false);
+        state.left_paddle.y = 0.0;
+
+        assert_eq!(steer_to_mouse(&state, Player::Left, None, &field), None);
+        // Pointer at the bottom of the field pulls the paddle down
+        assert_eq!(
+            steer_to_mouse(&state, Player::Left, Some(rows - 2), &field),
+            Some(InputAction::LeftPaddleDown)
+        );
+
+        // Paddle already centred on the pointer: no movement
+        let row = 12;
+        let target = field.field_y_at_row(row);
+        state.right_paddle.y = target - state.right_paddle.height / 2.0;
+        assert_eq!(
+            steer_to_mouse(&state, Player::Right, Some(row), &field),
+            None
+        );
+        assert_eq!(
+            steer_to_mouse(&state, Player::Right, Some(0), &field),
+            Some(InputAction::RightPaddleUp)
+        );
+    }
+
+    #[test]
+    fn test_mouse_steering_pace_ignores_frame_rate() {
+        // A second's worth of frames takes the same number of steps at any frame rate
+        for fps in [30, 60, 144] {
+            let mut steering = MouseSteering::new();
+            let steps: usize = (0..fps)
+                .map(|_| steering.take_steps(1.0 / fps as f32, 100.0))
+                .sum();
+            assert!((59..=60).contains(&steps), "{} FPS: {} steps", fps, steps);
+        }
+
+        // A stall doesn't bank a burst, and steps stop at the pointer
+        let mut steering = MouseSteering::new();
+        assert_eq!(steering.take_steps(1.0, 100.0), 4);
+        assert_eq!(steering.take_steps(1.0, 2.0), 2);
+    }
+
+    #[test]
+    fn test_bell_rings_on_events_with_a_gap() {
+        let goal = PhysicsEvents {
+            goal_scored: true,
+            ..PhysicsEvents::default()
+        };
+        let start = Instant::now();
+
+        assert!(!Bell::new(false).due(&goal, start));
+
+        let mut bell = Bell::new(true);
+        assert!(!bell.due(&PhysicsEvents::default(), start));
+        let powerup = PhysicsEvents {
+            powerup_changed: true,
+            ..PhysicsEvents::default()
+        };
+        assert!(!bell.due(&powerup, start));
+        assert!(bell.due(&goal, start));
+        assert!(!bell.due(&goal, start + BELL_MIN_GAP / 2));
+        assert!(bell.due(&goal, start + BELL_MIN_GAP));
+    }
+}
@@ -0,0 +1,253 @@
+// Ghost race: play live against your own earlier vs-AI game. The recorded player's paddle
+// moves (mirrored) drive the opponent paddle, tick for tick; once the recording runs out
+// the ghost stands still. The header compares your points with the ghost's at the same
+// moment of its game.
+
+use std::time::{Duration, Instant};
+
+use ratatui::Terminal;
+
+use crate::config::Config;
+use crate::debug;
+use crate::error::P2PongError;
+use crate::game::physics::PhysicsEvents;
+use crate::game::{self, poll_input_player_left, poll_input_player_right, GameState, InputAction};
+use crate::replay::{self, Replay};
+use crate::ui;
+
+use super::ai::apply_paddle_action;
+use super::common::{
+    game_over_lines, help_overlay, limit_frame_rate, next_game_overlay, poll_any_key, rematch_hint,
+    terminal_too_small, Bell, FpsCounter, HelpLayout, MouseSteering, PhysicsClock,
+};
+
+/// The recorded player's paddle moves, mirrored onto the other paddle
+fn ghost_inputs(replay: &Replay, recorded_side: game::Player) -> Vec<(u64, InputAction)> {
+    replay
+        .inputs
+        .iter()
+        .filter(|(_, action)| is_paddle_input(recorded_side, *action))
+        .map(|&(tick, action)| (tick, action.mirrored()))
+        .collect()
+}
+
+fn is_paddle_input(side: game::Player, action: InputAction) -> bool {
+    matches!(
+        (side, action),
+        (
+            game::Player::Left,
+            InputAction::LeftPaddleUp | InputAction::LeftPaddleDown | InputAction::LeftCatch
+        ) | (
+            game::Player::Right,
+            InputAction::RightPaddleUp | InputAction::RightPaddleDown | InputAction::RightCatch
+        )
+    )
+}
+
+/// Header line comparing points so far with the ghost's at the same tick
+fn pace_line(yours: usize, ghost: usize) -> String {
+    let lead = yours as i64 - ghost as i64;
+    format!("You {} vs ghost {} ({:+})", yours, ghost, lead)
+}
+
+/// Race against a recorded solo game. The player keeps the side they played on.
+pub fn run_ghost<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    config: &Config,
+    replay: &Replay,
+    player_side: game::Player,
+) -> Result<(), P2PongError> {
+    debug::log(
+        "GAME_START",
+        &format!(
+            "Ghost race on {:?}: {} recorded ticks",
+            player_side, replay.ticks
+        ),
+    );
+
+    let frame_duration = Duration::from_millis(1000 / config.display.target_fps);
+
+    // The recording's physics and goal slow motion, so the serves come just as they did
+    // for the ghost
+    let size = terminal.size()?;
+    let mut game_state = GameState::new(size.width, size.height, &replay.physics);
+    game_state.goal_slow_motion = replay.goal_slow_motion;
+    let mut clock = PhysicsClock::new(replay.physics.tick_rate_hz);
+    let mut fps_counter = FpsCounter::new(config.display.show_fps);
+    let mut bell = Bell::new(config.display.sound_enabled);
+    let mut show_help = false; // Help overlay open (game paused)
+    let mut mouse_row = None;
+    let mut mouse_steering = MouseSteering::new();
+
+    let ghost = ghost_inputs(replay, player_side);
+    let ghost_goals = replay.scoring_ticks(player_side);
+    let mut tick: u64 = 0; // Physics ticks since the race (or rematch) started
+    let mut next_ghost = 0; // Index of the first ghost input not yet applied
+    let mut your_points = 0;
+
+    loop {
+        let now = Instant::now();
+        let fps = fps_counter.frame();
+
+        let actions = if show_help {
+            show_help = !poll_any_key()?;
+            Vec::new()
+        } else {
+            let mut actions = match player_side {
+                game::Player::Left => poll_input_player_left(config, &mut mouse_row)?,
+                game::Player::Right => poll_input_player_right(config, &mut mouse_row)?,
+            };
+            let size = terminal.size()?;
+            let field = ui::FieldArea::new(
+                size.width,
+                size.height,
+                &game_state,
+                config.display.preserve_aspect,
+            );
+            actions.extend(mouse_steering.steer(&game_state, player_side, mouse_row, &field));
+            actions
+        };
+
+        for action in &actions {
+            // While paused only system keys are handled
+            if game_state.paused
+                && !matches!(
+                    action,
+                    InputAction::Quit | InputAction::Pause | InputAction::ToggleHelp
+                )
+            {
+                continue;
+            }
+
+            match action {
+                InputAction::Quit => return Ok(()),
+                InputAction::Rematch if game_state.match_over() => {
+                    game_state.reset_game();
+                    tick = 0;
+                    next_ghost = 0;
+                    your_points = 0;
+                }
+                // The player's input only ever moves their own paddle
+                InputAction::LeftPaddleUp
+                | InputAction::LeftPaddleDown
+                | InputAction::RightPaddleUp
+                | InputAction::RightPaddleDown => apply_paddle_action(&mut game_state, *action),
+                InputAction::LeftCatch | InputAction::RightCatch => {
+                    game::physics::press_catch(&mut game_state, player_side);
+                }
+                InputAction::ToggleHelp => show_help = true,
+                InputAction::Pause => game_state.toggle_pause(),
+                _ => {}
+            }
+        }
+
+        // Update physics at the fixed tick rate, feeding in the ghost's moves tick by tick
+        // (the clock is drained while paused or the terminal is too small)
+        clock.set_rate(game_state.time_scale());
+        let ticks = clock.ticks();
+        let held = show_help || terminal_too_small(terminal)?;
+        if !held {
+            let mut frame_events = PhysicsEvents::default();
+            for _ in 0..ticks {
+                if game_state.paused {
+                    break;
+                }
+                let end = next_ghost
+                    + ghost[next_ghost..]
+                        .iter()
+                        .take_while(|(at, _)| *at <= tick)
+                        .count();
+                for &(_, action) in &ghost[next_ghost..end] {
+                    replay::apply_input(&mut game_state, action);
+                }
+                next_ghost = end;
+
+                let before = game_state.score(player_side);
+                frame_events.merge(game::update_with_events(&mut game_state, clock.timestep()));
+                if game_state.score(player_side) > before {
+                    your_points += 1;
+                }
+                tick += 1;
+            }
+            bell.play(&frame_events)?;
+        }
+
+        let ghost_points = ghost_goals.partition_point(|at| *at < tick);
+        let pace = pace_line(your_points, ghost_points);
+
+        let overlay = if show_help {
+            Some(help_overlay(config, HelpLayout::SinglePlayer, true))
+        } else if game_state.game_over && !game_state.match_over() {
+            let winner_name = if game_state.winner == Some(player_side) {
+                "you"
+            } else {
+                "ghost"
+            };
+            Some(next_game_overlay(&game_state, winner_name).with_display_config(&config.display))
+        } else if game_state.game_over {
+            let winner_text = if game_state.winner == Some(player_side) {
+                "YOU BEAT YOUR GHOST!"
+            } else {
+                "GHOST WINS"
+            };
+            Some(
+                ui::OverlayMessage::info(game_over_lines(
+                    &game_state,
+                    winner_text.to_string(),
+                    rematch_hint(config),
+                ))
+                .with_display_config(&config.display),
+            )
+        } else {
+            None
+        };
+
+        terminal.draw(|f| {
+            ui::render(
+                f,
+                &game_state,
+                None,
+                overlay.as_ref(),
+                Some(player_side),
+                ui::RenderOptions {
+                    fps,
+                    header_note: Some(&pace),
+                    ..ui::RenderOptions::from_display(&config.display)
+                },
+            )
+        })?;
+
+        limit_frame_rate(now, frame_duration);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PhysicsConfig;
+
+    #[test]
+    fn test_ghost_replays_the_players_moves_on_the_other_side() {
+        let replay = Replay {
+            physics: PhysicsConfig::default(),
+            paddle_scales: (1.0, 1.3),
+            inputs: vec![
+                (0, InputAction::LeftPaddleUp),
+                (1, InputAction::RightPaddleDown), // The bot's move
+                (2, InputAction::Pause),
+                (4, InputAction::LeftCatch),
+            ],
+            ticks: 10,
+            player_side: Some(game::Player::Left),
+            goal_slow_motion: false,
+        };
+        assert_eq!(
+            ghost_inputs(&replay, game::Player::Left),
+            vec![
+                (0, InputAction::RightPaddleUp),
+                (4, InputAction::RightCatch)
+            ]
+        );
+        assert_eq!(pace_line(2, 3), "You 2 vs ghost 3 (-1)");
+    }
+}
@@ -1,93 +1,228 @@
-use std::io;
 use std::time::{Duration, Instant};
 
 use ratatui::Terminal;
 
 use crate::config::Config;
 use crate::debug;
+use crate::error::P2PongError;
+use crate::game::physics::PhysicsEvents;
 use crate::game::{self, poll_input_local_2p, GameState, InputAction};
+use crate::leaderboard;
+use crate::replay::Recorder;
 use crate::ui;
-use crate::FIXED_TIMESTEP;
 
-use super::common::limit_frame_rate;
+use super::common::{
+    game_over_lines, help_overlay, limit_frame_rate, next_game_overlay, poll_any_key, rematch_hint,
+    terminal_too_small, Bell, FpsCounter, HelpLayout, PhysicsClock,
+};
 
 /// Run local 2-player game (no networking)
+/// Results are recorded on the leaderboard when both players entered a name
 pub fn run_game_local<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     config: &Config,
-) -> Result<(), io::Error> {
-    debug::log("GAME_START", "Local 2-player mode");
+    left_name: &str,
+    right_name: &str,
+) -> Result<(), P2PongError> {
+    debug::info("GAME_START", "Local 2-player mode");
+
+    // Remember names for next time
+    let mut board = leaderboard::load();
+    board.last_left_name = left_name.to_string();
+    board.last_right_name = right_name.to_string();
+    if let Err(e) = leaderboard::save(&board) {
+        debug::warn("LEADERBOARD", &format!("Failed to save leaderboard: {}", e));
+    }
+
+    let ranked = !left_name.is_empty() && !right_name.is_empty() && left_name != right_name;
+    let mut result_recorded = false;
 
     let frame_duration = Duration::from_millis(1000 / config.display.target_fps);
 
     let size = terminal.size()?;
     let mut game_state = GameState::new(size.width, size.height, &config.physics);
+    game_state.goal_slow_motion = config.display.goal_slow_motion;
+    let mut clock = PhysicsClock::new(config.physics.tick_rate_hz);
+    let mut fps_counter = FpsCounter::new(config.display.show_fps);
+    let mut bell = Bell::new(config.display.sound_enabled);
+    let mut show_help = false; // Help overlay open (game paused)
+    let mut sides_swapped = false; // Players have switched seats (keys move the other paddle)
+    let mut recorder = config.replay.record.then(|| {
+        Recorder::new(
+            &config.physics,
+            game_state.paddle_scales,
+            None,
+            game_state.goal_slow_motion,
+        )
+    });
 
     loop {
         let now = Instant::now();
+        let fps = fps_counter.frame();
 
-        // Handle input (both paddles)
-        let actions = poll_input_local_2p(config)?;
+        // Handle input (both paddles); while help is open any key just closes it
+        let actions = if show_help {
+            show_help = !poll_any_key()?;
+            Vec::new()
+        } else {
+            poll_input_local_2p(config, sides_swapped)?
+        };
 
         for action in &actions {
+            // Only remaps the keys, so it isn't recorded and works while paused
+            if *action == InputAction::SwapSides {
+                sides_swapped = !sides_swapped;
+                continue;
+            }
+
+            // Score adjustment is for drills, never for leaderboard games
+            if ranked && action.adjusts_score() {
+                continue;
+            }
+
+            // While paused only system keys are handled
+            if game_state.paused
+                && !matches!(
+                    action,
+                    InputAction::Quit | InputAction::Pause | InputAction::ToggleHelp
+                )
+            {
+                continue;
+            }
+            if let Some(recorder) = &mut recorder {
+                recorder.input(*action);
+            }
+
             match action {
-                InputAction::Quit => return Ok(()),
+                InputAction::Quit => {
+                    if let Some(recorder) = &recorder {
+                        recorder.save();
+                    }
+                    return Ok(());
+                }
                 InputAction::Rematch => {
-                    if game_state.game_over {
+                    if game_state.match_over() {
                         game_state.reset_game();
+                        result_recorded = false;
                     }
                 }
                 InputAction::LeftPaddleUp => {
-                    game::physics::move_paddle_up(
-                        &mut game_state.left_paddle,
-                        game_state.tap_distance,
-                    );
+                    game::physics::paddle_input(&mut game_state, game::Player::Left, false);
                 }
                 InputAction::LeftPaddleDown => {
-                    game::physics::move_paddle_down(
-                        &mut game_state.left_paddle,
-                        game_state.field_height,
-                        game_state.tap_distance,
-                    );
+                    game::physics::paddle_input(&mut game_state, game::Player::Left, true);
                 }
                 InputAction::RightPaddleUp => {
-                    game::physics::move_paddle_up(
-                        &mut game_state.right_paddle,
-                        game_state.tap_distance,
-                    );
+                    game::physics::paddle_input(&mut game_state, game::Player::Right, false);
                 }
                 InputAction::RightPaddleDown => {
-                    game::physics::move_paddle_down(
-                        &mut game_state.right_paddle,
-                        game_state.field_height,
-                        game_state.tap_distance,
-                    );
+                    game::physics::paddle_input(&mut game_state, game::Player::Right, true);
+                }
+                InputAction::LeftCatch => {
+                    game::physics::press_catch(&mut game_state, game::Player::Left);
+                }
+                InputAction::RightCatch => {
+                    game::physics::press_catch(&mut game_state, game::Player::Right);
                 }
+                InputAction::ToggleHelp => show_help = true,
+                InputAction::Pause => game_state.toggle_pause(),
+                InputAction::LeftScoreUp => game_state.adjust_score(game::Player::Left, true),
+                InputAction::LeftScoreDown => game_state.adjust_score(game::Player::Left, false),
+                InputAction::RightScoreUp => game_state.adjust_score(game::Player::Right, true),
+                InputAction::RightScoreDown => game_state.adjust_score(game::Player::Right, false),
+                // Not emitted by the two-player keymap
+                InputAction::OpenChat
+                | InputAction::BallFaster
+                | InputAction::BallSlower
+                | InputAction::SwapSides
+                | InputAction::ToggleConnectionInfo
+                | InputAction::SaveHighlight => {}
             }
         }
 
-        // Update physics
-        let _events = game::update_with_events(&mut game_state, FIXED_TIMESTEP);
+        // Update physics at the fixed tick rate (the clock is drained while paused
+        // or the terminal is too small, so resuming doesn't replay that time)
+        clock.set_rate(game_state.time_scale());
+        let ticks = clock.ticks();
+        if !show_help && !terminal_too_small(terminal)? {
+            let mut frame_events = PhysicsEvents::default();
+            for _ in 0..ticks {
+                frame_events.merge(game::update_with_events(&mut game_state, clock.timestep()));
+                if let Some(recorder) = &mut recorder {
+                    recorder.tick();
+                }
+            }
+            bell.play(&frame_events)?;
+        }
 
-        // Create overlay message if game is over
-        let overlay = if game_state.game_over {
-            let winner_text = match game_state
+        // Create overlay message if help is open or the game is over
+        let overlay = if show_help {
+            Some(help_overlay(
+                config,
+                HelpLayout::TwoPlayer {
+                    swapped: sides_swapped,
+                    score_keys: !ranked,
+                },
+                true,
+            ))
+        } else if game_state.game_over && !game_state.match_over() {
+            let winner_name = match (game_state.winner, ranked) {
+                (Some(game::Player::Left), true) => left_name,
+                (Some(game::Player::Right), true) => right_name,
+                (Some(game::Player::Left), false) => "left",
+                _ => "right",
+            };
+            Some(next_game_overlay(&game_state, winner_name).with_display_config(&config.display))
+        } else if game_state.game_over {
+            let winner = game_state
                 .winner
-                .expect("game_over is true but winner is None")
-            {
-                game::Player::Left => "LEFT WINS",
-                game::Player::Right => "RIGHT WINS",
+                .expect("game_over is true but winner is None");
+
+            // Record the result once per match
+            if ranked && !result_recorded {
+                let (winner_name, loser_name) = match winner {
+                    game::Player::Left => (left_name, right_name),
+                    game::Player::Right => (right_name, left_name),
+                };
+                board.record_result(winner_name, loser_name);
+                if let Err(e) = leaderboard::save(&board) {
+                    debug::warn("LEADERBOARD", &format!("Failed to save leaderboard: {}", e));
+                }
+                result_recorded = true;
+            }
+
+            let winner_text = match (winner, ranked) {
+                (game::Player::Left, true) => format!("{} WINS", left_name.to_uppercase()),
+                (game::Player::Right, true) => format!("{} WINS", right_name.to_uppercase()),
+                (game::Player::Left, false) => "LEFT WINS".to_string(),
+                (game::Player::Right, false) => "RIGHT WINS".to_string(),
             };
-            Some(ui::OverlayMessage::info(vec![
-                winner_text.to_string(),
-                "".to_string(),
-                "R to Rematch  |  Q to Quit".to_string(),
-            ]))
+            Some(
+                ui::OverlayMessage::info(game_over_lines(
+                    &game_state,
+                    winner_text,
+                    rematch_hint(config),
+                ))
+                .with_display_config(&config.display),
+            )
         } else {
             None
         };
 
-        terminal.draw(|f| ui::render(f, &game_state, None, overlay.as_ref(), None))?;
+        terminal.draw(|f| {
+            ui::render(
+                f,
+                &game_state,
+                None,
+                overlay.as_ref(),
+                None,
+                ui::RenderOptions {
+                    fps,
+                    assist: config.display.assist,
+                    ..ui::RenderOptions::from_display(&config.display)
+                },
+            )
+        })?;
 
         // Frame rate limiting
         limit_frame_rate(now, frame_duration);
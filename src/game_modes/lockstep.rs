@@ -0,0 +1,257 @@
+// Deterministic lockstep for networked play
+// Both peers run the same simulation and only exchange inputs. Local inputs are scheduled
+// INPUT_DELAY_FRAMES ahead so they usually reach the peer before that frame is simulated,
+// and a frame only advances once both peers' inputs for it are known.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::game::{InputAction, Player};
+use crate::network::NetworkMessage;
+
+/// Frames between a key press and the frame it takes effect on (~25ms at 120Hz)
+pub const INPUT_DELAY_FRAMES: u64 = 3;
+
+/// Most elapsed ticks kept while stalled, so a late peer catches up without a long fast-forward
+const MAX_BACKLOG_TICKS: u32 = 8;
+
+/// Inputs both players made on one frame
+#[derive(Debug, Default, PartialEq)]
+pub struct FrameInputs {
+    pub left: Vec<InputAction>,
+    pub right: Vec<InputAction>,
+}
+
+/// Input buffer that decides when the next frame may be simulated
+pub struct Lockstep {
+    local_side: Player,
+    /// Next frame to simulate
+    frame: u64,
+    /// Elapsed ticks not yet simulated (waiting on the peer)
+    backlog: u32,
+    /// Local actions not yet assigned to a frame
+    pending: Vec<InputAction>,
+    /// Local inputs from `local_base` on, kept until simulated and acknowledged by the peer
+    local: VecDeque<Vec<InputAction>>,
+    local_base: u64,
+    /// First local frame the peer is still missing
+    peer_ack: u64,
+    /// Peer inputs received but not yet simulated, by frame
+    remote: BTreeMap<u64, Vec<InputAction>>,
+    /// Every peer frame before this has been received
+    remote_next: u64,
+}
+
+impl Lockstep {
+    pub fn new(local_side: Player) -> Self {
+        // Nobody can have pressed anything during the initial delay
+        let empty = || (0..INPUT_DELAY_FRAMES).map(|_| Vec::new());
+        Self {
+            local_side,
+            frame: 0,
+            backlog: 0,
+            pending: Vec::new(),
+            local: empty().collect(),
+            local_base: 0,
+            peer_ack: 0,
+            remote: (0..INPUT_DELAY_FRAMES).zip(empty()).collect(),
+            remote_next: INPUT_DELAY_FRAMES,
+        }
+    }
+
+    /// Queue a local gameplay action for the next scheduled frame
+    pub fn queue(&mut self, action: InputAction) {
+        self.pending.push(action);
+    }
+
+    /// Record newly elapsed physics ticks
+    pub fn add_ticks(&mut self, ticks: u32) {
+        self.backlog = (self.backlog + ticks).min(MAX_BACKLOG_TICKS);
+    }
+
+    /// Inputs for the next frame, if a tick is due and both peers' inputs for it are in
+    pub fn next_frame(&mut self) -> Option<FrameInputs> {
+        if self.backlog == 0 {
+            return None;
+        }
+
+        // Commit queued local actions to the frame INPUT_DELAY_FRAMES ahead
+        if self.local_next() <= self.frame + INPUT_DELAY_FRAMES {
+            self.local.push_back(std::mem::take(&mut self.pending));
+        }
+
+        let local = self
+            .local
+            .get((self.frame - self.local_base) as usize)?
+            .clone();
+        let remote = self.remote.remove(&self.frame)?;
+
+        self.frame += 1;
+        self.backlog -= 1;
+        self.prune_local();
+
+        Some(match self.local_side {
+            Player::Left => FrameInputs {
+                left: local,
+                right: remote,
+            },
+            Player::Right => FrameInputs {
+                left: remote,
+                right: local,
+            },
+        })
+    }
+
+    /// Store inputs from the peer (duplicates and already simulated frames are ignored)
+    pub fn receive(&mut self, first_frame: u64, inputs: Vec<Vec<InputAction>>, ack: u64) {
+        for (frame, actions) in (first_frame..).zip(inputs) {
+            if frame >= self.frame {
+                self.remote.entry(frame).or_insert(actions);
+            }
+        }
+        while self.remote.contains_key(&self.remote_next) {
+            self.remote_next += 1;
+        }
+
+        self.peer_ack = self.peer_ack.max(ack);
+        self.prune_local();
+    }
+
+    /// Message carrying every local frame the peer hasn't acknowledged yet
+    pub fn outgoing(&self) -> NetworkMessage {
+        let first_frame = self.peer_ack.max(self.local_base);
+        let skip = (first_frame - self.local_base) as usize;
+        NetworkMessage::LockstepInput {
+            first_frame,
+            inputs: self.local.iter().skip(skip).cloned().collect(),
+            ack: self.remote_next,
+        }
+    }
+
+    /// Whether local input is still waiting for its frame to be simulated
+    pub fn awaiting_local_input(&self) -> bool {
+        let simulated = (self.frame - self.local_base) as usize;
+        !self.pending.is_empty() || self.local.iter().skip(simulated).any(|a| !a.is_empty())
+    }
+
+    fn local_next(&self) -> u64 {
+        self.local_base + self.local.len() as u64
+    }
+
+    /// Drop local frames that are both simulated and acknowledged
+    fn prune_local(&mut self) {
+        while self.local_base < self.frame.min(self.peer_ack) && !self.local.is_empty() {
+            self.local.pop_front();
+            self.local_base += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deliver `from`'s outgoing message to `to`
+    fn deliver(from: &Lockstep, to: &mut Lockstep) {
+        if let NetworkMessage::LockstepInput {
+            first_frame,
+            inputs,
+            ack,
+        } = from.outgoing()
+        {
+            to.receive(first_frame, inputs, ack);
+        }
+    }
+
+    #[test]
+    fn test_both_peers_see_the_same_frames() {
+        let mut left = Lockstep::new(Player::Left);
+        let mut right = Lockstep::new(Player::Right);
+        left.queue(InputAction::LeftPaddleUp);
+        right.queue(InputAction::RightPaddleDown);
+
+        let mut left_frames = Vec::new();
+        let mut right_frames = Vec::new();
+        for _ in 0..10 {
+            left.add_ticks(1);
+            right.add_ticks(1);
+            left_frames.extend(left.next_frame());
+            right_frames.extend(right.next_frame());
+            deliver(&left, &mut right);
+            deliver(&right, &mut left);
+        }
+
+        assert!(left_frames.len() >= INPUT_DELAY_FRAMES as usize);
+        let shared = left_frames.len().min(right_frames.len());
+        assert_eq!(left_frames[..shared], right_frames[..shared]);
+
+        // Inputs land INPUT_DELAY_FRAMES after the press
+        let pressed = &left_frames[INPUT_DELAY_FRAMES as usize];
+        assert_eq!(pressed.left, vec![InputAction::LeftPaddleUp]);
+        assert_eq!(pressed.right, vec![InputAction::RightPaddleDown]);
+    }
+
+    #[test]
+    fn test_awaiting_local_input_until_simulated() {
+        let mut left = Lockstep::new(Player::Left);
+        let mut right = Lockstep::new(Player::Right);
+        assert!(!left.awaiting_local_input());
+
+        left.queue(InputAction::LeftPaddleUp);
+        for _ in 0..=INPUT_DELAY_FRAMES {
+            assert!(left.awaiting_local_input());
+            left.add_ticks(1);
+            right.add_ticks(1);
+            left.next_frame();
+            right.next_frame();
+            deliver(&left, &mut right);
+            deliver(&right, &mut left);
+        }
+        assert!(!left.awaiting_local_input());
+    }
+
+    #[test]
+    fn test_stalls_without_peer_and_recovers_from_loss() {
+        let mut left = Lockstep::new(Player::Left);
+        let mut right = Lockstep::new(Player::Right);
+
+        // The initial delay frames need nothing from the peer, the next one does
+        left.add_ticks(INPUT_DELAY_FRAMES as u32 + 1);
+        for _ in 0..INPUT_DELAY_FRAMES {
+            assert!(left.next_frame().is_some());
+        }
+        assert!(left.next_frame().is_none());
+
+        // A lost message is covered by the next one, which resends unacknowledged frames
+        right.add_ticks(2);
+        right.next_frame();
+        let _lost = right.outgoing();
+        right.next_frame();
+        deliver(&right, &mut left);
+        assert!(left.next_frame().is_some());
+    }
+
+    #[test]
+    fn test_acknowledged_frames_are_not_resent() {
+        let mut left = Lockstep::new(Player::Left);
+        let mut right = Lockstep::new(Player::Right);
+        for _ in 0..5 {
+            left.add_ticks(1);
+            right.add_ticks(1);
+            left.next_frame();
+            right.next_frame();
+            deliver(&left, &mut right);
+            deliver(&right, &mut left);
+        }
+
+        let NetworkMessage::LockstepInput {
+            first_frame,
+            inputs,
+            ..
+        } = left.outgoing()
+        else {
+            panic!("expected lockstep input");
+        };
+        assert_eq!(first_frame, right.remote_next);
+        assert!(inputs.is_empty());
+    }
+}
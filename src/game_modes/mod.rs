@@ -1,8 +1,17 @@
 mod ai;
 mod common;
+mod ghost;
 mod local;
+mod lockstep;
 mod network;
+mod practice;
+mod replay;
+mod spectate;
 
-pub use ai::run_game_vs_ai;
+pub use ai::{run_game_ai_vs_ai, run_game_vs_ai};
+pub use ghost::run_ghost;
 pub use local::run_game_local;
 pub use network::{run_game_network_client, run_game_network_host};
+pub use practice::run_game_practice;
+pub use replay::{run_highlight, run_replay};
+pub use spectate::run_game_spectate;
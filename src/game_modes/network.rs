@@ -1,53 +1,221 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::io;
 use std::time::{Duration, Instant};
 
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use ratatui::Terminal;
 
+use crate::clipboard;
+use crate::config::types::DisplayConfig;
 use crate::config::Config;
 use crate::debug;
+use crate::error::P2PongError;
+use crate::game::physics::{PaddleMode, PhysicsEvents};
+use crate::game::state::{PowerUp, NEXT_GAME_DELAY};
 use crate::game::{self, poll_input_player_left, poll_input_player_right, GameState, InputAction};
 use crate::menu;
+use crate::network::chat::{sanitize_chat, ChatLog, MAX_CHAT_LEN};
 use crate::network::client::NetworkEvent;
-use crate::network::{self, BallState, ConnectionMode, NetworkMessage};
+use crate::network::{
+    self, BallState, ConnectionMode, ConnectionStage, DisconnectReason, MatchSetup, NetMode,
+    NetworkMessage, PROTOCOL_VERSION,
+};
+use crate::recent_peers;
+use crate::replay::HighlightBuffer;
 use crate::ui;
-use crate::FIXED_TIMESTEP;
 use crate::POSITION_CORRECTION_ALPHA;
 use crate::POSITION_SNAP_THRESHOLD;
 
-use super::common::limit_frame_rate;
+use super::common::{
+    game_over_lines, help_overlay, limit_frame_rate, next_game_overlay, poll_any_key,
+    poll_text_input, rematch_hint, Bell, FpsCounter, HelpLayout, MouseSteering, PhysicsClock,
+    TextKey,
+};
+use super::lockstep::{FrameInputs, Lockstep};
+use super::spectate::SpectatorFeed;
 
-/// Player role determines who controls ball physics
+/// How long to wait for the peer's version handshake once the data channel is open
+const HELLO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often our version handshake is resent until the peer's arrives
+const HELLO_RESEND: Duration = Duration::from_millis(250);
+
+/// How long the "opponent left" overlay stays up before returning to the menu
+const SESSION_END_DISPLAY: Duration = Duration::from_secs(3);
+
+/// How often Ready is resent while waiting for the opponent's game to start
+const READY_RESEND: Duration = Duration::from_millis(250);
+
+/// How often the host resends its match setup until the client's Ready shows it arrived
+const SETUP_RESEND: Duration = Duration::from_millis(250);
+
+/// How long "Copied ..." shows on the host waiting screen
+const COPY_FEEDBACK: Duration = Duration::from_secs(2);
+
+/// Client treats the ball as predicted once the last BallSync is older than this
+const STALE_SYNC_THRESHOLD: Duration = Duration::from_millis(200);
+
+/// Player role determines who controls ball physics (independent of paddle side)
 #[derive(Debug)]
-enum PlayerRole {
-    Host,   // Controls ball physics (left paddle)
-    Client, // Receives ball state (right paddle)
+pub(super) enum PlayerRole {
+    Host,   // Controls ball physics, chooses its side via config
+    Client, // Receives ball state, plays the side the host didn't pick
+}
+
+/// Frame rate at which remote_paddle_smoothing is the share covered per frame; at
+/// other frame rates the share is scaled so the glide takes the same time
+const PADDLE_SMOOTHING_FPS: f32 = 60.0;
+
+/// Client-side smoothing for the opponent's paddle: their taps move a target, and the
+/// drawn paddle covers a share of the remaining distance each frame (like the ball's
+/// POSITION_CORRECTION_ALPHA). Only the look changes; the client doesn't simulate hits.
+struct RemotePaddleSmoothing {
+    side: game::Player,
+    alpha: f32,
+    target: Option<f32>, // Where the opponent's inputs put the paddle, if not there yet
+    drawn_y: f32,        // Where the paddle was left; anything else moved it since
+}
+
+impl RemotePaddleSmoothing {
+    /// Smoothing for a client in a host-authoritative tap-mode game, if enabled
+    fn new(state: &GameState, side: game::Player, smoothing: f32) -> Option<Self> {
+        let enabled = smoothing.is_finite()
+            && smoothing > 0.0
+            && smoothing < 1.0
+            && state.paddle_mode == PaddleMode::Tap;
+        enabled.then(|| Self {
+            side,
+            alpha: smoothing,
+            target: None,
+            drawn_y: state.paddle(side).y,
+        })
+    }
+
+    /// Move the paddle to its target so inputs apply from there; returns the drawn y
+    fn begin_inputs(&mut self, state: &mut GameState) -> f32 {
+        let paddle = state.paddle_mut(self.side);
+        let drawn_y = paddle.y;
+        match self.target {
+            // Something else (a new game) moved the paddle; the target is stale
+            Some(_) if drawn_y != self.drawn_y => self.target = None,
+            Some(target) => paddle.y = target,
+            None => {}
+        }
+        drawn_y
+    }
+
+    /// Take where the inputs left the paddle as the new target, and put it back
+    fn end_inputs(&mut self, state: &mut GameState, drawn_y: f32) {
+        let paddle = state.paddle_mut(self.side);
+        if paddle.y != drawn_y {
+            self.target = Some(paddle.y);
+        }
+        paddle.y = drawn_y;
+        self.drawn_y = drawn_y;
+    }
+
+    /// Move the drawn paddle toward the target for a frame of `elapsed` seconds
+    fn step(&mut self, state: &mut GameState, elapsed: f32) {
+        let share = 1.0 - (1.0 - self.alpha).powf(elapsed * PADDLE_SMOOTHING_FPS);
+        let paddle = state.paddle_mut(self.side);
+        if let Some(target) = self.target.filter(|_| paddle.y == self.drawn_y) {
+            paddle.y += (target - paddle.y) * share;
+            if (target - paddle.y).abs() < 0.5 {
+                paddle.y = target;
+                self.target = None;
+            }
+        } else {
+            self.target = None;
+        }
+        self.drawn_y = paddle.y;
+    }
 }
 
 /// Network synchronization state for a networked game session
 /// Replaces global AtomicU64 statics with proper local state
+#[derive(Default)]
 struct NetworkSyncState {
     /// Sequence number for ball state messages sent by host
     ball_sequence: u64,
 
-    /// Last received ball sequence number (client-side tracking)
-    last_received_sequence: u64,
+    /// Newest ball sequence received for each ball (client-side tracking)
+    ball_sequences: BallSequences,
+
+    /// When a ball sync was last applied (client-side tracking)
+    last_sync_at: Option<Instant>,
 
     /// Last measured round-trip time in milliseconds
     last_rtt_ms: u64,
 
+    /// Latest packet loss and jitter from the network thread
+    connection: Option<ui::ConnectionQuality>,
+
     /// Debug counter for input sends (used for logging first N inputs)
     input_send_count: u64,
+
+    /// Sequence of the newest opponent input applied (client and host alike)
+    last_input_sequence: Option<u32>,
+
+    /// Which of the INPUT_HISTORY sequences before last_input_sequence were applied
+    /// (bit n set = last_input_sequence - n - 1 was)
+    applied_inputs: u64,
+}
+
+/// How many opponent input sequences before the newest are remembered to spot repeats
+const INPUT_HISTORY: u32 = u64::BITS;
+
+impl NetworkSyncState {
+    /// Whether an opponent input should be applied: only if it wasn't already. Inputs
+    /// are relative moves, so one arriving late still has to count or the paddle drifts;
+    /// only repeats (and ones too old to tell) are dropped.
+    fn accept_input(&mut self, sequence: u32) -> bool {
+        let Some(newest) = self.last_input_sequence else {
+            self.last_input_sequence = Some(sequence);
+            return true;
+        };
+
+        // Compare by wrapping distance so the count can roll over
+        let ahead = sequence.wrapping_sub(newest) as i32;
+        if ahead > 0 {
+            let shift = ahead as u32;
+            self.applied_inputs = if shift >= INPUT_HISTORY {
+                0
+            } else {
+                (self.applied_inputs << shift) | (1 << (shift - 1))
+            };
+            self.last_input_sequence = Some(sequence);
+            return true;
+        }
+
+        let behind = ahead.unsigned_abs();
+        if behind == 0 || behind > INPUT_HISTORY {
+            return false;
+        }
+        let bit = 1 << (behind - 1);
+        let repeat = self.applied_inputs & bit != 0;
+        self.applied_inputs |= bit;
+        !repeat
+    }
 }
 
-impl Default for NetworkSyncState {
-    fn default() -> Self {
-        Self {
-            ball_sequence: 0,
-            last_received_sequence: 0,
-            last_rtt_ms: 0,
-            input_send_count: 0,
+/// Newest ball sync sequence applied to each ball. Tracked per ball: the syncs for one
+/// frame's balls each take the next sequence, and if they arrive out of order a later
+/// ball's sync must not make an earlier ball's look stale.
+#[derive(Default)]
+pub(super) struct BallSequences(HashMap<u8, u64>);
+
+impl BallSequences {
+    /// Whether `ball_state` is newer than the last sync applied to its ball (noting it if so)
+    pub(super) fn accept(&mut self, ball_state: &BallState) -> bool {
+        let newer = self
+            .0
+            .get(&ball_state.index)
+            .is_none_or(|&last| ball_state.sequence > last);
+        if newer {
+            self.0.insert(ball_state.index, ball_state.sequence);
         }
+        newer
     }
 }
 
@@ -55,26 +223,43 @@ impl Default for NetworkSyncState {
 pub fn run_game_network_host<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     config: &Config,
-) -> Result<(), io::Error> {
-    debug::log("GAME_START", "Network host mode");
+) -> Result<(), P2PongError> {
+    debug::info("GAME_START", "Network host mode");
 
     // Initialize network
     let network_client = network::start_network(
-        ConnectionMode::Listen,
+        ConnectionMode::Listen {
+            allow_spectators: config.network.allow_spectators,
+        },
         config.network.signaling_server.clone(),
+        config.network.require_tls,
+        &config.network.ice_servers,
+        config.network.ice_disconnect_timeout_secs,
     )?;
 
+    // Match settings sent to the client once the data channel opens
+    let setup = MatchSetup::from_config(config);
+
     // Wait for connection with TUI display
     match wait_for_connection_tui(
         terminal,
         &network_client,
         &PlayerRole::Host,
         None,
-        config.network.connection_timeout_secs,
+        Some(setup),
+        config,
     )? {
-        Some(_peer_id) => {
+        Some((setup, host_peer_id)) => {
             // Connection established, start game
-            run_game_networked(terminal, network_client, PlayerRole::Host, config)
+            run_game_networked(
+                terminal,
+                network_client,
+                PlayerRole::Host,
+                setup.host_side,
+                &setup,
+                &host_peer_id,
+                config,
+            )
         }
         None => {
             // User cancelled, return to menu
@@ -88,7 +273,7 @@ pub fn run_game_network_client<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     config: &Config,
     peer_id: &str,
-) -> Result<(), io::Error> {
+) -> Result<(), P2PongError> {
     debug::log(
         "GAME_START",
         &format!("Network client mode, peer: {}", peer_id),
@@ -100,19 +285,31 @@ pub fn run_game_network_client<B: ratatui::backend::Backend>(
             multiaddr: peer_id.to_string(),
         },
         config.network.signaling_server.clone(),
+        config.network.require_tls,
+        &config.network.ice_servers,
+        config.network.ice_disconnect_timeout_secs,
     )?;
 
-    // Wait for connection with TUI display
+    // Wait for connection (and the host's match setup) with TUI display
     match wait_for_connection_tui(
         terminal,
         &network_client,
         &PlayerRole::Client,
         Some(peer_id.to_string()),
-        config.network.connection_timeout_secs,
+        None,
+        config,
     )? {
-        Some(_peer_id) => {
-            // Connection established, start game
-            run_game_networked(terminal, network_client, PlayerRole::Client, config)
+        Some((setup, host_peer_id)) => {
+            // Connection established, start game on the side the host left free
+            run_game_networked(
+                terminal,
+                network_client,
+                PlayerRole::Client,
+                setup.host_side.opponent(),
+                &setup,
+                &host_peer_id,
+                config,
+            )
         }
         None => {
             // User cancelled, return to menu
@@ -126,20 +323,54 @@ fn run_game_networked<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     network_client: network::NetworkClient,
     player_role: PlayerRole,
+    local_side: game::Player,
+    setup: &MatchSetup, // Host's match settings, shared by both peers
+    host_peer_id: &str, // Spectators join with this ID
     config: &Config,
-) -> Result<(), io::Error> {
+) -> Result<(), P2PongError> {
     let game_start = Instant::now();
     let frame_duration = Duration::from_millis(1000 / config.display.target_fps);
     let backup_sync_interval = config.network.backup_sync_interval;
     let heartbeat_interval = Duration::from_millis(config.network.heartbeat_interval_ms);
+    let reconnect_timeout = Duration::from_secs(config.network.reconnect_timeout_secs);
+    let idle_timeout = Duration::from_secs(config.network.idle_timeout_mins.saturating_mul(60));
 
     let size = terminal.size()?;
-    let mut game_state = GameState::new(size.width, size.height, &config.physics);
+    // Host's match settings replace the local physics config on both peers
+    let physics = setup.physics();
+    let mut game_state = GameState::new(size.width, size.height, &physics);
     let mut frame_count: u64 = 0;
+    let mut clock = PhysicsClock::new(setup.tick_rate_hz);
+    let mut fps_counter = FpsCounter::new(config.display.show_fps);
+    let mut bell = Bell::new(config.display.sound_enabled);
+    let mut show_help = false; // Help overlay open (game keeps running underneath)
+    let mut show_connection_info = false; // Peer ID / RTT overlay (toggled, game keeps running)
+
+    // Chat: typed text goes to the prompt while it is open (the game keeps running)
+    let mut chat_log = ChatLog::default();
+    let mut chat_draft: Option<String> = None;
 
     // Network synchronization state (replaces global atomics)
     let mut sync_state = NetworkSyncState::default();
 
+    // Lockstep: both peers simulate and gameplay input goes through the frame buffer
+    let mut lockstep = (setup.net_mode == NetMode::Lockstep).then(|| Lockstep::new(local_side));
+
+    // Client: the opponent's taps glide their paddle rather than jump it
+    let mut remote_paddle = match (&player_role, &lockstep) {
+        (PlayerRole::Client, None) => RemotePaddleSmoothing::new(
+            &game_state,
+            local_side.opponent(),
+            config.network.remote_paddle_smoothing,
+        ),
+        _ => None,
+    };
+
+    // Host: the game as anyone watching sees it
+    let mut spectator_feed = (matches!(player_role, PlayerRole::Host)
+        && config.network.allow_spectators)
+        .then(SpectatorFeed::default);
+
     // RTT measurement
     let mut last_ping_time = Instant::now();
     let mut ping_timestamp: Option<u64> = None;
@@ -152,15 +383,78 @@ fn run_game_networked<B: ratatui::backend::Backend>(
     let mut local_wants_rematch = false;
     let mut peer_wants_rematch = false;
 
+    // Set while the connection is down; the game is frozen until it recovers or times out
+    let mut reconnecting_since: Option<Instant> = None;
+    let mut resync_after_reconnect = false;
+    let mut mouse_row = None;
+    let mut mouse_steering = MouseSteering::new();
+
+    // Start handshake: play begins once the opponent's game is running too
+    let mut peer_ready = false;
+    let mut last_ready_sent: Option<Instant> = None;
+
+    // Last input from either player (or frame of a match in progress), for the idle timeout
+    let mut last_activity = Instant::now();
+
+    // The point being played, for saving the decisive one from the game-over screen
+    let mut highlights = HighlightBuffer::new(config.display.target_fps);
+    let mut highlight_status: Option<String> = None;
+
     loop {
         let now = Instant::now();
+        let fps = fps_counter.frame();
 
-        // Handle local input (mode-aware based on role)
-        let local_actions = match player_role {
-            PlayerRole::Host => poll_input_player_left(config)?,
-            PlayerRole::Client => poll_input_player_right(config)?,
+        // Handle local input (mode-aware based on side); while help is open any key just closes it
+        let local_actions = if show_help {
+            show_help = !poll_any_key()?;
+            Vec::new()
+        } else if let Some(draft) = chat_draft.as_mut() {
+            let mut submitted = None;
+            for key in poll_text_input()? {
+                match key {
+                    TextKey::Char(c) if draft.chars().count() < MAX_CHAT_LEN => draft.push(c),
+                    TextKey::Char(_) => {}
+                    TextKey::Backspace => {
+                        draft.pop();
+                    }
+                    TextKey::Submit => submitted = Some(sanitize_chat(draft)),
+                    TextKey::Cancel => submitted = Some(String::new()),
+                }
+            }
+            if let Some(text) = submitted {
+                if !text.is_empty() {
+                    let _ =
+                        network_client.send_message(NetworkMessage::Chat { text: text.clone() });
+                    chat_log.push("You", &text);
+                }
+                chat_draft = None;
+            }
+            Vec::new()
+        } else {
+            let mut actions = match local_side {
+                game::Player::Left => poll_input_player_left(config, &mut mouse_row)?,
+                game::Player::Right => poll_input_player_right(config, &mut mouse_row)?,
+            };
+            // Lockstep applies input a few frames late; wait for it before steering again
+            if !lockstep
+                .as_ref()
+                .is_some_and(Lockstep::awaiting_local_input)
+            {
+                let size = terminal.size()?;
+                let field = ui::FieldArea::new(
+                    size.width,
+                    size.height,
+                    &game_state,
+                    config.display.preserve_aspect,
+                );
+                actions.extend(mouse_steering.steer(&game_state, local_side, mouse_row, &field));
+            }
+            actions
         };
 
+        // Events heard this frame (the client only learns of goals, from score updates)
+        let mut frame_events = PhysicsEvents::default();
+
         // Handle remote input and network events
         let mut remote_actions = Vec::new();
 
@@ -174,6 +468,17 @@ fn run_game_networked<B: ratatui::backend::Backend>(
             last_ping_time = Instant::now();
         }
 
+        // Tell the opponent our game is up until theirs is too. The client only starts
+        // once it has the match setup, so its Ready also acknowledges that; until then
+        // the host keeps resending the setup in case it was lost.
+        if !peer_ready && last_ready_sent.is_none_or(|sent| sent.elapsed() > READY_RESEND) {
+            if matches!(player_role, PlayerRole::Host) {
+                let _ = network_client.send_message(NetworkMessage::MatchSetup(setup.clone()));
+            }
+            let _ = network_client.send_message(NetworkMessage::Ready);
+            last_ready_sent = Some(now);
+        }
+
         // Send periodic heartbeat
         if last_heartbeat_time.elapsed() > heartbeat_interval {
             let _ = network_client.send_message(NetworkMessage::Heartbeat {
@@ -190,49 +495,60 @@ fn run_game_networked<B: ratatui::backend::Backend>(
         // Process network events
         while let Some(event) = network_client.try_recv_event() {
             match event {
-                NetworkEvent::ReceivedInput(action) => remote_actions.push(action),
-                NetworkEvent::ReceivedBallState(ball_state) => {
-                    if matches!(player_role, PlayerRole::Client) {
-                        if ball_state.sequence > sync_state.last_received_sequence {
-                            sync_state.last_received_sequence = ball_state.sequence;
-
-                            let error_x = ball_state.x - game_state.ball.x;
-                            let error_y = ball_state.y - game_state.ball.y;
-                            let error_magnitude = (error_x * error_x + error_y * error_y).sqrt();
-
-                            if error_magnitude > POSITION_SNAP_THRESHOLD {
-                                game_state.ball.x = ball_state.x;
-                                game_state.ball.y = ball_state.y;
-                            } else {
-                                game_state.ball.x += error_x * POSITION_CORRECTION_ALPHA;
-                                game_state.ball.y += error_y * POSITION_CORRECTION_ALPHA;
-                            }
-
-                            game_state.ball.vx = ball_state.vx;
-                            game_state.ball.vy = ball_state.vy;
-                        }
+                NetworkEvent::ReceivedInput { action, sequence } => {
+                    if !is_peer_input(local_side, action) {
+                        debug::warn(
+                            "GAME_PEER_INPUT",
+                            &format!("Ignored input the peer may not send: {:?}", action),
+                        );
+                    } else if sync_state.accept_input(sequence) {
+                        remote_actions.push(action);
+                    } else {
+                        debug::log(
+                            "GAME_STALE_INPUT",
+                            &format!("Dropped repeated input #{}: {:?}", sequence, action),
+                        );
                     }
                 }
+                NetworkEvent::ReceivedBallState(ball_state)
+                    if matches!(player_role, PlayerRole::Client)
+                        && sync_state.ball_sequences.accept(&ball_state) =>
+                {
+                    sync_state.last_sync_at = Some(Instant::now());
+                    apply_ball_state(&mut game_state, &ball_state);
+                }
                 NetworkEvent::ReceivedScore {
                     left,
                     right,
                     game_over,
+                    left_games,
+                    right_games,
+                    stats,
                 } => {
                     if matches!(player_role, PlayerRole::Client) {
-                        game_state.left_score = left;
-                        game_state.right_score = right;
-                        game_state.game_over = game_over;
-
-                        // Determine winner when game is over
-                        if game_over {
-                            if left > right {
-                                game_state.winner = Some(game::Player::Left);
-                            } else if right > left {
-                                game_state.winner = Some(game::Player::Right);
-                            }
+                        frame_events.goal_scored |= apply_score(
+                            &mut game_state,
+                            (left, right),
+                            game_over,
+                            (left_games, right_games),
+                        );
+                        if let Some(stats) = stats {
+                            game_state.match_stats = stats;
                         }
                     }
                 }
+                NetworkEvent::ReceivedPowerUps {
+                    powerups,
+                    left_paddle_height,
+                    right_paddle_height,
+                } if matches!(player_role, PlayerRole::Client) => {
+                    apply_powerups(
+                        &mut game_state,
+                        powerups,
+                        left_paddle_height,
+                        right_paddle_height,
+                    );
+                }
                 NetworkEvent::ReceivedPing { timestamp_ms } => {
                     let _ = network_client.send_message(NetworkMessage::Pong { timestamp_ms });
                 }
@@ -246,6 +562,15 @@ fn run_game_networked<B: ratatui::backend::Backend>(
                         }
                     }
                 }
+                NetworkEvent::ConnectionStats {
+                    loss_pct,
+                    jitter_ms,
+                } => {
+                    sync_state.connection = Some(ui::ConnectionQuality {
+                        loss_pct,
+                        jitter_ms,
+                    });
+                }
                 NetworkEvent::ReceivedRematchRequest => {
                     peer_wants_rematch = true;
                     // If both want rematch, send confirm and reset
@@ -263,30 +588,179 @@ fn run_game_networked<B: ratatui::backend::Backend>(
                     peer_wants_rematch = false;
                 }
                 NetworkEvent::ReceivedQuitRequest => {
-                    // Peer wants to quit, exit immediately
-                    return Ok(());
+                    // Peer wants to quit, tell the player before leaving
+                    debug::log("PEER_QUIT", "Opponent quit the game");
+                    return show_session_ended(
+                        terminal,
+                        &config.display,
+                        &game_state,
+                        Some(sync_state.last_rtt_ms),
+                        Some(local_side),
+                        ui::OverlayMessage::info(vec![
+                            "Opponent quit".to_string(),
+                            "".to_string(),
+                            "Opponent left the game".to_string(),
+                            "".to_string(),
+                            "Press any key to return to menu".to_string(),
+                        ])
+                        .with_display_config(&config.display),
+                    );
                 }
-                NetworkEvent::Disconnected => {
-                    return Ok(());
+                NetworkEvent::Reconnecting if !reconnect_timeout.is_zero() => {
+                    debug::log(
+                        "PEER_RECONNECTING",
+                        "Connection dropped, waiting to recover",
+                    );
+                    reconnecting_since.get_or_insert(now);
                 }
-                NetworkEvent::Error(_msg) => {
-                    // Network errors are logged internally; continue game loop
+                NetworkEvent::Reconnected => {
+                    debug::log("PEER_RECONNECTED", "Connection recovered");
+                    reconnecting_since = None;
+                    resync_after_reconnect = true;
+                }
+                NetworkEvent::Disconnected(_) | NetworkEvent::Reconnecting => {
+                    // With no reconnect window, a dropped ICE path ends the game at once
+                    let reason = match event {
+                        NetworkEvent::Disconnected(reason) => reason,
+                        _ => DisconnectReason::IceFailure,
+                    };
+                    debug::log(
+                        "PEER_DISCONNECTED",
+                        &format!("Connection to opponent lost: {:?}", reason),
+                    );
+                    return show_session_ended(
+                        terminal,
+                        &config.display,
+                        &game_state,
+                        Some(sync_state.last_rtt_ms),
+                        Some(local_side),
+                        disconnect_overlay(reason).with_display_config(&config.display),
+                    );
+                }
+                NetworkEvent::ReceivedLockstepInput {
+                    first_frame,
+                    inputs,
+                    ack,
+                } => {
+                    if let Some(lockstep) = lockstep.as_mut() {
+                        lockstep.receive(first_frame, inputs, ack);
+                    }
+                }
+                NetworkEvent::PeerReady => {
+                    // Answered even once playing: our earlier Readys may have been lost
+                    if peer_ready {
+                        let _ = network_client.send_message(NetworkMessage::Ready);
+                    } else {
+                        debug::log("PEER_READY", "Opponent ready, starting play");
+                        peer_ready = true;
+                    }
+                }
+                // The opponent is still waiting for our handshake: their game only
+                // starts (and sends Ready) once it has arrived
+                NetworkEvent::PeerHello { .. } if !peer_ready => {
+                    let _ = network_client.send_message(NetworkMessage::Hello {
+                        protocol_version: PROTOCOL_VERSION,
+                    });
+                }
+                NetworkEvent::ReceivedChat(text) => chat_log.push("Opponent", &text),
+                // The network thread has failed: nothing more will arrive
+                NetworkEvent::Error(msg) => {
+                    debug::error("NET_ERROR", &format!("Network error: {}", msg));
+                    return show_session_ended(
+                        terminal,
+                        &config.display,
+                        &game_state,
+                        Some(sync_state.last_rtt_ms),
+                        Some(local_side),
+                        ui::OverlayMessage::error(vec![
+                            "Connection error".to_string(),
+                            "".to_string(),
+                            msg,
+                            "".to_string(),
+                            "Press any key to return to menu".to_string(),
+                        ])
+                        .with_display_config(&config.display),
+                    );
                 }
                 _ => {}
             }
         }
 
+        // Give up on a dropped connection once the reconnect window has passed
+        if reconnecting_since.is_some_and(|since| since.elapsed() > reconnect_timeout) {
+            debug::warn("PEER_RECONNECT_TIMEOUT", "Connection did not recover");
+            return show_session_ended(
+                terminal,
+                &config.display,
+                &game_state,
+                Some(sync_state.last_rtt_ms),
+                Some(local_side),
+                disconnect_overlay(DisconnectReason::Timeout).with_display_config(&config.display),
+            );
+        }
+
+        // Nobody playing (e.g. both away from the game-over screen): free the connection
+        if !local_actions.is_empty() || !remote_actions.is_empty() || !game_state.match_over() {
+            last_activity = now;
+        }
+        if !idle_timeout.is_zero() && last_activity.elapsed() > idle_timeout {
+            debug::log("IDLE_TIMEOUT", "No activity, ending the session");
+            let _ = network_client.send_message(NetworkMessage::QuitRequest);
+            let _ = network_client.disconnect();
+            return show_session_ended(
+                terminal,
+                &config.display,
+                &game_state,
+                Some(sync_state.last_rtt_ms),
+                Some(local_side),
+                ui::OverlayMessage::info(vec![
+                    "Session ended".to_string(),
+                    "".to_string(),
+                    format!(
+                        "No activity for {} minutes",
+                        config.network.idle_timeout_mins
+                    ),
+                    "".to_string(),
+                    "Press any key to return to menu".to_string(),
+                ])
+                .with_display_config(&config.display),
+            );
+        }
+
         // Process all actions
+        let remote_drawn_y = remote_paddle
+            .as_mut()
+            .map(|smoothing| smoothing.begin_inputs(&mut game_state));
         for action in local_actions.iter().chain(remote_actions.iter()) {
+            // While reconnecting, only quit, help and connection info are handled
+            // (paddles stay put)
+            if reconnecting_since.is_some()
+                && !matches!(
+                    action,
+                    InputAction::Quit | InputAction::ToggleHelp | InputAction::ToggleConnectionInfo
+                )
+            {
+                continue;
+            }
+
+            // Lockstep applies gameplay input when its frame is simulated, on both peers
+            if let Some(lockstep) = lockstep.as_mut() {
+                if is_own_input(local_side, *action) || *action == InputAction::Rematch {
+                    lockstep.queue(*action);
+                    continue;
+                }
+            }
+
             match action {
                 InputAction::Quit => {
-                    // Send quit request to peer and exit
+                    // Send quit request to peer, close the connection and exit
                     let _ = network_client.send_message(NetworkMessage::QuitRequest);
+                    let _ = network_client.disconnect();
                     return Ok(());
                 }
                 InputAction::Rematch => {
-                    // Only handle rematch if game is over
-                    if game_state.game_over {
+                    // Only handle rematch once the match is over
+                    if game_state.match_over() {
                         local_wants_rematch = true;
                         // Send rematch request to peer
                         let _ = network_client.send_message(NetworkMessage::RematchRequest);
@@ -300,175 +774,602 @@ fn run_game_networked<B: ratatui::backend::Backend>(
                     }
                 }
                 InputAction::LeftPaddleUp => {
-                    game::physics::move_paddle_up(
-                        &mut game_state.left_paddle,
-                        game_state.tap_distance,
-                    );
+                    game::physics::paddle_input(&mut game_state, game::Player::Left, false);
                 }
                 InputAction::LeftPaddleDown => {
-                    game::physics::move_paddle_down(
-                        &mut game_state.left_paddle,
-                        game_state.field_height,
-                        game_state.tap_distance,
-                    );
+                    game::physics::paddle_input(&mut game_state, game::Player::Left, true);
                 }
                 InputAction::RightPaddleUp => {
-                    game::physics::move_paddle_up(
-                        &mut game_state.right_paddle,
-                        game_state.tap_distance,
-                    );
+                    game::physics::paddle_input(&mut game_state, game::Player::Right, false);
                 }
                 InputAction::RightPaddleDown => {
-                    game::physics::move_paddle_down(
-                        &mut game_state.right_paddle,
-                        game_state.field_height,
-                        game_state.tap_distance,
-                    );
+                    game::physics::paddle_input(&mut game_state, game::Player::Right, true);
+                }
+                // Local only (never sent to the peer); no pause in networked play
+                InputAction::ToggleHelp => show_help = true,
+                InputAction::ToggleConnectionInfo => show_connection_info = !show_connection_info,
+                // Pausing is intentionally unsupported online: a peer can't be paused
+                InputAction::Pause => {}
+                InputAction::OpenChat => chat_draft = Some(String::new()),
+                InputAction::SaveHighlight if game_state.match_over() => {
+                    let names = match local_side {
+                        game::Player::Left => ("You", "Opponent"),
+                        game::Player::Right => ("Opponent", "You"),
+                    };
+                    let saved = highlights
+                        .highlight(&physics, config.display.target_fps, names)
+                        .ok_or_else(|| io::Error::other("nothing was captured"))
+                        .and_then(|highlight| highlight.save());
+                    highlight_status = Some(match saved {
+                        Ok(path) => format!("Highlight saved to {}", path.display()),
+                        Err(e) => format!("Couldn't save highlight: {}", e),
+                    });
+                }
+                InputAction::SaveHighlight => {}
+                InputAction::BallFaster | InputAction::BallSlower => {} // Practice only
+                InputAction::SwapSides => {}                            // Local 2-player only
+                InputAction::LeftScoreUp
+                | InputAction::LeftScoreDown
+                | InputAction::RightScoreUp
+                | InputAction::RightScoreDown => {} // Never in networked games
+                InputAction::LeftCatch | InputAction::RightCatch => {
+                    // Catches are resolved by the host's physics only
+                    if matches!(player_role, PlayerRole::Host) {
+                        let player = if *action == InputAction::LeftCatch {
+                            game::Player::Left
+                        } else {
+                            game::Player::Right
+                        };
+                        game::physics::press_catch(&mut game_state, player);
+                    }
                 }
             }
         }
 
-        // Send local inputs to opponent
-        for action in &local_actions {
-            let should_send = match (&player_role, action) {
-                (PlayerRole::Host, InputAction::LeftPaddleUp) => true,
-                (PlayerRole::Host, InputAction::LeftPaddleDown) => true,
-                (PlayerRole::Client, InputAction::RightPaddleUp) => true,
-                (PlayerRole::Client, InputAction::RightPaddleDown) => true,
-                _ => false,
-            };
+        if let (Some(smoothing), Some(drawn_y)) = (remote_paddle.as_mut(), remote_drawn_y) {
+            smoothing.end_inputs(&mut game_state, drawn_y);
+        }
 
-            if should_send && *action != InputAction::Quit {
-                if sync_state.input_send_count < 5 {
-                    debug::log(
-                        "GAME_INPUT",
-                        &format!(
-                            "Sending input #{}: {:?}",
-                            sync_state.input_send_count, action
-                        ),
-                    );
+        // Send local inputs to opponent (lockstep sends them per frame instead)
+        if lockstep.is_none() {
+            for action in &local_actions {
+                if is_own_input(local_side, *action) {
+                    if sync_state.input_send_count < 5 {
+                        debug::log(
+                            "GAME_INPUT",
+                            &format!(
+                                "Sending input #{}: {:?}",
+                                sync_state.input_send_count, action
+                            ),
+                        );
+                    }
+                    sync_state.input_send_count += 1;
+                    let _ = network_client.send_input(*action);
                 }
-                sync_state.input_send_count += 1;
-                let _ = network_client.send_input(*action);
             }
         }
 
+        // Physics is frozen until the opponent is ready and while reconnecting; the clock
+        // is still drained so play resumes without a burst of catch-up ticks
+        let ticks = clock.ticks();
+        let ticks = if !peer_ready || reconnecting_since.is_some() {
+            0
+        } else {
+            ticks
+        };
+
         // Update physics based on role
-        match player_role {
-            PlayerRole::Host => {
+        match (&player_role, lockstep.as_mut()) {
+            // Lockstep: both peers step the same frames with the same inputs
+            (_, Some(lockstep)) => {
+                lockstep.add_ticks(ticks);
+                while let Some(inputs) = lockstep.next_frame() {
+                    if !inputs.left.is_empty() || !inputs.right.is_empty() {
+                        last_activity = now;
+                    }
+                    apply_frame_inputs(
+                        &mut game_state,
+                        &inputs,
+                        local_side,
+                        &mut local_wants_rematch,
+                        &mut peer_wants_rematch,
+                    );
+                    frame_events.merge(game::update_with_events(&mut game_state, clock.timestep()));
+                }
+
+                // Resent every frame: it doubles as the ack and recovers lost messages
+                if reconnecting_since.is_none() {
+                    let _ = network_client.send_message(lockstep.outgoing());
+                }
+            }
+            (PlayerRole::Host, None) => {
                 let prev_left_score = game_state.left_score;
                 let prev_right_score = game_state.right_score;
+                let prev_game_over = game_state.game_over;
+
+                // Step physics at the fixed tick rate, collecting events across ticks
+                let mut physics_events = PhysicsEvents::default();
+                let mut backup_due = false;
+                for _ in 0..ticks {
+                    let events = game::update_with_events(&mut game_state, clock.timestep());
+                    physics_events.merge(events);
+                    frame_events.merge(events);
 
-                let physics_events = game::update_with_events(&mut game_state, FIXED_TIMESTEP);
-                frame_count += 1;
+                    frame_count += 1;
+                    backup_due |= frame_count.is_multiple_of(backup_sync_interval);
+                }
 
-                // Send score sync if changed
+                // Send score sync if changed (or the peer may have missed it while disconnected)
+                // (a timed game can also end without a goal)
                 if game_state.left_score != prev_left_score
                     || game_state.right_score != prev_right_score
+                    || game_state.game_over != prev_game_over
+                    || resync_after_reconnect
                 {
                     let msg = NetworkMessage::ScoreSync {
                         left: game_state.left_score,
                         right: game_state.right_score,
                         game_over: game_state.game_over,
+                        left_games: game_state.match_state.left_games,
+                        right_games: game_state.match_state.right_games,
+                        stats: game_state.game_over.then_some(game_state.match_stats),
                     };
                     let _ = network_client.send_message(msg);
                 }
 
-                // Event-based ball sync + periodic backup
-                let should_sync = physics_events.any() || frame_count % backup_sync_interval == 0;
+                // Also repeated with the backup ball sync, in case the change was lost
+                if physics_events.powerup_changed
+                    || (backup_due && game_state.powerups_enabled)
+                    || resync_after_reconnect
+                {
+                    let msg = NetworkMessage::PowerUpEvent {
+                        powerups: game_state.powerups.clone(),
+                        left_paddle_height: game_state.left_paddle.height,
+                        right_paddle_height: game_state.right_paddle.height,
+                    };
+                    let _ = network_client.send_message(msg);
+                }
+
+                // Event-based ball sync + periodic backup (a speed boost changes the ball too)
+                let should_sync = physics_events.any()
+                    || physics_events.powerup_changed
+                    || backup_due
+                    || resync_after_reconnect;
+                resync_after_reconnect = false;
 
                 if should_sync {
-                    let sequence = sync_state.ball_sequence;
-                    sync_state.ball_sequence += 1;
-                    let ball_state = BallState {
-                        x: game_state.ball.x,
-                        y: game_state.ball.y,
-                        vx: game_state.ball.vx,
-                        vy: game_state.ball.vy,
-                        sequence,
-                        timestamp_ms: now.elapsed().as_millis() as u64,
-                    };
+                    // One BallSync per ball, each with its own sequence number
+                    for (index, ball) in game_state.balls().enumerate() {
+                        let sequence = sync_state.ball_sequence;
+                        sync_state.ball_sequence += 1;
+                        let ball_state = BallState {
+                            index: index as u8,
+                            x: ball.x,
+                            y: ball.y,
+                            vx: ball.vx,
+                            vy: ball.vy,
+                            sequence,
+                            timestamp_ms: now.elapsed().as_millis() as u64,
+                        };
 
-                    if sequence % 30 == 0 {
-                        debug::log(
-                            "GAME_SEND_MARKER",
-                            &format!("Sending seq={} at frame={}", sequence, frame_count),
-                        );
-                    }
+                        if sequence % 30 == 0 {
+                            debug::log(
+                                "GAME_SEND_MARKER",
+                                &format!("Sending seq={} at frame={}", sequence, frame_count),
+                            );
+                        }
 
-                    let msg = NetworkMessage::BallSync(ball_state);
-                    if let Err(e) = network_client.send_message(msg) {
-                        debug::log(
-                            "GAME_SEND_ERROR",
-                            &format!("Failed to send seq={}: {}", sequence, e),
-                        );
+                        let msg = NetworkMessage::BallSync(ball_state);
+                        if let Err(e) = network_client.send_message(msg) {
+                            debug::log(
+                                "GAME_SEND_ERROR",
+                                &format!("Failed to send seq={}: {}", sequence, e),
+                            );
+                        }
                     }
                 }
             }
-            PlayerRole::Client => {
-                // Dead reckoning
-                game_state.ball.x += game_state.ball.vx * FIXED_TIMESTEP;
-                game_state.ball.y += game_state.ball.vy * FIXED_TIMESTEP;
+            (PlayerRole::Client, None) => {
+                // Dead reckoning (smooth-mode paddles glide locally too)
+                for _ in 0..ticks {
+                    // The host ends and starts games; these only run the clocks shown meanwhile
+                    if game_state.game_over {
+                        game_state.next_game_in -= clock.timestep();
+                    } else {
+                        game_state.elapsed += clock.timestep();
+                    }
+                    game::physics::update_paddles(&mut game_state, clock.timestep());
+                    game_state.push_ball_trail();
+                    let dt = clock.timestep();
+                    for ball in game_state.balls_mut() {
+                        ball.x += ball.vx * dt;
+                        ball.y += ball.vy * dt;
+                    }
+                }
+                if let Some(smoothing) = remote_paddle.as_mut() {
+                    smoothing.step(&mut game_state, clock.frame_secs());
+                }
             }
         }
 
+        if let (Some(feed), None) = (spectator_feed.as_mut(), reconnecting_since) {
+            feed.send(&network_client, &game_state);
+        }
+
+        if peer_ready && reconnecting_since.is_none() {
+            highlights.record(&game_state);
+        }
+        if !game_state.match_over() {
+            highlight_status = None;
+        }
+
+        bell.play(&frame_events)?;
+
         // Render with overlay for game over and rematch status
         let rtt_ms = Some(sync_state.last_rtt_ms);
-        let overlay = if game_state.game_over {
-            // Determine winner text based on role and winner
-            let winner_text = match (game_state.winner, &player_role) {
-                (Some(game::Player::Left), PlayerRole::Host) => "YOU WIN!",
-                (Some(game::Player::Left), PlayerRole::Client) => "YOU LOSE",
-                (Some(game::Player::Right), PlayerRole::Host) => "YOU LOSE",
-                (Some(game::Player::Right), PlayerRole::Client) => "YOU WIN!",
-                (None, _) => "GAME OVER",
+        let overlay = if show_help {
+            Some(help_overlay(config, HelpLayout::SinglePlayer, false))
+        } else if show_connection_info {
+            Some(
+                ui::OverlayMessage::info(connection_info_lines(
+                    &player_role,
+                    host_peer_id,
+                    config.network.allow_spectators,
+                    sync_state.last_rtt_ms,
+                    sync_state.connection,
+                    &config.keybindings.connection_info,
+                ))
+                .with_display_config(&config.display),
+            )
+        } else if !peer_ready {
+            Some(
+                ui::OverlayMessage::info(vec![
+                    "Waiting for opponent to be ready...".to_string(),
+                    "".to_string(),
+                    format!("{} to Quit", config.keybindings.quit),
+                ])
+                .with_display_config(&config.display),
+            )
+        } else if let Some(since) = reconnecting_since {
+            let remaining = reconnect_timeout.saturating_sub(since.elapsed());
+            Some(
+                ui::OverlayMessage::error(vec![
+                    "Reconnecting...".to_string(),
+                    "".to_string(),
+                    format!("Connection interrupted ({}s left)", remaining.as_secs() + 1),
+                    "".to_string(),
+                    format!("{} to Quit", config.keybindings.quit),
+                ])
+                .with_display_config(&config.display),
+            )
+        } else if game_state.game_over && !game_state.match_over() {
+            let winner_name = match game_state.winner {
+                Some(winner) if winner == local_side => "you",
+                Some(_) => "opponent",
+                None => "nobody",
+            };
+            Some(next_game_overlay(&game_state, winner_name).with_display_config(&config.display))
+        } else if game_state.game_over {
+            // Determine winner text based on our side and winner
+            let winner_text = match game_state.winner {
+                Some(winner) if winner == local_side => "YOU WIN!",
+                Some(_) => "YOU LOSE",
+                None => "DRAW",
             };
 
             // Build status message based on rematch state
             let status_text = if local_wants_rematch && peer_wants_rematch {
-                "Both ready! Restarting..."
+                "Both ready! Restarting...".to_string()
             } else if local_wants_rematch {
-                "Waiting for opponent..."
+                "Waiting for opponent...".to_string()
             } else if peer_wants_rematch {
-                "Opponent ready! Press R to Rematch"
+                format!(
+                    "Opponent ready! Press {} to Rematch",
+                    config.keybindings.rematch
+                )
             } else {
-                "R to Rematch  |  Q to Quit"
+                rematch_hint(config)
             };
 
-            Some(ui::OverlayMessage::info(vec![
-                winner_text.to_string(),
-                "".to_string(),
-                status_text.to_string(),
-            ]))
+            let mut lines = game_over_lines(&game_state, winner_text.to_string(), status_text);
+            lines.push(highlight_status.clone().unwrap_or_else(|| {
+                format!(
+                    "{} to save the decisive point",
+                    config.keybindings.save_highlight
+                )
+            }));
+            Some(ui::OverlayMessage::info(lines).with_display_config(&config.display))
         } else {
             None
         };
 
-        // Determine your player based on role
-        let your_player = match player_role {
-            PlayerRole::Host => Some(game::Player::Left),
-            PlayerRole::Client => Some(game::Player::Right),
-        };
+        let your_player = Some(local_side);
 
-        terminal.draw(|f| ui::render(f, &game_state, rtt_ms, overlay.as_ref(), your_player))?;
+        // Client: flag the ball as a guess when syncs have gone quiet
+        let ball_predicted = config.display.show_prediction_indicator
+            && matches!(player_role, PlayerRole::Client)
+            && lockstep.is_none()
+            && !game_state.game_over
+            && sync_state
+                .last_sync_at
+                .is_none_or(|at| at.elapsed() > STALE_SYNC_THRESHOLD);
+
+        terminal.draw(|f| {
+            ui::render(
+                f,
+                &game_state,
+                rtt_ms,
+                overlay.as_ref(),
+                your_player,
+                ui::RenderOptions {
+                    ball_predicted,
+                    fps,
+                    chat_lines: chat_log.lines(),
+                    chat_input: chat_draft.as_deref(),
+                    connection: sync_state.connection,
+                    ..ui::RenderOptions::from_display(&config.display)
+                },
+            )
+        })?;
 
         // Frame rate limiting
         limit_frame_rate(now, frame_duration);
     }
 }
 
+/// Connection info overlay: the host's peer ID (to invite spectators, if allowed) and
+/// link quality
+fn connection_info_lines(
+    player_role: &PlayerRole,
+    host_peer_id: &str,
+    allow_spectators: bool,
+    rtt_ms: u64,
+    connection: Option<ui::ConnectionQuality>,
+    close_key: &str,
+) -> Vec<String> {
+    let mut lines = vec!["CONNECTION".to_string(), "".to_string()];
+    match player_role {
+        PlayerRole::Host => {
+            lines.push(format!("Your peer ID: {}", host_peer_id));
+            lines.push(if allow_spectators {
+                "Spectators can watch by joining with this ID".to_string()
+            } else {
+                "Spectators are off (network.allow_spectators)".to_string()
+            });
+        }
+        PlayerRole::Client => lines.push(format!("Host peer ID: {}", host_peer_id)),
+    }
+    lines.push("".to_string());
+    lines.push(format!("RTT: {} ms", rtt_ms));
+    if let Some(quality) = connection {
+        lines.push(format!(
+            "Packet loss: {:.1}%   Jitter: {:.0} ms",
+            quality.loss_pct, quality.jitter_ms
+        ));
+    }
+    lines.push("".to_string());
+    lines.push(format!("Press {} to close", close_key));
+    lines
+}
+
+/// Whether `action` moves or catches with the paddle on `side`
+fn is_own_input(side: game::Player, action: InputAction) -> bool {
+    matches!(
+        (side, action),
+        (
+            game::Player::Left,
+            InputAction::LeftPaddleUp | InputAction::LeftPaddleDown | InputAction::LeftCatch
+        ) | (
+            game::Player::Right,
+            InputAction::RightPaddleUp | InputAction::RightPaddleDown | InputAction::RightCatch
+        )
+    )
+}
+
+/// Whether the peer may send `action`: moves of its own paddle and a rematch request.
+/// Anything else (chat, overlays, highlights, quit) would act on our side of the game.
+fn is_peer_input(local_side: game::Player, action: InputAction) -> bool {
+    is_own_input(local_side.opponent(), action) || action == InputAction::Rematch
+}
+
+/// Apply one lockstep frame's inputs; a rematch starts on the frame both players have asked
+fn apply_frame_inputs(
+    game_state: &mut GameState,
+    inputs: &FrameInputs,
+    local_side: game::Player,
+    local_wants_rematch: &mut bool,
+    peer_wants_rematch: &mut bool,
+) {
+    for (side, actions) in [
+        (game::Player::Left, &inputs.left),
+        (game::Player::Right, &inputs.right),
+    ] {
+        for &action in actions {
+            match action {
+                InputAction::Rematch if game_state.match_over() => {
+                    if side == local_side {
+                        *local_wants_rematch = true;
+                    } else {
+                        *peer_wants_rematch = true;
+                    }
+                }
+                // A peer may only steer its own paddle
+                _ if !is_own_input(side, action) => {}
+                InputAction::LeftCatch | InputAction::RightCatch => {
+                    game::physics::press_catch(game_state, side);
+                }
+                InputAction::LeftPaddleUp | InputAction::RightPaddleUp => {
+                    game::physics::paddle_input(game_state, side, false);
+                }
+                _ => game::physics::paddle_input(game_state, side, true),
+            }
+        }
+    }
+
+    if *local_wants_rematch && *peer_wants_rematch {
+        game_state.reset_game();
+        *local_wants_rematch = false;
+        *peer_wants_rematch = false;
+    }
+}
+
+/// Client and spectator: take the host's ball state, snapping to it when far off
+/// and easing towards it otherwise
+pub(super) fn apply_ball_state(game_state: &mut GameState, ball_state: &BallState) {
+    let Some(ball) = game_state.ball_mut(ball_state.index as usize) else {
+        return; // Not a ball this match has
+    };
+    let error_x = ball_state.x - ball.x;
+    let error_y = ball_state.y - ball.y;
+    let error_magnitude = (error_x * error_x + error_y * error_y).sqrt();
+
+    if error_magnitude > POSITION_SNAP_THRESHOLD {
+        ball.x = ball_state.x;
+        ball.y = ball_state.y;
+    } else {
+        ball.x += error_x * POSITION_CORRECTION_ALPHA;
+        ball.y += error_y * POSITION_CORRECTION_ALPHA;
+    }
+
+    ball.vx = ball_state.vx;
+    ball.vy = ball_state.vy;
+}
+
+/// Client and spectator: take the host's score and match tally; true if a goal was scored
+pub(super) fn apply_score(
+    game_state: &mut GameState,
+    (left, right): (u8, u8),
+    game_over: bool,
+    (left_games, right_games): (u8, u8),
+) -> bool {
+    let goal_scored = u16::from(left) + u16::from(right)
+        > u16::from(game_state.left_score) + u16::from(game_state.right_score);
+    if game_over && !game_state.game_over {
+        game_state.next_game_in = NEXT_GAME_DELAY;
+    } else if !game_over && game_state.game_over {
+        game_state.elapsed = 0.0; // The host started the next game
+    }
+    game_state.left_score = left;
+    game_state.right_score = right;
+    game_state.game_over = game_over;
+    game_state.match_state.left_games = left_games;
+    game_state.match_state.right_games = right_games;
+
+    // Determine winner when game is over (level scores are a draw, not last game's winner)
+    if game_over {
+        game_state.winner = match left.cmp(&right) {
+            Ordering::Greater => Some(game::Player::Left),
+            Ordering::Less => Some(game::Player::Right),
+            Ordering::Equal => None,
+        };
+    }
+    goal_scored
+}
+
+/// Client and spectator: take the host's power-ups and paddle heights
+pub(super) fn apply_powerups(
+    game_state: &mut GameState,
+    powerups: Vec<PowerUp>,
+    left_paddle_height: f32,
+    right_paddle_height: f32,
+) {
+    game_state.powerups = powerups;
+    let field_height = game_state.field_height;
+    for (paddle, height) in [
+        (&mut game_state.left_paddle, left_paddle_height),
+        (&mut game_state.right_paddle, right_paddle_height),
+    ] {
+        if height.is_finite() && height > 0.0 {
+            paddle.resize(height, 0.0, field_height);
+        }
+    }
+}
+
+/// What to tell the player when the connection ends
+fn disconnect_overlay(reason: DisconnectReason) -> ui::OverlayMessage {
+    let (title, detail) = match reason {
+        DisconnectReason::PeerQuit => ("Opponent left", "Opponent closed the connection"),
+        DisconnectReason::Timeout => ("Connection lost", "Could not reconnect to opponent"),
+        DisconnectReason::IceFailure => ("Connection lost", "Network path to opponent failed"),
+        DisconnectReason::Unknown => ("Connection lost", "Opponent left the game"),
+    };
+    let lines = vec![
+        title.to_string(),
+        "".to_string(),
+        detail.to_string(),
+        "".to_string(),
+        "Press any key to return to menu".to_string(),
+    ];
+    match reason {
+        DisconnectReason::PeerQuit => ui::OverlayMessage::info(lines),
+        _ => ui::OverlayMessage::error(lines),
+    }
+}
+
+/// Show a final overlay over the frozen game after the session ends,
+/// until SESSION_END_DISPLAY elapses or the player presses a key
+pub(super) fn show_session_ended<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    display: &DisplayConfig,
+    game_state: &GameState,
+    rtt_ms: Option<u64>,
+    your_player: Option<game::Player>,
+    overlay: ui::OverlayMessage,
+) -> Result<(), P2PongError> {
+    let shown_at = Instant::now();
+
+    while shown_at.elapsed() < SESSION_END_DISPLAY {
+        terminal.draw(|f| {
+            ui::render(
+                f,
+                game_state,
+                rtt_ms,
+                Some(&overlay),
+                your_player,
+                ui::RenderOptions::from_display(display),
+            )
+        })?;
+
+        if event::poll(Duration::from_millis(100))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Wait for peer connection with TUI display
-/// Returns Some(peer_id) if connected, None if user cancelled
-fn wait_for_connection_tui<B: ratatui::backend::Backend>(
+/// Host passes its match setup, which is sent to the client once the channel opens;
+/// the client waits until it has received the host's setup.
+/// Returns Some((setup, host peer ID)) if connected, None if user cancelled
+/// (the host peer ID is our own when hosting, the one we connected to otherwise).
+/// A failed connection (a timeout, an error, an incompatible peer) is shown until the
+/// user dismisses it, then returned as a Network error.
+pub(super) fn wait_for_connection_tui<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     client: &network::NetworkClient,
     player_role: &PlayerRole,
     target_peer_id: Option<String>, // For client mode: the peer we're connecting to
-    timeout_secs: u64,
-) -> Result<Option<String>, io::Error> {
+    mut match_setup: Option<MatchSetup>, // For host mode: settings to send to the client
+    config: &Config,
+) -> Result<Option<(MatchSetup, String)>, P2PongError> {
+    let timeout_secs = config.network.connection_timeout_secs;
     let mut peer_connected = false;
     let mut data_channel_ready = false;
+    let mut channel_opened_at: Option<Instant> = None;
+    let mut last_setup_sent: Option<Instant> = None;
+    let mut last_hello_sent: Option<Instant> = None;
+    let mut peer_hello = false;
+    let mut failure: Option<String> = None;
+    let mut qr: Option<Vec<String>> = None; // Once there is a peer ID to encode
     let mut peer_id = String::from("waiting...");
+    let mut peer_id_ready = false;
+    let mut copied: Option<(&str, Instant)> = None; // What the host last copied, and when
+    let mut stage: Option<ConnectionStage> = None;
     let connection_start = Instant::now();
 
     debug::log(
@@ -478,13 +1379,13 @@ fn wait_for_connection_tui<B: ratatui::backend::Backend>(
 
     loop {
         // Check for timeout (configurable via config.network.connection_timeout_secs)
-        if connection_start.elapsed() > Duration::from_secs(timeout_secs) {
-            debug::log("CONN_TIMEOUT", "Connection timeout");
-            return Err(io::Error::new(
-                io::ErrorKind::TimedOut,
-                "Connection timeout - peer may not exist or be offline",
-            ));
+        let timeout = Duration::from_secs(timeout_secs);
+        let elapsed = connection_start.elapsed();
+        if elapsed > timeout && failure.is_none() {
+            debug::warn("CONN_TIMEOUT", "Connection timeout");
+            failure = Some("Timed out - peer may not exist or be offline".to_string());
         }
+        let seconds_left = timeout.saturating_sub(elapsed).as_secs_f32().ceil() as u64;
 
         // Check for user input (Q to cancel)
         if event::poll(Duration::from_millis(100))? {
@@ -495,6 +1396,19 @@ fn wait_for_connection_tui<B: ratatui::backend::Backend>(
                             debug::log("WAIT_CANCELLED", "User cancelled connection wait");
                             return Ok(None); // User cancelled
                         }
+                        // Host: C copies the peer ID, X a command that joins with it
+                        KeyCode::Char(c @ ('c' | 'C' | 'x' | 'X'))
+                            if matches!(player_role, PlayerRole::Host) && peer_id_ready =>
+                        {
+                            let (what, text) = match c {
+                                'c' | 'C' => ("peer ID", peer_id.clone()),
+                                _ => ("connect command", crate::connect_command(&peer_id)),
+                            };
+                            match clipboard::copy(&text) {
+                                Ok(()) => copied = Some((what, Instant::now())),
+                                Err(e) => debug::warn("CLIPBOARD", &format!("Copy failed: {}", e)),
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -504,8 +1418,18 @@ fn wait_for_connection_tui<B: ratatui::backend::Backend>(
         // Drain network events
         while let Some(event) = client.try_recv_event() {
             match event {
+                NetworkEvent::ConnectionStage(reached) => {
+                    debug::log("CONN_STAGE", &format!("Reached {:?}", reached));
+                    stage = stage.max(Some(reached));
+                }
                 NetworkEvent::LocalPeerIdReady { peer_id: id } => {
                     peer_id = id;
+                    peer_id_ready = true;
+                    qr = config
+                        .display
+                        .show_peer_qr
+                        .then(|| menu::peer_id_qr(&peer_id))
+                        .flatten();
                     debug::log(
                         "LOCAL_PEER_ID",
                         &format!("Local peer ID ready: {}", peer_id),
@@ -517,58 +1441,135 @@ fn wait_for_connection_tui<B: ratatui::backend::Backend>(
                 }
                 NetworkEvent::DataChannelOpened => {
                     data_channel_ready = true;
+                    channel_opened_at = Some(Instant::now());
                     debug::log("DC_OPENED", "Data channel opened");
                 }
+                NetworkEvent::PeerHello { protocol_version } => {
+                    debug::log(
+                        "PEER_HELLO",
+                        &format!("Peer protocol v{}", protocol_version),
+                    );
+                    // A repeat means the peer is still missing ours (answered at the
+                    // resend rate, so two waiting peers don't bounce it back and forth)
+                    if peer_hello
+                        && last_hello_sent.is_none_or(|sent| sent.elapsed() > HELLO_RESEND)
+                    {
+                        let _ = client.send_message(NetworkMessage::Hello {
+                            protocol_version: PROTOCOL_VERSION,
+                        });
+                        last_hello_sent = Some(Instant::now());
+                    }
+                    peer_hello = true;
+                }
+                NetworkEvent::ReceivedMatchSetup(setup) => {
+                    debug::log("MATCH_SETUP", &format!("Host match setup: {:?}", setup));
+                    if matches!(player_role, PlayerRole::Client) {
+                        match_setup = Some(setup);
+                    }
+                }
                 NetworkEvent::Error(msg) => {
-                    debug::log("NET_ERROR", &format!("Network error: {}", msg));
+                    debug::error("NET_ERROR", &format!("Network error: {}", msg));
+                    failure.get_or_insert(msg);
+                }
+                _ => {}
+            }
+        }
 
-                    // Show error overlay and wait for user acknowledgment
-                    loop {
-                        let error_overlay = ui::OverlayMessage::error(vec![
-                            "Connection Failed".to_string(),
-                            "".to_string(),
-                            msg.clone(),
-                            "".to_string(),
-                            "Press Q to return to menu".to_string(),
-                        ]);
-
-                        terminal.draw(|f| match player_role {
-                            PlayerRole::Host => {
-                                menu::render_waiting_for_connection(
-                                    f,
-                                    &peer_id,
-                                    Some(&error_overlay),
-                                );
-                            }
-                            PlayerRole::Client => {
-                                let target = target_peer_id.as_deref().unwrap_or("unknown");
-                                menu::render_connecting_to_peer(f, target, Some(&error_overlay));
-                            }
-                        })?;
-
-                        // Wait for user to press Q
-                        if event::poll(Duration::from_millis(100))? {
-                            if let Event::Key(key) = event::read()? {
-                                if key.kind == KeyEventKind::Press {
-                                    if matches!(
-                                        key.code,
-                                        KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc
-                                    ) {
-                                        return Ok(None); // Return to menu
-                                    }
-                                }
-                            }
+        // Host tells the client the match settings, resending them since the channel is
+        // lossy (the game loop goes on resending until the client's Ready arrives)
+        if let (PlayerRole::Host, Some(setup), true) =
+            (player_role, &match_setup, data_channel_ready)
+        {
+            if last_setup_sent.is_none_or(|sent| sent.elapsed() > SETUP_RESEND) {
+                let _ = client.send_message(NetworkMessage::MatchSetup(setup.clone()));
+                last_setup_sent = Some(Instant::now());
+            }
+        }
+
+        // The channel is lossy: resend our handshake until the peer's arrives (the network
+        // runtime sent the first one when the channel opened)
+        if data_channel_ready && !peer_hello {
+            let sent = *last_hello_sent.get_or_insert_with(Instant::now);
+            if sent.elapsed() > HELLO_RESEND {
+                let _ = client.send_message(NetworkMessage::Hello {
+                    protocol_version: PROTOCOL_VERSION,
+                });
+                last_hello_sent = Some(Instant::now());
+            }
+        }
+
+        // A peer that never sends a handshake predates versioning (or is broken)
+        if !peer_hello && channel_opened_at.is_some_and(|at| at.elapsed() > HELLO_TIMEOUT) {
+            failure.get_or_insert_with(|| {
+                "Incompatible version: opponent did not send a version handshake".to_string()
+            });
+        }
+
+        if let Some(msg) = failure.take() {
+            // Show error overlay and wait for user acknowledgment
+            loop {
+                let error_overlay = ui::OverlayMessage::error(vec![
+                    "Connection Failed".to_string(),
+                    "".to_string(),
+                    msg.clone(),
+                    "".to_string(),
+                    "Press Q to return to menu".to_string(),
+                ]);
+
+                terminal.draw(|f| match player_role {
+                    PlayerRole::Host => {
+                        menu::render_waiting_for_connection(
+                            f,
+                            &peer_id,
+                            qr.as_deref(),
+                            None,
+                            stage,
+                            seconds_left,
+                            Some(&error_overlay),
+                        );
+                    }
+                    PlayerRole::Client => {
+                        let target = target_peer_id.as_deref().unwrap_or("unknown");
+                        menu::render_connecting_to_peer(
+                            f,
+                            target,
+                            stage,
+                            seconds_left,
+                            Some(&error_overlay),
+                        );
+                    }
+                })?;
+
+                // Wait for user to press Q
+                if event::poll(Duration::from_millis(100))? {
+                    if let Event::Key(key) = event::read()? {
+                        if key.kind == KeyEventKind::Press
+                            && matches!(
+                                key.code,
+                                KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc
+                            )
+                        {
+                            return Err(P2PongError::Network(msg)); // Return to menu
                         }
                     }
                 }
-                _ => {}
             }
         }
 
-        // Check if connection is ready
-        if peer_connected && data_channel_ready {
-            debug::log("READY", "Connection ready - starting game");
-            return Ok(Some(peer_id));
+        // Check if connection is ready (versions match; client also needs the host's setup)
+        if peer_connected && data_channel_ready && peer_hello {
+            if let Some(setup) = match_setup.take() {
+                debug::log("READY", "Connection ready - starting game");
+
+                // Offer this peer again in the join dialog
+                if let Some(target) = &target_peer_id {
+                    if let Err(e) = recent_peers::record_connection(target) {
+                        debug::warn("RECENT_PEERS", &format!("Failed to save: {}", e));
+                    }
+                }
+                let host_peer_id = target_peer_id.unwrap_or(peer_id);
+                return Ok(Some((setup, host_peer_id)));
+            }
         }
 
         // Render waiting screen (different for host vs client)
@@ -576,14 +1577,160 @@ fn wait_for_connection_tui<B: ratatui::backend::Backend>(
             match player_role {
                 PlayerRole::Host => {
                     // Host: show "Share this Peer ID:" screen
-                    menu::render_waiting_for_connection(f, &peer_id, None);
+                    menu::render_waiting_for_connection(
+                        f,
+                        &peer_id,
+                        qr.as_deref(),
+                        copied
+                            .filter(|(_, at)| at.elapsed() < COPY_FEEDBACK)
+                            .map(|(what, _)| what),
+                        stage,
+                        seconds_left,
+                        None,
+                    );
                 }
                 PlayerRole::Client => {
                     // Client: show "Connecting to peer..." screen
                     let target = target_peer_id.as_deref().unwrap_or("unknown");
-                    menu::render_connecting_to_peer(f, target, None);
+                    menu::render_connecting_to_peer(f, target, stage, seconds_left, None);
                 }
             }
         })?;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PhysicsConfig;
+
+    #[test]
+    fn test_connection_info_lines() {
+        let lines = connection_info_lines(&PlayerRole::Client, "abc123", true, 42, None, "I");
+        assert!(lines.contains(&"Host peer ID: abc123".to_string()));
+        assert!(lines.contains(&"RTT: 42 ms".to_string()));
+        assert!(!lines.iter().any(|line| line.starts_with("Packet loss")));
+
+        let quality = ui::ConnectionQuality {
+            loss_pct: 2.5,
+            jitter_ms: 12.0,
+        };
+        let lines =
+            connection_info_lines(&PlayerRole::Host, "abc123", true, 42, Some(quality), "I");
+        assert!(lines.contains(&"Your peer ID: abc123".to_string()));
+        assert!(lines.contains(&"Spectators can watch by joining with this ID".to_string()));
+        assert!(lines.contains(&"Packet loss: 2.5%   Jitter: 12 ms".to_string()));
+        assert_eq!(lines.last().unwrap(), "Press I to close");
+    }
+
+    #[test]
+    fn test_peer_may_only_send_its_paddle_moves_and_rematch() {
+        let local_side = game::Player::Left;
+        assert!(is_peer_input(local_side, InputAction::RightPaddleUp));
+        assert!(is_peer_input(local_side, InputAction::RightCatch));
+        assert!(is_peer_input(local_side, InputAction::Rematch));
+
+        for action in [
+            InputAction::OpenChat,
+            InputAction::Quit,
+            InputAction::ToggleHelp,
+            InputAction::ToggleConnectionInfo,
+            InputAction::SaveHighlight,
+            InputAction::LeftPaddleUp, // Our paddle
+        ] {
+            assert!(!is_peer_input(local_side, action), "{:?}", action);
+        }
+    }
+
+    #[test]
+    fn test_only_repeated_inputs_are_dropped() {
+        let mut sync_state = NetworkSyncState::default();
+        assert!(sync_state.accept_input(0));
+        assert!(sync_state.accept_input(2));
+        assert!(sync_state.accept_input(1)); // Late, but still a move
+        assert!(!sync_state.accept_input(1)); // Repeat of a late one
+        assert!(!sync_state.accept_input(2)); // Repeat of the newest
+        assert!(sync_state.accept_input(3));
+        assert!(!sync_state.accept_input(0));
+
+        // Too old to tell
+        assert!(sync_state.accept_input(3 + INPUT_HISTORY));
+        assert!(!sync_state.accept_input(2));
+
+        // Sequence numbers roll over
+        sync_state.last_input_sequence = Some(u32::MAX);
+        sync_state.applied_inputs = 0;
+        assert!(sync_state.accept_input(0));
+        assert!(!sync_state.accept_input(u32::MAX));
+    }
+
+    #[test]
+    fn test_ball_syncs_are_ordered_per_ball() {
+        let sync = |index, sequence| BallState {
+            index,
+            x: 0.0,
+            y: 0.0,
+            vx: 0.0,
+            vy: 0.0,
+            sequence,
+            timestamp_ms: 0,
+        };
+        let mut sequences = BallSequences::default();
+        assert!(sequences.accept(&sync(0, 0)));
+        // Ball 1's sync overtaken by ball 0's from the same frame still applies
+        assert!(sequences.accept(&sync(0, 2)));
+        assert!(sequences.accept(&sync(1, 1)));
+        assert!(!sequences.accept(&sync(0, 2))); // Duplicate
+        assert!(!sequences.accept(&sync(1, 1)));
+        assert!(sequences.accept(&sync(1, 3)));
+    }
+
+    #[test]
+    fn test_score_sync_winner_and_draw() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        apply_score(&mut state, (5, 3), true, (1, 0));
+        assert_eq!(state.winner, Some(game::Player::Left));
+
+        // A game ending level is a draw, not a repeat of the last winner
+        apply_score(&mut state, (0, 0), false, (1, 0));
+        apply_score(&mut state, (4, 4), true, (1, 0));
+        assert_eq!(state.winner, None);
+    }
+
+    #[test]
+    fn test_remote_paddle_glides_to_tapped_position() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        let start = state.right_paddle.y;
+        let mut smoothing = RemotePaddleSmoothing::new(&state, game::Player::Right, 0.5).unwrap();
+
+        // Two taps land on the target, not the drawn paddle
+        let drawn_y = smoothing.begin_inputs(&mut state);
+        game::physics::paddle_input(&mut state, game::Player::Right, true);
+        game::physics::paddle_input(&mut state, game::Player::Right, true);
+        smoothing.end_inputs(&mut state, drawn_y);
+        let target = start + 2.0 * state.tap_distance;
+        assert_eq!(state.right_paddle.y, start);
+
+        // Half the distance in a reference frame, however many frames it's drawn in
+        let frame = 1.0 / PADDLE_SMOOTHING_FPS;
+        smoothing.step(&mut state, frame / 2.0);
+        smoothing.step(&mut state, frame / 2.0);
+        assert!((state.right_paddle.y - (start + state.tap_distance)).abs() < 0.01);
+        for _ in 0..10 {
+            smoothing.step(&mut state, frame);
+        }
+        assert_eq!(state.right_paddle.y, target);
+
+        // A reset elsewhere drops the target instead of gliding back to it
+        let drawn_y = smoothing.begin_inputs(&mut state);
+        game::physics::paddle_input(&mut state, game::Player::Right, false);
+        smoothing.end_inputs(&mut state, drawn_y);
+        state.reset_game();
+        let center = state.right_paddle.y;
+        smoothing.step(&mut state, frame);
+        assert_eq!(state.right_paddle.y, center);
+
+        // Off at 1.0 (jump straight there)
+        assert!(RemotePaddleSmoothing::new(&state, game::Player::Right, 1.0).is_none());
+    }
+}
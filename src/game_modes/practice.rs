@@ -0,0 +1,253 @@
+// Practice mode: the player's left paddle against the Backboard bot with no scoring.
+// Missed balls are simply served again, and the ball speed keys (+ / -) change its speed.
+
+use std::time::{Duration, Instant};
+
+use ratatui::Terminal;
+
+use crate::ai::{self, BotType};
+use crate::config::Config;
+use crate::debug;
+use crate::error::P2PongError;
+use crate::game::physics::PhysicsEvents;
+use crate::game::{self, poll_input_practice, GameState, InputAction};
+use crate::ui;
+
+use super::ai::apply_paddle_action;
+use super::common::{
+    help_overlay, limit_frame_rate, poll_any_key, score_keys_help, terminal_too_small, Bell,
+    FpsCounter, HelpLayout, MouseSteering, PhysicsClock,
+};
+
+/// Speed multiplier change per ball speed key press
+const SPEED_STEP: f32 = 1.25;
+
+/// Bounds for the speed multiplier (relative to the configured serve speed)
+const MIN_SPEED_MULTIPLIER: f32 = 0.25;
+const MAX_SPEED_MULTIPLIER: f32 = 4.0;
+
+/// How long the speed overlay stays up at the start and after each change
+const SPEED_OVERLAY_TIME: Duration = Duration::from_millis(1500);
+
+/// Run practice mode
+pub fn run_game_practice<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    config: &Config,
+) -> Result<(), P2PongError> {
+    debug::info("GAME_START", "Practice mode");
+
+    let frame_duration = Duration::from_millis(1000 / config.display.target_fps);
+
+    let size = terminal.size()?;
+    let mut game_state = GameState::new(size.width, size.height, &config.physics);
+    game_state.goal_slow_motion = config.display.goal_slow_motion;
+    game_state.practice = true;
+    game_state.time_limit = None; // Nothing to win, so nothing to time
+    let base_speed = game_state.ball_speed;
+    let mut speed_multiplier = 1.0;
+
+    let mut clock = PhysicsClock::new(config.physics.tick_rate_hz);
+    let mut fps_counter = FpsCounter::new(config.display.show_fps);
+    let mut bell = Bell::new(config.display.sound_enabled);
+    let mut show_help = false; // Help overlay open (game paused)
+    let mut speed_shown_until = Instant::now() + SPEED_OVERLAY_TIME;
+
+    let mut bot = ai::create_bot(BotType::Backboard, game::Player::Right, &config.ai);
+    let mut mouse_row = None;
+    let mut mouse_steering = MouseSteering::new();
+
+    loop {
+        let now = Instant::now();
+        let fps = fps_counter.frame();
+
+        // Handle player input (left paddle only); while help is open any key just closes it
+        let actions = if show_help {
+            show_help = !poll_any_key()?;
+            Vec::new()
+        } else {
+            let mut actions = poll_input_practice(config, &mut mouse_row)?;
+            let size = terminal.size()?;
+            let field = ui::FieldArea::new(
+                size.width,
+                size.height,
+                &game_state,
+                config.display.preserve_aspect,
+            );
+            actions.extend(mouse_steering.steer(
+                &game_state,
+                game::Player::Left,
+                mouse_row,
+                &field,
+            ));
+            actions
+        };
+
+        for action in &actions {
+            // While paused only system keys are handled
+            if game_state.paused
+                && !matches!(
+                    action,
+                    InputAction::Quit | InputAction::Pause | InputAction::ToggleHelp
+                )
+            {
+                continue;
+            }
+
+            match action {
+                InputAction::Quit => return Ok(()),
+                InputAction::LeftPaddleUp => {
+                    game::physics::paddle_input(&mut game_state, game::Player::Left, false);
+                }
+                InputAction::LeftPaddleDown => {
+                    game::physics::paddle_input(&mut game_state, game::Player::Left, true);
+                }
+                InputAction::LeftCatch => {
+                    game::physics::press_catch(&mut game_state, game::Player::Left);
+                }
+                InputAction::BallFaster | InputAction::BallSlower => {
+                    let step = if *action == InputAction::BallFaster {
+                        SPEED_STEP
+                    } else {
+                        1.0 / SPEED_STEP
+                    };
+                    change_speed(&mut game_state, base_speed, &mut speed_multiplier, step);
+                    speed_shown_until = now + SPEED_OVERLAY_TIME;
+                }
+                InputAction::ToggleHelp => show_help = true,
+                InputAction::Pause => game_state.toggle_pause(),
+                InputAction::LeftScoreUp => game_state.adjust_score(game::Player::Left, true),
+                InputAction::LeftScoreDown => game_state.adjust_score(game::Player::Left, false),
+                InputAction::RightScoreUp => game_state.adjust_score(game::Player::Right, true),
+                InputAction::RightScoreDown => game_state.adjust_score(game::Player::Right, false),
+                _ => {} // No rematch or chat in practice
+            }
+        }
+
+        // Physics ticks due this frame (the clock is drained while paused or the
+        // terminal is too small, so resuming doesn't replay that time)
+        clock.set_rate(game_state.time_scale());
+        let ticks = clock.ticks();
+
+        // The Backboard bot returns every ball, once per rendered frame; it's given the
+        // real time since the last frame, and waits out the slow motion after a goal
+        let held = show_help || terminal_too_small(terminal)?;
+        let bot_action = if held || game_state.paused || game_state.pending_serve.is_some() {
+            None
+        } else {
+            bot.get_action(&game_state, clock.frame_secs())
+        };
+        if let Some(bot_action) = bot_action {
+            apply_paddle_action(&mut game_state, bot_action);
+        }
+
+        // Update physics at the fixed tick rate
+        if !held {
+            let mut frame_events = PhysicsEvents::default();
+            for _ in 0..ticks {
+                let events = game::update_with_events(&mut game_state, clock.timestep());
+                frame_events.merge(events);
+                if events.goal_scored {
+                    bot.reset();
+                }
+            }
+            bell.play(&frame_events)?;
+        }
+
+        let overlay = if show_help {
+            Some(help_overlay(config, HelpLayout::Practice, true))
+        } else if now < speed_shown_until {
+            Some(
+                ui::OverlayMessage::info(vec![
+                    "PRACTICE".to_string(),
+                    "".to_string(),
+                    format!("Ball speed x{:.2}", speed_multiplier),
+                    format!(
+                        "{} / {} to change",
+                        config.keybindings.ball_faster, config.keybindings.ball_slower
+                    ),
+                    "".to_string(),
+                    score_keys_help(&config.keybindings),
+                ])
+                .with_display_config(&config.display),
+            )
+        } else {
+            None
+        };
+
+        terminal.draw(|f| {
+            ui::render(
+                f,
+                &game_state,
+                None,
+                overlay.as_ref(),
+                Some(game::Player::Left),
+                ui::RenderOptions {
+                    fps,
+                    assist: config.display.assist,
+                    ..ui::RenderOptions::from_display(&config.display)
+                },
+            )
+        })?;
+
+        // Frame rate limiting
+        limit_frame_rate(now, frame_duration);
+    }
+}
+
+/// Step the speed multiplier by `step` (within bounds), rescaling the ball in flight
+/// and the serves that follow
+fn change_speed(state: &mut GameState, base_speed: f32, multiplier: &mut f32, step: f32) {
+    let new_multiplier = (*multiplier * step).clamp(MIN_SPEED_MULTIPLIER, MAX_SPEED_MULTIPLIER);
+    let factor = new_multiplier / *multiplier;
+    *multiplier = new_multiplier;
+
+    state.ball_speed = (base_speed * new_multiplier).min(state.max_ball_speed);
+
+    let max_speed = state.max_ball_speed;
+    for ball in state.balls_mut() {
+        let speed = (ball.vx * ball.vx + ball.vy * ball.vy).sqrt();
+        if speed > 0.0 {
+            let scale = (speed * factor).min(max_speed) / speed;
+            ball.vx *= scale;
+            ball.vy *= scale;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PhysicsConfig;
+
+    #[test]
+    fn test_change_speed_scales_ball_within_bounds() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        let base_speed = state.ball_speed;
+        let mut multiplier = 1.0;
+        let vx = state.ball.vx;
+
+        change_speed(&mut state, base_speed, &mut multiplier, SPEED_STEP);
+        assert_eq!(multiplier, SPEED_STEP);
+        assert!((state.ball.vx - vx * SPEED_STEP).abs() < 0.01);
+        assert!((state.ball_speed - base_speed * SPEED_STEP).abs() < 0.01);
+
+        for _ in 0..20 {
+            change_speed(&mut state, base_speed, &mut multiplier, 1.0 / SPEED_STEP);
+        }
+        assert_eq!(multiplier, MIN_SPEED_MULTIPLIER);
+        assert!((state.ball_speed - base_speed * MIN_SPEED_MULTIPLIER).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_missed_ball_is_served_again_without_scoring() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        state.practice = true;
+        state.ball.x = 0.0;
+        state.ball.vx = -100.0;
+
+        let events = game::update_with_events(&mut state, 1.0 / 60.0);
+        assert!(events.goal_scored);
+        assert_eq!((state.left_score, state.right_score), (0, 0));
+        assert_eq!(state.ball.x, state.field_width / 2.0);
+    }
+}
@@ -0,0 +1,234 @@
+// Replay playback: feeds a recording's inputs back through the physics tick by tick.
+// Highlights are played frame by frame instead, at the rate they were captured.
+// Space pauses, Right (or '.') steps one tick (or frame) while paused, Esc or Q exits.
+
+use std::io;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::Terminal;
+
+use crate::config::Config;
+use crate::debug;
+use crate::error::P2PongError;
+use crate::game::{self, GameState};
+use crate::replay::{self, Highlight, Replay};
+use crate::ui;
+
+use super::common::{limit_frame_rate, FpsCounter, PhysicsClock};
+
+enum ReplayKey {
+    Quit,
+    Pause,
+    Step,
+}
+
+fn poll_replay_keys() -> Result<Vec<ReplayKey>, io::Error> {
+    let mut keys = Vec::new();
+    while event::poll(Duration::from_millis(0))? {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => keys.push(ReplayKey::Quit),
+            KeyCode::Char(' ') => keys.push(ReplayKey::Pause),
+            KeyCode::Right | KeyCode::Char('.') => keys.push(ReplayKey::Step),
+            _ => {}
+        }
+    }
+    Ok(keys)
+}
+
+/// Play back a recorded game
+pub fn run_replay<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    config: &Config,
+    replay: &Replay,
+) -> Result<(), P2PongError> {
+    debug::log(
+        "GAME_START",
+        &format!(
+            "Replay: {} inputs over {} ticks",
+            replay.inputs.len(),
+            replay.ticks
+        ),
+    );
+
+    let frame_duration = Duration::from_millis(1000 / config.display.target_fps);
+
+    let size = terminal.size()?;
+    let mut game_state = GameState::new(size.width, size.height, &replay.physics);
+    game_state.set_paddle_scales(replay.paddle_scales);
+    game_state.goal_slow_motion = replay.goal_slow_motion;
+    let mut clock = PhysicsClock::new(replay.physics.tick_rate_hz);
+    let mut fps_counter = FpsCounter::new(config.display.show_fps);
+
+    let mut tick = 0; // Physics ticks played so far
+    let mut next_input = 0; // Index of the first input not yet applied
+    let mut paused = false;
+
+    loop {
+        let now = Instant::now();
+        let fps = fps_counter.frame();
+
+        let mut steps = 0;
+        for key in poll_replay_keys()? {
+            match key {
+                ReplayKey::Quit => return Ok(()),
+                ReplayKey::Pause => paused = !paused,
+                ReplayKey::Step if paused => steps += 1,
+                ReplayKey::Step => {}
+            }
+        }
+
+        // Play at the recorded tick rate (slowed after goals as it was when recorded), or
+        // only the requested steps while paused
+        clock.set_rate(game_state.time_scale());
+        let ticks = clock.ticks();
+        let to_play = if paused { steps } else { ticks };
+        for _ in 0..to_play {
+            if tick >= replay.ticks {
+                break;
+            }
+            let end = replay.inputs_before(tick, next_input);
+            for &(_, action) in &replay.inputs[next_input..end] {
+                replay::apply_input(&mut game_state, action);
+            }
+            next_input = end;
+            game::update_with_events(&mut game_state, clock.timestep());
+            tick += 1;
+        }
+
+        let overlay = if tick >= replay.ticks {
+            Some(ui::OverlayMessage::info(vec![
+                "REPLAY OVER".to_string(),
+                "".to_string(),
+                "Esc to exit".to_string(),
+            ]))
+        } else if paused {
+            Some(ui::OverlayMessage::info(vec![
+                "REPLAY PAUSED".to_string(),
+                "".to_string(),
+                "Space: resume   \u{2192}: step".to_string(),
+                "Esc: exit".to_string(),
+            ]))
+        } else {
+            None
+        };
+
+        terminal.draw(|f| {
+            ui::render(
+                f,
+                &game_state,
+                None,
+                overlay
+                    .map(|o| o.with_display_config(&config.display))
+                    .as_ref(),
+                None,
+                ui::RenderOptions {
+                    fps,
+                    ..ui::RenderOptions::from_display(&config.display)
+                },
+            )
+        })?;
+
+        limit_frame_rate(now, frame_duration);
+    }
+}
+
+/// Play back a saved highlight
+pub fn run_highlight<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    config: &Config,
+    highlight: &Highlight,
+) -> Result<(), P2PongError> {
+    debug::log(
+        "GAME_START",
+        &format!(
+            "Highlight: {} vs {}, {} frames",
+            highlight.left_name,
+            highlight.right_name,
+            highlight.frames.len()
+        ),
+    );
+
+    let frame_duration = Duration::from_millis(1000 / highlight.fps.max(1));
+
+    let size = terminal.size()?;
+    let mut game_state = GameState::new(size.width, size.height, &highlight.physics);
+    let mut fps_counter = FpsCounter::new(config.display.show_fps);
+
+    let mut shown = 0; // Frames shown so far
+    let mut paused = false;
+
+    loop {
+        let now = Instant::now();
+        let fps = fps_counter.frame();
+
+        let mut steps = 0;
+        for key in poll_replay_keys()? {
+            match key {
+                ReplayKey::Quit => return Ok(()),
+                ReplayKey::Pause => paused = !paused,
+                ReplayKey::Step if paused => steps += 1,
+                ReplayKey::Step => {}
+            }
+        }
+
+        let to_show = if paused { steps } else { 1 };
+        for _ in 0..to_show {
+            if let Some(frame) = highlight.frames.get(shown) {
+                frame.show(&mut game_state);
+                shown += 1;
+            }
+        }
+
+        let overlay = if shown >= highlight.frames.len() {
+            let (left, right) = highlight.final_score;
+            let mut lines = vec![
+                "DECISIVE POINT".to_string(),
+                "".to_string(),
+                format!(
+                    "{} {} - {} {}",
+                    highlight.left_name, left, right, highlight.right_name
+                ),
+            ];
+            if highlight.truncated {
+                lines.push("(the start of this long point was not kept)".to_string());
+            }
+            lines.push("".to_string());
+            lines.push("Esc to exit".to_string());
+            Some(ui::OverlayMessage::info(lines))
+        } else if paused {
+            Some(ui::OverlayMessage::info(vec![
+                "HIGHLIGHT PAUSED".to_string(),
+                "".to_string(),
+                "Space: resume   \u{2192}: step".to_string(),
+                "Esc: exit".to_string(),
+            ]))
+        } else {
+            None
+        };
+
+        terminal.draw(|f| {
+            ui::render(
+                f,
+                &game_state,
+                None,
+                overlay
+                    .map(|o| o.with_display_config(&config.display))
+                    .as_ref(),
+                None,
+                ui::RenderOptions {
+                    fps,
+                    ..ui::RenderOptions::from_display(&config.display)
+                },
+            )
+        })?;
+
+        limit_frame_rate(now, frame_duration);
+    }
+}
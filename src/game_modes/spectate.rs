@@ -0,0 +1,366 @@
+// Watching a networked match: a spectator connects to the host much like a client but
+// only listens, drawing the host's paddles, balls, score and power-ups as they arrive.
+// The host's side of that stream (SpectatorFeed) lives here too.
+
+use std::io;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::Terminal;
+
+use crate::config::Config;
+use crate::debug;
+use crate::error::P2PongError;
+use crate::game::state::{MatchStats, PowerUp};
+use crate::game::{self, GameState};
+use crate::network::client::NetworkEvent;
+use crate::network::{
+    self, BallState, ConnectionMode, DisconnectReason, MatchSetup, NetworkClient, NetworkMessage,
+};
+use crate::ui;
+
+use super::common::{
+    game_over_lines, limit_frame_rate, next_game_overlay, FpsCounter, PhysicsClock,
+};
+use super::network::{
+    apply_ball_state, apply_powerups, apply_score, show_session_ended, wait_for_connection_tui,
+    BallSequences, PlayerRole,
+};
+
+/// Frames between repeats of the score and power-ups, which otherwise only go out
+/// when they change (a spectator joining mid-match would wait for the next goal)
+const FEED_REFRESH_FRAMES: u64 = 30;
+
+/// Host: the game as spectators see it, sent every frame. Paddles and balls always go
+/// (spectators don't simulate); score and power-ups when they change, and now and then.
+#[derive(Default)]
+pub(super) struct SpectatorFeed {
+    frame: u64,
+    ball_sequence: u64,
+    score: Option<(u8, u8, bool, u8, u8, Option<MatchStats>)>,
+    powerups: Option<(Vec<PowerUp>, f32, f32)>,
+}
+
+impl SpectatorFeed {
+    pub(super) fn send(&mut self, client: &NetworkClient, game_state: &GameState) {
+        let refresh = self.frame.is_multiple_of(FEED_REFRESH_FRAMES);
+        self.frame += 1;
+
+        let _ = client.send_spectators(NetworkMessage::PaddleSync {
+            left_y: game_state.left_paddle.y,
+            right_y: game_state.right_paddle.y,
+        });
+
+        for (index, ball) in game_state.balls().enumerate() {
+            self.ball_sequence += 1;
+            let _ = client.send_spectators(NetworkMessage::BallSync(BallState {
+                index: index as u8,
+                x: ball.x,
+                y: ball.y,
+                vx: ball.vx,
+                vy: ball.vy,
+                sequence: self.ball_sequence,
+                timestamp_ms: 0,
+            }));
+        }
+
+        let score = (
+            game_state.left_score,
+            game_state.right_score,
+            game_state.game_over,
+            game_state.match_state.left_games,
+            game_state.match_state.right_games,
+            game_state.game_over.then_some(game_state.match_stats),
+        );
+        if refresh || self.score != Some(score) {
+            let (left, right, game_over, left_games, right_games, stats) = score;
+            let _ = client.send_spectators(NetworkMessage::ScoreSync {
+                left,
+                right,
+                game_over,
+                left_games,
+                right_games,
+                stats,
+            });
+            self.score = Some(score);
+        }
+
+        let powerups = (
+            game_state.powerups.clone(),
+            game_state.left_paddle.height,
+            game_state.right_paddle.height,
+        );
+        if refresh || self.powerups.as_ref() != Some(&powerups) {
+            let _ = client.send_spectators(NetworkMessage::PowerUpEvent {
+                powerups: powerups.0.clone(),
+                left_paddle_height: powerups.1,
+                right_paddle_height: powerups.2,
+            });
+            self.powerups = Some(powerups);
+        }
+    }
+}
+
+/// Drain pending terminal events; returns true if Q or Esc was pressed
+fn poll_quit() -> Result<bool, io::Error> {
+    let mut quit = false;
+    while event::poll(Duration::from_millis(0))? {
+        if let Event::Key(key) = event::read()? {
+            quit |= key.kind == KeyEventKind::Press
+                && matches!(
+                    key.code,
+                    KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q')
+                );
+        }
+    }
+    Ok(quit)
+}
+
+/// Watch the match hosted by `peer_id`
+pub fn run_game_spectate<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    config: &Config,
+    peer_id: &str,
+) -> Result<(), P2PongError> {
+    debug::info("GAME_START", &format!("Spectating peer: {}", peer_id));
+
+    let network_client = network::start_network(
+        ConnectionMode::Spectate {
+            peer_id: peer_id.to_string(),
+        },
+        config.network.signaling_server.clone(),
+        config.network.require_tls,
+        &config.network.ice_servers,
+        config.network.ice_disconnect_timeout_secs,
+    )?;
+
+    // The host sends its match settings as the channel opens, as it does to a client
+    match wait_for_connection_tui(
+        terminal,
+        &network_client,
+        &PlayerRole::Client,
+        Some(peer_id.to_string()),
+        None,
+        config,
+    )? {
+        Some((setup, _)) => watch(terminal, network_client, &setup, config),
+        None => Ok(()),
+    }
+}
+
+fn watch<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    network_client: NetworkClient,
+    setup: &MatchSetup,
+    config: &Config,
+) -> Result<(), P2PongError> {
+    let frame_duration = Duration::from_millis(1000 / config.display.target_fps);
+
+    let size = terminal.size()?;
+    let mut game_state = GameState::new(size.width, size.height, &setup.physics());
+    let mut clock = PhysicsClock::new(setup.tick_rate_hz);
+    let mut fps_counter = FpsCounter::new(config.display.show_fps);
+    let mut ball_sequences = BallSequences::default();
+
+    loop {
+        let now = Instant::now();
+        let fps = fps_counter.frame();
+
+        if poll_quit()? {
+            let _ = network_client.disconnect();
+            return Ok(());
+        }
+
+        while let Some(event) = network_client.try_recv_event() {
+            match event {
+                NetworkEvent::ReceivedBallState(ball_state)
+                    if ball_sequences.accept(&ball_state) =>
+                {
+                    apply_ball_state(&mut game_state, &ball_state);
+                }
+                NetworkEvent::ReceivedScore {
+                    left,
+                    right,
+                    game_over,
+                    left_games,
+                    right_games,
+                    stats,
+                } => {
+                    apply_score(
+                        &mut game_state,
+                        (left, right),
+                        game_over,
+                        (left_games, right_games),
+                    );
+                    if let Some(stats) = stats {
+                        game_state.match_stats = stats;
+                    }
+                }
+                NetworkEvent::ReceivedPowerUps {
+                    powerups,
+                    left_paddle_height,
+                    right_paddle_height,
+                } => {
+                    apply_powerups(
+                        &mut game_state,
+                        powerups,
+                        left_paddle_height,
+                        right_paddle_height,
+                    );
+                }
+                NetworkEvent::ReceivedPaddles { left_y, right_y }
+                    if left_y.is_finite() && right_y.is_finite() =>
+                {
+                    game_state.left_paddle.y = left_y;
+                    game_state.right_paddle.y = right_y;
+                }
+                // Nothing to wait out for a spectator: any drop ends the show
+                NetworkEvent::Disconnected(_) | NetworkEvent::Reconnecting => {
+                    let detail = match event {
+                        NetworkEvent::Disconnected(DisconnectReason::PeerQuit) => {
+                            "The host ended the match"
+                        }
+                        _ => "Lost the connection to the host",
+                    };
+                    debug::log("SPECTATE_END", detail);
+                    return show_session_ended(
+                        terminal,
+                        &config.display,
+                        &game_state,
+                        None,
+                        None,
+                        ui::OverlayMessage::info(vec![
+                            "Stopped watching".to_string(),
+                            "".to_string(),
+                            detail.to_string(),
+                            "".to_string(),
+                            "Press any key to return to menu".to_string(),
+                        ])
+                        .with_display_config(&config.display),
+                    );
+                }
+                NetworkEvent::Error(msg) => {
+                    debug::error("SPECTATE_END", &format!("Network error: {}", msg));
+                    return show_session_ended(
+                        terminal,
+                        &config.display,
+                        &game_state,
+                        None,
+                        None,
+                        ui::OverlayMessage::error(vec![
+                            "Stopped watching".to_string(),
+                            "".to_string(),
+                            msg,
+                            "".to_string(),
+                            "Press any key to return to menu".to_string(),
+                        ])
+                        .with_display_config(&config.display),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        // Between updates the balls carry on as last seen (the host's clocks, too)
+        for _ in 0..clock.ticks() {
+            let dt = clock.timestep();
+            if game_state.game_over {
+                game_state.next_game_in -= dt;
+            } else {
+                game_state.elapsed += dt;
+            }
+            game_state.push_ball_trail();
+            for ball in game_state.balls_mut() {
+                ball.x += ball.vx * dt;
+                ball.y += ball.vy * dt;
+            }
+        }
+
+        let winner_name = match game_state.winner {
+            Some(game::Player::Left) => "left",
+            Some(game::Player::Right) => "right",
+            None => "nobody",
+        };
+        let overlay = if game_state.game_over && !game_state.match_over() {
+            Some(next_game_overlay(&game_state, winner_name))
+        } else if game_state.game_over {
+            let title = match game_state.winner {
+                Some(_) => format!("{} WINS", winner_name.to_uppercase()),
+                None => "DRAW".to_string(),
+            };
+            Some(ui::OverlayMessage::info(game_over_lines(
+                &game_state,
+                title,
+                "Q to stop watching".to_string(),
+            )))
+        } else {
+            None
+        };
+
+        terminal.draw(|f| {
+            ui::render(
+                f,
+                &game_state,
+                None,
+                overlay
+                    .map(|o| o.with_display_config(&config.display))
+                    .as_ref(),
+                None,
+                ui::RenderOptions {
+                    fps,
+                    ..ui::RenderOptions::from_display(&config.display)
+                },
+            )
+        })?;
+
+        limit_frame_rate(now, frame_duration);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PhysicsConfig;
+    use crate::network::client::NetworkCommand;
+    use std::sync::mpsc;
+
+    /// Names of the messages the feed sent to spectators
+    fn sent(rx: &mpsc::Receiver<NetworkCommand>) -> Vec<&'static str> {
+        rx.try_iter()
+            .map(|cmd| match cmd {
+                NetworkCommand::SendSpectators(NetworkMessage::PaddleSync { .. }) => "paddles",
+                NetworkCommand::SendSpectators(NetworkMessage::BallSync(_)) => "ball",
+                NetworkCommand::SendSpectators(NetworkMessage::ScoreSync { .. }) => "score",
+                NetworkCommand::SendSpectators(NetworkMessage::PowerUpEvent { .. }) => "powerups",
+                other => panic!("Unexpected command: {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_spectator_feed_sends_changes_and_refreshes() {
+        let (tx, rx) = mpsc::channel();
+        let (_event_tx, event_rx) = mpsc::channel();
+        let client = NetworkClient::new(tx, event_rx);
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        let mut feed = SpectatorFeed::default();
+
+        // Everything on the first frame, then only what moves
+        feed.send(&client, &state);
+        assert_eq!(sent(&rx), ["paddles", "ball", "score", "powerups"]);
+        feed.send(&client, &state);
+        assert_eq!(sent(&rx), ["paddles", "ball"]);
+
+        state.left_score += 1;
+        feed.send(&client, &state);
+        assert_eq!(sent(&rx), ["paddles", "ball", "score"]);
+
+        // Repeated now and then for spectators who joined since
+        for _ in 3..FEED_REFRESH_FRAMES {
+            feed.send(&client, &state);
+        }
+        let _ = sent(&rx);
+        feed.send(&client, &state);
+        assert_eq!(sent(&rx), ["paddles", "ball", "score", "powerups"]);
+    }
+}
@@ -0,0 +1,52 @@
+// Small JSON files kept next to config.toml (stats, leaderboard, recent peers)
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::debug;
+
+/// Read `path`, starting from the default if it's missing or unreadable
+pub fn load<T: DeserializeOwned + Default>(path: &Path) -> T {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return T::default();
+    };
+
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        debug::log(
+            "JSON_STORE",
+            &format!("Failed to parse {}: {}", path.display(), e),
+        );
+        T::default()
+    })
+}
+
+/// Write `value` to `path` as pretty-printed JSON
+pub fn save<T: Serialize>(value: &T, path: &Path) -> Result<(), io::Error> {
+    let json = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    fs::write(path, json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_round_trip_and_fallbacks() {
+        let path =
+            std::env::temp_dir().join(format!("p2pong-store-test-{}.json", std::process::id()));
+        assert_eq!(load::<BTreeMap<String, u32>>(&path), BTreeMap::new());
+
+        let value = BTreeMap::from([("wins".to_string(), 3), ("losses".to_string(), 1)]);
+        save(&value, &path).unwrap();
+        assert_eq!(load::<BTreeMap<String, u32>>(&path), value);
+
+        fs::write(&path, "not json").unwrap();
+        assert_eq!(load::<BTreeMap<String, u32>>(&path), BTreeMap::new());
+        fs::remove_file(&path).unwrap();
+    }
+}
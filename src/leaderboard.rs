@@ -0,0 +1,121 @@
+// Persistent leaderboard for local hotseat players
+// Stored as JSON next to config.toml, keyed by player name
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::loader::get_config_path;
+use crate::json_store;
+
+/// Win/loss record for a single named player
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct PlayerRecord {
+    pub wins: u32,
+    pub losses: u32,
+}
+
+impl PlayerRecord {
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses
+    }
+
+    /// Fraction of games won (0.0 when no games played)
+    pub fn win_rate(&self) -> f32 {
+        if self.games() == 0 {
+            0.0
+        } else {
+            self.wins as f32 / self.games() as f32
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Leaderboard {
+    pub players: BTreeMap<String, PlayerRecord>,
+
+    // Names used in the last local game, offered again next time
+    pub last_left_name: String,
+    pub last_right_name: String,
+}
+
+impl Leaderboard {
+    /// Record a finished game between two named players
+    pub fn record_result(&mut self, winner: &str, loser: &str) {
+        self.players.entry(winner.to_string()).or_default().wins += 1;
+        self.players.entry(loser.to_string()).or_default().losses += 1;
+    }
+
+    /// Players sorted by win rate, then by wins, then by name
+    pub fn ranked(&self) -> Vec<(&String, &PlayerRecord)> {
+        let mut ranked: Vec<_> = self.players.iter().collect();
+        ranked.sort_by(|(name_a, a), (name_b, b)| {
+            b.win_rate()
+                .total_cmp(&a.win_rate())
+                .then(b.wins.cmp(&a.wins))
+                .then(name_a.cmp(name_b))
+        });
+        ranked
+    }
+}
+
+/// Get the path to the leaderboard file (alongside config.toml)
+pub fn get_leaderboard_path() -> PathBuf {
+    get_config_path().with_file_name("leaderboard.json")
+}
+
+/// Load the leaderboard, falling back to an empty one if missing or unreadable
+pub fn load() -> Leaderboard {
+    json_store::load(&get_leaderboard_path())
+}
+
+/// Write the leaderboard back to disk
+pub fn save(leaderboard: &Leaderboard) -> Result<(), io::Error> {
+    json_store::save(leaderboard, &get_leaderboard_path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_rank() {
+        let mut board = Leaderboard::default();
+        board.record_result("alice", "bob");
+        board.record_result("alice", "bob");
+        board.record_result("bob", "carol");
+
+        let ranked: Vec<&str> = board
+            .ranked()
+            .into_iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(ranked, vec!["alice", "bob", "carol"]);
+
+        let bob = &board.players["bob"];
+        assert_eq!((bob.wins, bob.losses), (1, 2));
+    }
+
+    #[test]
+    fn test_round_trip_and_missing_file() {
+        let path = std::env::temp_dir().join(format!(
+            "p2pong-leaderboard-test-{}.json",
+            std::process::id()
+        ));
+
+        let mut board = Leaderboard::default();
+        board.record_result("alice", "bob");
+        board.last_left_name = "alice".to_string();
+        json_store::save(&board, &path).unwrap();
+
+        let loaded: Leaderboard = json_store::load(&path);
+        assert_eq!(loaded.players, board.players);
+        assert_eq!(loaded.last_left_name, "alice");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(json_store::load::<Leaderboard>(&path).players.is_empty());
+    }
+}
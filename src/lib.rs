@@ -0,0 +1,68 @@
+//! P2Pong: terminal pong, played locally, against bots or peer to peer over WebRTC.
+//!
+//! The simulation in [`game`] needs no terminal or network: build a [`GameState`] from
+//! a [`PhysicsConfig`], apply paddle input and step it with [`update_with_events`].
+//! Given the same config (including `serve_seed`), inputs and timesteps it always
+//! plays out the same, which lockstep networking, replays and the `bench` binary rely on.
+//!
+//! ```
+//! use p2pong::game::physics::paddle_input;
+//! use p2pong::{update_with_events, GameState, PhysicsConfig, Player};
+//!
+//! let physics = PhysicsConfig {
+//!     serve_seed: Some(7), // Tilted serves
+//!     ..PhysicsConfig::default()
+//! };
+//! let mut state = GameState::new(0, 0, &physics);
+//! state.right_paddle.y = 0.0; // Parked at the top: right loses
+//! let mut goals = 0;
+//! for _frame in 0..60 * 600 {
+//!     if state.game_over {
+//!         break;
+//!     }
+//!     // Left chases the ball
+//!     let (ball_y, paddle) = (state.ball.y, &state.left_paddle);
+//!     let center = paddle.y + paddle.height / 2.0;
+//!     if (ball_y - center).abs() > state.tap_distance {
+//!         paddle_input(&mut state, Player::Left, ball_y > center);
+//!     }
+//!     if update_with_events(&mut state, 1.0 / 60.0).goal_scored {
+//!         goals += 1;
+//!     }
+//! }
+//! assert_eq!(state.winner, Some(Player::Left));
+//! assert!(goals >= state.winning_score as usize);
+//! ```
+//!
+//! Everything else (menus, game modes, networking, rendering) makes up the `p2pong`
+//! binary, and is public so it can be driven from there.
+
+pub mod ai;
+pub mod bench;
+pub mod clipboard;
+pub mod config;
+pub mod debug;
+pub mod error;
+pub mod game;
+pub mod game_modes;
+pub mod json_store;
+pub mod leaderboard;
+pub mod menu;
+pub mod network;
+pub mod recent_peers;
+pub mod replay;
+pub mod stats;
+pub mod ui;
+
+pub use config::PhysicsConfig;
+pub use game::physics::PhysicsEvents;
+pub use game::{update_with_events, GameState, InputAction, Player};
+
+// Dead reckoning configuration for client-side prediction
+pub const POSITION_SNAP_THRESHOLD: f32 = 50.0; // Snap if error > 50 virtual units (collision happened)
+pub const POSITION_CORRECTION_ALPHA: f32 = 0.3; // Gentle correction factor for small prediction errors
+
+/// Command line that joins the game hosted as `peer_id`, for sharing
+pub fn connect_command(peer_id: &str) -> String {
+    format!("p2pong --connect {}", peer_id)
+}
@@ -1,97 +1,260 @@
-mod ai;
-mod config;
-mod debug;
-mod game;
-mod game_modes;
-mod menu;
-mod network;
-mod ui;
-
 // Standard library imports
 use std::io;
+use std::path::Path;
+use std::sync::Mutex;
 
 // External crate imports
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
+    cursor::Show,
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 
-// Internal crate imports
-use config::Config;
-use menu::{handle_menu_input, render_menu, AppState, GameMode, MenuAction, MenuState};
-
-// Fixed at 1/60s for deterministic physics — do not derive from target_fps.
-// Both peers in a networked game must use the same timestep or simulations diverge.
-pub const FIXED_TIMESTEP: f32 = 1.0 / 60.0;
+// Library imports (the game itself lives in the p2pong library; this is the entry point)
+use p2pong::config::{self, Config};
+use p2pong::error::P2PongError;
+use p2pong::game::Player;
+use p2pong::menu::{handle_menu_input, render_menu, AppState, GameMode, MenuAction, MenuState};
+use p2pong::{debug, game_modes, network, replay};
 
-// Dead reckoning configuration for client-side prediction
-pub const POSITION_SNAP_THRESHOLD: f32 = 50.0; // Snap if error > 50 virtual units (collision happened)
-pub const POSITION_CORRECTION_ALPHA: f32 = 0.3; // Gentle correction factor for small prediction errors
+fn main() -> Result<(), P2PongError> {
+    // Load configuration
+    let mut config = config::load_config()?;
 
-fn main() -> Result<(), io::Error> {
     // Check for --debug flag to enable diagnostic logging
     let debug_enabled = std::env::args().any(|arg| arg == "--debug" || arg == "-d");
 
-    // Initialize debug logging system (opt-in via --debug flag)
-    debug::init(debug_enabled)?;
-    debug::log("SESSION_START", "P2Pong debug logging initialized");
+    // Initialize debug logging system (opt-in via --debug or [logging] enabled)
+    let logging_enabled = debug_enabled || config.logging.enabled;
+    debug::init(&config.logging, logging_enabled)?;
+    if logging_enabled {
+        eprintln!("Debug log: {}", debug::log_path(&config.logging).display());
+    }
+    debug::info("SESSION_START", "P2Pong debug logging initialized");
 
-    // Load configuration
-    let config = config::load_config()?;
+    // --replay <file> plays a recording (or a saved highlight) instead of opening the menu
+    let replay =
+        match replay_arg(std::env::args()) {
+            Some(path) => Some(replay::load(Path::new(&path)).map_err(|e| {
+                io::Error::new(e.kind(), format!("Can't load replay {}: {}", path, e))
+            })?),
+            None => None,
+        };
+
+    // --ghost <file> races a recorded solo game instead of opening the menu
+    let ghost = match ghost_arg(std::env::args()) {
+        Some(path) => Some(load_ghost(&path)?),
+        None => None,
+    };
+
+    // A panic must not leave the terminal in raw mode on the alternate screen
+    install_panic_hook();
 
     // Setup terminal BEFORE entering app loop
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
+    // Restore the terminal however the app ends, so an error is readable afterwards
+    let result = run_app(&mut terminal, &mut config, replay.as_ref(), ghost.as_ref());
+    restore_terminal()?;
+    report_thread_panics();
+    result
+}
+
+/// Put the terminal back as it was before the game started
+fn restore_terminal() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        Show
+    )
+}
+
+/// Panic messages from threads other than main, held until the terminal is restored
+static THREAD_PANICS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Restore the terminal before a main-thread panic prints its message. Panics on other
+/// threads (the network thread catches its own) would garble the screen, so they are
+/// logged and printed once the app has restored the terminal.
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let thread = std::thread::current();
+        if thread.name() == Some("main") {
+            let _ = restore_terminal();
+            default_hook(info);
+            print_debug_log_path();
+        } else {
+            debug::error("PANIC", &info.to_string());
+            let msg = format!("thread '{}' {}", thread.name().unwrap_or("<unnamed>"), info);
+            if let Ok(mut panics) = THREAD_PANICS.lock() {
+                panics.push(msg);
+            }
+        }
+    }));
+}
+
+/// Print the panics held back while the game was on screen
+fn report_thread_panics() {
+    let panics = match THREAD_PANICS.lock() {
+        Ok(panics) => panics.clone(),
+        Err(_) => return,
+    };
+    if panics.is_empty() {
+        return;
+    }
+    for msg in &panics {
+        eprintln!("{}", msg);
+    }
+    print_debug_log_path();
+}
+
+/// Point to the debug log after a panic (only when logging is on)
+fn print_debug_log_path() {
+    if let Some(path) = debug::active_path() {
+        eprintln!("Debug log: {}", path.display());
+    }
+}
+
+/// The menu and game loop, from the first screen until the player quits
+fn run_app<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    config: &mut Config,
+    replay: Option<&replay::Recording>,
+    ghost: Option<&(replay::Replay, Player)>,
+) -> Result<(), P2PongError> {
     // AppState loop: Menu -> Game -> Menu
-    let mut app_state = AppState::Menu;
+    // (--connect <peer_id> joins that game straight away, then carries on in the menu)
+    if let Some((replay, side)) = ghost {
+        return game_modes::run_ghost(terminal, config, replay, *side);
+    }
+    let mut network_error = None; // Shown in the menu (a bad --connect, or a game ended by one)
+    let mut app_state = match replay {
+        Some(replay::Recording::Game(replay)) => {
+            game_modes::run_replay(terminal, config, replay)?;
+            AppState::Exiting
+        }
+        Some(replay::Recording::Highlight(highlight)) => {
+            game_modes::run_highlight(terminal, config, highlight)?;
+            AppState::Exiting
+        }
+        None => match connect_arg(std::env::args()).map(|arg| network::parse_peer_id(&arg)) {
+            Some(Ok(peer_id)) => AppState::Game(GameMode::NetworkClient(peer_id)),
+            Some(Err(e)) => {
+                let msg = format!("Ignoring --connect: {}", e);
+                debug::log("CONNECT_ARG", &msg);
+                network_error = Some(msg);
+                AppState::Menu
+            }
+            None => AppState::Menu,
+        },
+    };
 
     loop {
         match app_state {
             AppState::Menu => {
-                app_state = run_menu(&mut terminal)?;
+                app_state = run_menu(terminal, config, network_error.take())?;
             }
             AppState::Game(mode) => {
-                run_game_mode(&mut terminal, mode, &config)?;
+                match run_game_mode(terminal, mode, config) {
+                    // A failed connection ends that game, not the app
+                    Err(e @ P2PongError::Network(_)) => {
+                        debug::error("GAME_NETWORK", &e.to_string());
+                        network_error = Some(e.to_string());
+                    }
+                    result => result?,
+                }
                 app_state = AppState::Menu;
             }
             AppState::Exiting => break,
         }
     }
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
-
     Ok(())
 }
 
+/// File given with `--replay <file>`, if any
+fn replay_arg(args: impl Iterator<Item = String>) -> Option<String> {
+    let mut args = args.skip_while(|arg| arg != "--replay");
+    args.next()?;
+    args.next()
+}
+
+/// File given with `--ghost <file>`, if any
+fn ghost_arg(args: impl Iterator<Item = String>) -> Option<String> {
+    let mut args = args.skip_while(|arg| arg != "--ghost");
+    args.next()?;
+    args.next()
+}
+
+/// Load a recording to race, with the side its player was on
+fn load_ghost(path: &str) -> io::Result<(replay::Replay, Player)> {
+    let error = |kind, msg: String| io::Error::new(kind, format!("Can't race {}: {}", path, msg));
+    match replay::load(Path::new(path)).map_err(|e| error(e.kind(), e.to_string()))? {
+        replay::Recording::Game(replay) => match replay.player_side {
+            Some(side) => Ok((replay, side)),
+            None => Err(error(
+                io::ErrorKind::InvalidInput,
+                "not a solo (vs-AI) recording".to_string(),
+            )),
+        },
+        replay::Recording::Highlight(_) => Err(error(
+            io::ErrorKind::InvalidInput,
+            "highlights can't be raced".to_string(),
+        )),
+    }
+}
+
+/// Peer ID given with `--connect <peer_id>`, if any
+fn connect_arg(args: impl Iterator<Item = String>) -> Option<String> {
+    let mut args = args.skip_while(|arg| arg != "--connect");
+    args.next()?;
+    args.next()
+}
+
 /// Run the main menu and return next app state
+/// Settings changed in the menu are saved and apply from the next game on; `error` is
+/// shown below the menu
 fn run_menu<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
+    config: &mut Config,
+    error: Option<String>,
 ) -> Result<AppState, io::Error> {
-    let mut menu_state = MenuState::new();
+    let mut menu_state = MenuState {
+        error,
+        ..MenuState::with_config(config.clone())
+    };
 
     loop {
         // Render menu
         terminal.draw(|f| render_menu(f, &menu_state))?;
 
-        // Handle input
+        // Handle input (blocks until a key, resize or the idle redraw timeout)
         match handle_menu_input(&mut menu_state)? {
             MenuAction::None => {} // Continue in menu
             MenuAction::StartGame(mode) => {
                 return Ok(AppState::Game(mode));
             }
+            MenuAction::SaveSettings(new_config) => {
+                menu_state.error = config::loader::save_config(&new_config).err().map(|e| {
+                    debug::warn("CONFIG_SAVE", &format!("Failed to save settings: {}", e));
+                    format!("Settings not saved: {}", e)
+                });
+                *config = *new_config;
+            }
             MenuAction::Quit => {
                 return Ok(AppState::Exiting);
             }
@@ -104,13 +267,23 @@ fn run_game_mode<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     mode: GameMode,
     config: &Config,
-) -> Result<(), io::Error> {
+) -> Result<(), P2PongError> {
     match mode {
-        GameMode::LocalTwoPlayer => game_modes::run_game_local(terminal, config),
+        GameMode::LocalTwoPlayer {
+            left_name,
+            right_name,
+        } => game_modes::run_game_local(terminal, config, &left_name, &right_name),
         GameMode::NetworkHost => game_modes::run_game_network_host(terminal, config),
         GameMode::NetworkClient(peer_id) => {
             game_modes::run_game_network_client(terminal, config, &peer_id)
         }
-        GameMode::SinglePlayerAI(bot_type) => game_modes::run_game_vs_ai(terminal, config, bot_type),
+        GameMode::Spectate(peer_id) => game_modes::run_game_spectate(terminal, config, &peer_id),
+        GameMode::SinglePlayerAI(bot_type, side) => {
+            game_modes::run_game_vs_ai(terminal, config, bot_type, side)
+        }
+        GameMode::AiVsAi(left, right) => {
+            game_modes::run_game_ai_vs_ai(terminal, config, left, right)
+        }
+        GameMode::Practice => game_modes::run_game_practice(terminal, config),
     }
 }
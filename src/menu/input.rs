@@ -4,7 +4,8 @@ use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use std::io;
 use std::time::Duration;
 
-use super::state::{GameMode, MenuItem, MenuState};
+use super::state::{BotSelectionPurpose, GameMode, MenuItem, MenuState};
+use crate::config::Config;
 
 /// Menu action result
 pub enum MenuAction {
@@ -12,17 +13,28 @@ pub enum MenuAction {
     None,
     /// Start a game mode
     StartGame(GameMode),
+    /// Settings were changed: apply and save this config
+    SaveSettings(Box<Config>),
     /// Exit application
     Quit,
 }
 
-/// Handle menu input and return the next action
+/// Longest the menu waits for input before redrawing anyway. Nothing in the menu
+/// animates, so an idle menu only needs to wake up now and then.
+const MENU_IDLE_REDRAW: Duration = Duration::from_secs(1);
+
+/// Handle menu input and return the next action.
+/// Blocks until an event arrives (or for at most MENU_IDLE_REDRAW), so the caller
+/// redraws once per event rather than on a busy timer.
 pub fn handle_menu_input(menu_state: &mut MenuState) -> Result<MenuAction, io::Error> {
-    if event::poll(Duration::from_millis(100))? {
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
+    if event::poll(MENU_IDLE_REDRAW)? {
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
                 return Ok(handle_key_press(menu_state, key.code));
             }
+            // Bracketed paste: the whole text at once, so a trailing newline can't submit it
+            Event::Paste(text) if menu_state.in_input_mode => menu_state.paste_peer_id(&text),
+            _ => {}
         }
     }
 
@@ -35,6 +47,24 @@ fn handle_key_press(menu_state: &mut MenuState, key_code: KeyCode) -> MenuAction
         return handle_bot_selection_input(menu_state, key_code);
     }
 
+    if menu_state.in_leaderboard_mode {
+        if matches!(
+            key_code,
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('Q')
+        ) {
+            menu_state.close_leaderboard_view();
+        }
+        return MenuAction::None;
+    }
+
+    if menu_state.in_settings_mode {
+        return handle_settings_input(menu_state, key_code);
+    }
+
+    if menu_state.in_name_entry_mode {
+        return handle_name_entry_input(menu_state, key_code);
+    }
+
     // If in peer ID input mode, handle input differently
     if menu_state.in_input_mode {
         return handle_peer_id_input(menu_state, key_code);
@@ -58,32 +88,116 @@ fn handle_key_press(menu_state: &mut MenuState, key_code: KeyCode) -> MenuAction
 
 fn handle_menu_selection(menu_state: &mut MenuState) -> MenuAction {
     match menu_state.selected_item() {
-        MenuItem::LocalTwoPlayer => MenuAction::StartGame(GameMode::LocalTwoPlayer),
+        MenuItem::LocalTwoPlayer => {
+            // Ask for player names before starting
+            menu_state.start_name_entry();
+            MenuAction::None
+        }
         MenuItem::HostP2P => MenuAction::StartGame(GameMode::NetworkHost),
         MenuItem::JoinP2P => {
             // Enter peer ID input mode
-            menu_state.start_peer_id_input();
+            menu_state.start_peer_id_input(false);
+            MenuAction::None
+        }
+        MenuItem::Spectate => {
+            menu_state.start_peer_id_input(true);
             MenuAction::None
         }
         MenuItem::SinglePlayerAI => {
             // Enter bot selection mode
-            menu_state.start_bot_selection();
+            menu_state.start_bot_selection(BotSelectionPurpose::Opponent);
+            MenuAction::None
+        }
+        MenuItem::Practice => MenuAction::StartGame(GameMode::Practice),
+        MenuItem::AiVsAi => {
+            // Pick the left bot first, then the right
+            menu_state.start_bot_selection(BotSelectionPurpose::DemoLeft);
+            MenuAction::None
+        }
+        MenuItem::Leaderboard => {
+            menu_state.start_leaderboard_view();
+            MenuAction::None
+        }
+        MenuItem::Settings => {
+            menu_state.start_settings();
             MenuAction::None
         }
         MenuItem::Quit => MenuAction::Quit,
     }
 }
 
-fn handle_peer_id_input(menu_state: &mut MenuState, key_code: KeyCode) -> MenuAction {
+fn handle_settings_input(menu_state: &mut MenuState, key_code: KeyCode) -> MenuAction {
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => {
+            menu_state.select_previous_setting();
+            MenuAction::None
+        }
+        KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => {
+            menu_state.select_next_setting();
+            MenuAction::None
+        }
+        KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('H') => {
+            menu_state.adjust_setting(false);
+            MenuAction::None
+        }
+        KeyCode::Right
+        | KeyCode::Char('l')
+        | KeyCode::Char('L')
+        | KeyCode::Enter
+        | KeyCode::Char(' ') => {
+            menu_state.adjust_setting(true);
+            MenuAction::None
+        }
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => {
+            match menu_state.close_settings() {
+                Some(config) => MenuAction::SaveSettings(Box::new(config)),
+                None => MenuAction::None,
+            }
+        }
+        _ => MenuAction::None,
+    }
+}
+
+fn handle_name_entry_input(menu_state: &mut MenuState, key_code: KeyCode) -> MenuAction {
     match key_code {
         KeyCode::Enter => {
-            let peer_id = menu_state.submit_peer_id();
-            if !peer_id.is_empty() {
-                MenuAction::StartGame(GameMode::NetworkClient(peer_id))
-            } else {
-                MenuAction::None
+            let (left_name, right_name) = menu_state.submit_names();
+            MenuAction::StartGame(GameMode::LocalTwoPlayer {
+                left_name,
+                right_name,
+            })
+        }
+        KeyCode::Esc => {
+            menu_state.cancel_name_entry();
+            MenuAction::None
+        }
+        KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+            menu_state.toggle_name_field();
+            MenuAction::None
+        }
+        KeyCode::Backspace => {
+            menu_state.backspace_name();
+            MenuAction::None
+        }
+        KeyCode::Char(c) => {
+            if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' {
+                menu_state.add_char_to_name(c);
             }
+            MenuAction::None
         }
+        _ => MenuAction::None,
+    }
+}
+
+fn handle_peer_id_input(menu_state: &mut MenuState, key_code: KeyCode) -> MenuAction {
+    match key_code {
+        KeyCode::Enter => match menu_state.submit_peer_id() {
+            None => MenuAction::None,
+            Some(peer_id) if menu_state.spectating => {
+                MenuAction::StartGame(GameMode::Spectate(peer_id))
+            }
+            Some(peer_id) => MenuAction::StartGame(GameMode::NetworkClient(peer_id)),
+        },
         KeyCode::Esc => {
             menu_state.cancel_peer_id_input();
             MenuAction::None
@@ -92,6 +206,14 @@ fn handle_peer_id_input(menu_state: &mut MenuState, key_code: KeyCode) -> MenuAc
             menu_state.backspace_peer_id();
             MenuAction::None
         }
+        KeyCode::Down => {
+            menu_state.cycle_recent_peer(true);
+            MenuAction::None
+        }
+        KeyCode::Up => {
+            menu_state.cycle_recent_peer(false);
+            MenuAction::None
+        }
         KeyCode::Char(c) => {
             // Add character to peer ID (alphanumeric and hyphens only)
             if c.is_alphanumeric() || c == '-' {
@@ -113,9 +235,27 @@ fn handle_bot_selection_input(menu_state: &mut MenuState, key_code: KeyCode) ->
             menu_state.select_next_bot();
             MenuAction::None
         }
+        KeyCode::Left | KeyCode::Right | KeyCode::Char('h' | 'H' | 'l' | 'L')
+            if menu_state.bot_selection_purpose == BotSelectionPurpose::Opponent =>
+        {
+            menu_state.toggle_player_side();
+            MenuAction::None
+        }
         KeyCode::Enter | KeyCode::Char(' ') => {
             let bot_type = menu_state.submit_bot_selection();
-            MenuAction::StartGame(GameMode::SinglePlayerAI(bot_type))
+            match menu_state.bot_selection_purpose {
+                BotSelectionPurpose::Opponent => MenuAction::StartGame(GameMode::SinglePlayerAI(
+                    bot_type,
+                    menu_state.player_side,
+                )),
+                BotSelectionPurpose::DemoLeft => {
+                    menu_state.start_bot_selection(BotSelectionPurpose::DemoRight(bot_type));
+                    MenuAction::None
+                }
+                BotSelectionPurpose::DemoRight(left) => {
+                    MenuAction::StartGame(GameMode::AiVsAi(left, bot_type))
+                }
+            }
         }
         KeyCode::Esc => {
             menu_state.cancel_bot_selection();
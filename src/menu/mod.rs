@@ -6,5 +6,7 @@ pub mod render;
 pub mod state;
 
 pub use input::{handle_menu_input, MenuAction};
-pub use render::{render_connecting_to_peer, render_menu, render_waiting_for_connection};
+pub use render::{
+    peer_id_qr, render_connecting_to_peer, render_menu, render_waiting_for_connection,
+};
 pub use state::{AppState, GameMode, MenuState};
@@ -4,11 +4,19 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 
-use super::state::MenuState;
+use qrcode::QrCode;
+
+use super::state::{BotSelectionPurpose, MenuState, Setting};
+use crate::network::ConnectionStage;
+use crate::recent_peers;
+use crate::ui::braille::BrailleCanvas;
+
+/// Light modules around the QR code (scanners need a clear border)
+const QR_QUIET_ZONE: usize = 4;
 
 /// Render the main menu
 pub fn render_menu(frame: &mut Frame, menu_state: &MenuState) {
@@ -101,8 +109,8 @@ pub fn render_menu(frame: &mut Frame, menu_state: &MenuState) {
     let menu = Paragraph::new(menu_items).alignment(Alignment::Center);
     frame.render_widget(menu, chunks[1]);
 
-    // Draw controls hint
-    let controls = vec![Line::from(vec![
+    // Draw controls hint, and any error to report
+    let mut controls = vec![Line::from(vec![
         Span::styled("↑/↓", Style::default().fg(Color::Gray)),
         Span::styled(": Navigate  ", Style::default().fg(Color::DarkGray)),
         Span::styled("Enter", Style::default().fg(Color::Gray)),
@@ -110,25 +118,47 @@ pub fn render_menu(frame: &mut Frame, menu_state: &MenuState) {
         Span::styled("Q/Esc", Style::default().fg(Color::Gray)),
         Span::styled(": Quit", Style::default().fg(Color::DarkGray)),
     ])];
+    if let Some(error) = &menu_state.error {
+        controls.push(Line::from(""));
+        controls.push(Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        )));
+    }
 
-    let controls_widget = Paragraph::new(controls).alignment(Alignment::Center);
+    let controls_widget = Paragraph::new(controls)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
     frame.render_widget(controls_widget, chunks[2]);
 
     // Show appropriate dialog overlay
-    if menu_state.in_bot_selection_mode {
+    if menu_state.in_leaderboard_mode {
+        render_leaderboard_dialog(frame, menu_state);
+    } else if menu_state.in_settings_mode {
+        render_settings_dialog(frame, menu_state);
+    } else if menu_state.in_name_entry_mode {
+        render_name_entry_dialog(frame, menu_state);
+    } else if menu_state.in_bot_selection_mode {
         render_bot_selection_dialog(frame, menu_state);
     } else if menu_state.in_input_mode {
-        render_peer_id_dialog(frame, &menu_state.peer_id_input);
+        render_peer_id_dialog(frame, menu_state);
     }
 }
 
 /// Render peer ID input dialog overlay
-fn render_peer_id_dialog(frame: &mut Frame, peer_id: &str) {
+fn render_peer_id_dialog(frame: &mut Frame, menu_state: &MenuState) {
     let area = frame.area();
+    let peer_id = menu_state.peer_id_input.as_str();
+    let recent = &menu_state.recent_peers.peers;
 
-    // Create centered dialog box
+    // Create centered dialog box, with room for the recent peers list
     let dialog_width = 60.min(area.width - 4);
-    let dialog_height = 7;
+    let recent_height = if recent.is_empty() {
+        0
+    } else {
+        recent.len() as u16 + 2
+    };
+    let dialog_height = (7 + recent_height).min(area.height);
     let dialog_area = Rect {
         x: (area.width - dialog_width) / 2,
         y: (area.height - dialog_height) / 2,
@@ -141,7 +171,11 @@ fn render_peer_id_dialog(frame: &mut Frame, peer_id: &str) {
 
     // Draw dialog border
     let block = Block::default()
-        .title(" Enter Peer ID ")
+        .title(if menu_state.spectating {
+            " Enter Peer ID to Watch "
+        } else {
+            " Enter Peer ID "
+        })
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Yellow))
         .style(Style::default().bg(Color::Rgb(20, 20, 20)));
@@ -155,10 +189,38 @@ fn render_peer_id_dialog(frame: &mut Frame, peer_id: &str) {
         .constraints([
             Constraint::Length(1),
             Constraint::Length(1),
+            Constraint::Length(recent_height),
             Constraint::Min(1),
         ])
         .split(inner);
 
+    // Recent peers: Up/Down fills the input with one
+    if !recent.is_empty() {
+        let now = recent_peers::unix_now();
+        let mut recent_lines = vec![Line::from(Span::styled(
+            "Recent (↑/↓):",
+            Style::default().fg(Color::Gray),
+        ))];
+        recent_lines.extend(recent.iter().enumerate().map(|(i, peer)| {
+            let is_selected = menu_state.selected_recent_peer == Some(i);
+            let prefix = if is_selected { "> " } else { "  " };
+            let text = format!(
+                "{}{:<8}{:<20}{}",
+                prefix,
+                peer.peer_id,
+                peer.nickname.as_deref().unwrap_or(""),
+                peer.age_label(now)
+            );
+            let color = if is_selected {
+                Color::Yellow
+            } else {
+                Color::White
+            };
+            Line::from(Span::styled(text, Style::default().fg(color)))
+        }));
+        frame.render_widget(Paragraph::new(recent_lines), dialog_chunks[2]);
+    }
+
     // Draw current input
     let input_text = if peer_id.is_empty() {
         Span::styled("(type peer ID)", Style::default().fg(Color::DarkGray))
@@ -169,6 +231,16 @@ fn render_peer_id_dialog(frame: &mut Frame, peer_id: &str) {
     let input_widget = Paragraph::new(Line::from(input_text));
     frame.render_widget(input_widget, dialog_chunks[0]);
 
+    if let Some(error) = &menu_state.peer_id_error {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                error.as_str(),
+                Style::default().fg(Color::Red),
+            ))),
+            dialog_chunks[1],
+        );
+    }
+
     // Draw hint
     let hint = Line::from(vec![
         Span::styled("Enter", Style::default().fg(Color::Gray)),
@@ -178,7 +250,157 @@ fn render_peer_id_dialog(frame: &mut Frame, peer_id: &str) {
     ]);
 
     let hint_widget = Paragraph::new(hint).alignment(Alignment::Center);
-    frame.render_widget(hint_widget, dialog_chunks[2]);
+    frame.render_widget(hint_widget, dialog_chunks[3]);
+}
+
+/// Render local player name entry dialog overlay
+fn render_name_entry_dialog(frame: &mut Frame, menu_state: &MenuState) {
+    let area = frame.area();
+
+    // Create centered dialog box (similar to peer ID dialog)
+    let dialog_width = 50.min(area.width - 4);
+    let dialog_height = 8;
+    let dialog_area = Rect {
+        x: (area.width - dialog_width) / 2,
+        y: (area.height - dialog_height) / 2,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    // Clear the area behind the dialog
+    frame.render_widget(Clear, dialog_area);
+
+    // Draw dialog border
+    let block = Block::default()
+        .title(" Player Names ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Rgb(20, 20, 20)));
+
+    frame.render_widget(block, dialog_area);
+
+    let inner = dialog_area.inner(ratatui::layout::Margin::new(2, 1));
+    let dialog_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ])
+        .split(inner);
+
+    // Draw both name fields, highlighting the one being edited
+    for (i, label) in ["Left: ", "Right:"].iter().enumerate() {
+        let is_active = i == menu_state.name_field;
+        let name = &menu_state.player_names[i];
+        let value = if name.is_empty() {
+            Span::styled("(unranked)", Style::default().fg(Color::DarkGray))
+        } else {
+            Span::styled(name.as_str(), Style::default().fg(Color::White))
+        };
+        let prefix = if is_active { "> " } else { "  " };
+        let label_style = if is_active {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+
+        let line = Line::from(vec![
+            Span::styled(format!("{}{} ", prefix, label), label_style),
+            value,
+        ]);
+        frame.render_widget(Paragraph::new(line), dialog_chunks[i]);
+    }
+
+    // Draw hint
+    let hint = Line::from(vec![
+        Span::styled("Tab", Style::default().fg(Color::Gray)),
+        Span::styled(": Switch  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Enter", Style::default().fg(Color::Gray)),
+        Span::styled(": Play  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Esc", Style::default().fg(Color::Gray)),
+        Span::styled(": Cancel", Style::default().fg(Color::DarkGray)),
+    ]);
+
+    let hint_widget = Paragraph::new(hint).alignment(Alignment::Center);
+    frame.render_widget(hint_widget, dialog_chunks[3]);
+}
+
+/// Render leaderboard dialog overlay (sorted by win rate)
+fn render_leaderboard_dialog(frame: &mut Frame, menu_state: &MenuState) {
+    let area = frame.area();
+
+    let ranked = menu_state.leaderboard.ranked();
+    let row_count = ranked.len().max(1);
+
+    let dialog_width = 50.min(area.width - 4);
+    let dialog_height = ((row_count + 5) as u16).min(area.height - 2);
+    let dialog_area = Rect {
+        x: (area.width - dialog_width) / 2,
+        y: (area.height - dialog_height) / 2,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    // Clear the area behind the dialog
+    frame.render_widget(Clear, dialog_area);
+
+    // Draw dialog border
+    let block = Block::default()
+        .title(" Leaderboard ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Rgb(20, 20, 20)));
+
+    frame.render_widget(block, dialog_area);
+
+    let inner = dialog_area.inner(ratatui::layout::Margin::new(2, 1));
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(
+                "{:<3} {:<16} {:>4} {:>4} {:>5}",
+                "#", "Name", "W", "L", "Win%"
+            ),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    if ranked.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No local games recorded yet",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    for (i, (name, record)) in ranked.iter().enumerate() {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{:<3} {:<16} {:>4} {:>4} {:>4.0}%",
+                i + 1,
+                name,
+                record.wins,
+                record.losses,
+                record.win_rate() * 100.0
+            ),
+            Style::default().fg(Color::White),
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("Esc", Style::default().fg(Color::Gray)),
+        Span::styled(": Back", Style::default().fg(Color::DarkGray)),
+    ]));
+
+    let widget = Paragraph::new(lines).alignment(Alignment::Center);
+    frame.render_widget(widget, inner);
 }
 
 /// Render bot selection dialog overlay
@@ -188,7 +410,10 @@ fn render_bot_selection_dialog(frame: &mut Frame, menu_state: &MenuState) {
     // Create centered dialog box (similar to peer ID dialog)
     let dialog_width = 50.min(area.width - 4);
     let bot_count = menu_state.available_bots.len();
-    let dialog_height = (bot_count + 4).min(20) as u16;
+    // Picking an opponent also picks the player's side, on a line below the bots
+    let picking_side = menu_state.bot_selection_purpose == BotSelectionPurpose::Opponent;
+    let side_rows = if picking_side { 2 } else { 0 };
+    let dialog_height = (bot_count + side_rows + 4).min(20) as u16;
 
     let dialog_area = Rect {
         x: (area.width - dialog_width) / 2,
@@ -200,9 +425,15 @@ fn render_bot_selection_dialog(frame: &mut Frame, menu_state: &MenuState) {
     // Clear the area behind the dialog
     frame.render_widget(Clear, dialog_area);
 
+    let title = match menu_state.bot_selection_purpose {
+        BotSelectionPurpose::Opponent => " Select Bot Opponent ",
+        BotSelectionPurpose::DemoLeft => " Select Left Bot ",
+        BotSelectionPurpose::DemoRight(_) => " Select Right Bot ",
+    };
+
     // Draw dialog border
     let block = Block::default()
-        .title(" Select Bot Opponent ")
+        .title(title)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Yellow))
         .style(Style::default().bg(Color::Rgb(20, 20, 20)));
@@ -213,12 +444,14 @@ fn render_bot_selection_dialog(frame: &mut Frame, menu_state: &MenuState) {
     let inner = dialog_area.inner(ratatui::layout::Margin::new(2, 1));
 
     let bot_count = menu_state.available_bots.len() as u16;
+    let side_rows = side_rows as u16;
     let dialog_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length((inner.height.saturating_sub(bot_count)) / 2), // Top spacing
-            Constraint::Length(bot_count),                                    // Bot list
-            Constraint::Min(0),                                               // Bottom spacing
+            Constraint::Length((inner.height.saturating_sub(bot_count + side_rows)) / 2), // Top spacing
+            Constraint::Length(bot_count), // Bot list
+            Constraint::Length(side_rows), // Side choice
+            Constraint::Min(0),            // Bottom spacing
         ])
         .split(inner);
 
@@ -229,7 +462,20 @@ fn render_bot_selection_dialog(frame: &mut Frame, menu_state: &MenuState) {
         .map(|(i, bot_type)| {
             let is_selected = i == menu_state.selected_bot_index;
             let prefix = if is_selected { "> " } else { "  " };
-            let text = format!("{}{}", prefix, bot_type.display_name());
+            let text = match menu_state.bot_selection_purpose {
+                // Show the player's lifetime record when picking an opponent
+                BotSelectionPurpose::Opponent => {
+                    let (wins, losses) = menu_state.stats.record(*bot_type);
+                    format!(
+                        "{}{:<12}W {} / L {}",
+                        prefix,
+                        bot_type.display_name(),
+                        wins,
+                        losses
+                    )
+                }
+                _ => format!("{}{}", prefix, bot_type.display_name()),
+            };
 
             if is_selected {
                 Line::from(Span::styled(
@@ -246,12 +492,98 @@ fn render_bot_selection_dialog(frame: &mut Frame, menu_state: &MenuState) {
 
     let bot_list = Paragraph::new(bot_items);
     frame.render_widget(bot_list, dialog_chunks[1]);
+
+    if picking_side {
+        let side = match menu_state.player_side {
+            crate::game::Player::Left => "Left",
+            crate::game::Player::Right => "Right",
+        };
+        let side_line = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("  {:<12}< {} >", "Your side", side),
+                Style::default().fg(Color::Gray),
+            )),
+        ]);
+        frame.render_widget(side_line, dialog_chunks[2]);
+    }
+}
+
+/// Render the settings dialog overlay
+fn render_settings_dialog(frame: &mut Frame, menu_state: &MenuState) {
+    let area = frame.area();
+
+    let settings = Setting::all();
+    let dialog_width = 50.min(area.width - 4);
+    let dialog_height = (settings.len() + 6).min(20) as u16;
+
+    let dialog_area = Rect {
+        x: (area.width - dialog_width) / 2,
+        y: (area.height - dialog_height) / 2,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    // Clear the area behind the dialog
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(" Settings ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Rgb(20, 20, 20)));
+
+    frame.render_widget(block, dialog_area);
+
+    let inner = dialog_area.inner(ratatui::layout::Margin::new(2, 1));
+
+    let mut lines: Vec<Line> = settings
+        .iter()
+        .enumerate()
+        .map(|(i, setting)| {
+            let is_selected = i == menu_state.selected_setting_index;
+            let prefix = if is_selected { "> " } else { "  " };
+            let text = format!(
+                "{}{:<16}< {} >",
+                prefix,
+                setting.label(),
+                menu_state.setting_value(*setting)
+            );
+
+            if is_selected {
+                Line::from(Span::styled(
+                    text,
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Line::from(Span::styled(text, Style::default().fg(Color::White)))
+            }
+        })
+        .collect();
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("←/→", Style::default().fg(Color::Gray)),
+        Span::styled(": Change  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Esc", Style::default().fg(Color::Gray)),
+        Span::styled(": Save & back", Style::default().fg(Color::DarkGray)),
+    ]));
+
+    let settings_list = Paragraph::new(lines);
+    frame.render_widget(
+        settings_list,
+        inner.inner(ratatui::layout::Margin::new(0, 1)),
+    );
 }
 
 /// Render connecting to peer screen (for client mode)
 pub fn render_connecting_to_peer(
     frame: &mut Frame,
     target_peer_id: &str,
+    stage: Option<ConnectionStage>,
+    seconds_left: u64,
     overlay: Option<&crate::ui::OverlayMessage>,
 ) {
     let area = frame.area();
@@ -333,6 +665,7 @@ pub fn render_connecting_to_peer(
         };
 
         frame.render_widget(peer_id_widget, peer_id_area);
+        render_connection_progress(frame, stage, seconds_left, peer_id_area);
     }
 
     // Render overlay if provided (on clean background if error)
@@ -341,10 +674,124 @@ pub fn render_connecting_to_peer(
     }
 }
 
+/// Checklist of connection setup stages below the peer ID box (`reached` and all
+/// earlier stages are ticked; the next one is in progress), then the time left
+fn render_connection_progress(
+    frame: &mut Frame,
+    reached: Option<ConnectionStage>,
+    seconds_left: u64,
+    above: Rect,
+) {
+    let area = frame.area();
+    let y = above.y + above.height + 1;
+    let height = ConnectionStage::ALL.len() as u16 + 2;
+    if y + height > area.height {
+        return;
+    }
+
+    let mut lines: Vec<Line> = connection_stage_marks(reached)
+        .into_iter()
+        .map(|(stage, mark)| {
+            let (symbol, color) = match mark {
+                StageMark::Done => ("✓", Color::Green),
+                StageMark::Current => ("…", Color::Yellow),
+                StageMark::Pending => ("·", Color::DarkGray),
+            };
+            Line::from(vec![
+                Span::styled(format!("{} ", symbol), Style::default().fg(color)),
+                Span::styled(
+                    stage.label(),
+                    Style::default().fg(match mark {
+                        StageMark::Pending => Color::DarkGray,
+                        _ => Color::White,
+                    }),
+                ),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("Timing out in {}s...", seconds_left),
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let width = ConnectionStage::ALL
+        .iter()
+        .map(|stage| stage.label().len() as u16 + 2)
+        .max()
+        .unwrap_or(0)
+        .min(area.width);
+    let stages_area = Rect {
+        x: area.width.saturating_sub(width) / 2,
+        y,
+        width,
+        height,
+    };
+    frame.render_widget(Paragraph::new(lines), stages_area);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StageMark {
+    Done,
+    Current,
+    Pending,
+}
+
+/// Each stage's place relative to the last one reached
+fn connection_stage_marks(reached: Option<ConnectionStage>) -> Vec<(ConnectionStage, StageMark)> {
+    let mut current_marked = false;
+    ConnectionStage::ALL
+        .into_iter()
+        .map(|stage| {
+            let mark = if reached.is_some_and(|r| stage <= r) {
+                StageMark::Done
+            } else if !current_marked {
+                current_marked = true;
+                StageMark::Current
+            } else {
+                StageMark::Pending
+            };
+            (stage, mark)
+        })
+        .collect()
+}
+
+/// The peer ID as a QR code, one string per text row, drawn on a Braille canvas
+/// (2×4 modules per cell). Light modules are the lit dots, so the code reads
+/// dark-on-light on a black terminal. Built once per peer ID, not on every draw.
+pub fn peer_id_qr(peer_id: &str) -> Option<Vec<String>> {
+    let code = QrCode::new(peer_id).ok()?;
+    let size = code.width() + QR_QUIET_ZONE * 2;
+    let mut canvas = BrailleCanvas::new(size.div_ceil(2), size.div_ceil(4));
+
+    let data = QR_QUIET_ZONE..QR_QUIET_ZONE + code.width();
+    for y in 0..size {
+        for x in 0..size {
+            let dark = data.contains(&x)
+                && data.contains(&y)
+                && code[(x - QR_QUIET_ZONE, y - QR_QUIET_ZONE)] == qrcode::Color::Dark;
+            if !dark {
+                canvas.set_pixel(x, y);
+            }
+        }
+    }
+
+    let (cols, rows) = (canvas.pixel_width() / 2, canvas.pixel_height() / 4);
+    Some(
+        (0..rows)
+            .map(|y| (0..cols).map(|x| canvas.to_char(x, y)).collect())
+            .collect(),
+    )
+}
+
 /// Render waiting for connection screen (for host mode)
 pub fn render_waiting_for_connection(
     frame: &mut Frame,
     peer_id: &str,
+    qr: Option<&[String]>, // From peer_id_qr, if shown
+    copied: Option<&str>,  // Just copied to the clipboard, e.g. "peer ID"
+    stage: Option<ConnectionStage>,
+    seconds_left: u64,
     overlay: Option<&crate::ui::OverlayMessage>,
 ) {
     let area = frame.area();
@@ -391,15 +838,31 @@ pub fn render_waiting_for_connection(
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
-        Line::from(vec![
-            Span::styled(
-                "Q",
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(" to cancel", Style::default().fg(Color::DarkGray)),
-        ]),
+        match copied {
+            Some(what) => Line::from(Span::styled(
+                format!("Copied {} to clipboard", what),
+                Style::default().fg(Color::Green),
+            )),
+            None => {
+                let key = |k| {
+                    Span::styled(
+                        k,
+                        Style::default()
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                };
+                let hint = |h| Span::styled(h, Style::default().fg(Color::DarkGray));
+                Line::from(vec![
+                    key("C"),
+                    hint(" copy ID  "),
+                    key("X"),
+                    hint(" copy command  "),
+                    key("Q"),
+                    hint(" to cancel"),
+                ])
+            }
+        },
     ];
 
     let peer_id_widget = Paragraph::new(peer_id_lines)
@@ -411,19 +874,75 @@ pub fn render_waiting_for_connection(
                 .style(Style::default().bg(Color::Rgb(20, 20, 20))),
         );
 
+    // QR code above the peer ID box, if both fit
+    let qr = qr.filter(|rows| chunks[1].y as usize + rows.len() + 8 <= area.height as usize);
+    let mut box_y = chunks[1].y;
+    if let Some(qr) = qr {
+        let (cols, rows) = (qr.first().map_or(0, |row| row.chars().count()), qr.len());
+        let qr_lines: Vec<Line> = qr.iter().map(|row| Line::from(row.as_str())).collect();
+        let qr_area = Rect {
+            x: area.width.saturating_sub(cols as u16) / 2,
+            y: box_y,
+            width: (cols as u16).min(area.width),
+            height: rows as u16,
+        };
+        frame.render_widget(
+            Paragraph::new(qr_lines).style(Style::default().fg(Color::White)),
+            qr_area,
+        );
+        box_y += rows as u16 + 1;
+    }
+
     // Center the peer ID box (constant height now)
     let box_width = (peer_id.len() as u16 + 10).max(50).min(area.width - 4);
     let peer_id_area = Rect {
         x: (area.width.saturating_sub(box_width)) / 2,
-        y: chunks[1].y,
+        y: box_y,
         width: box_width,
         height: 7,
     };
 
     frame.render_widget(peer_id_widget, peer_id_area);
+    render_connection_progress(frame, stage, seconds_left, peer_id_area);
 
     // Render overlay if provided
     if let Some(overlay_msg) = overlay {
         crate::ui::overlay::render_overlay(frame, overlay_msg, area);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peer_id_qr() {
+        let rows = peer_id_qr("ABCD").unwrap();
+        // Version 1 (21 modules) plus the quiet zone, rounded up to whole cells
+        assert_eq!(rows.len(), 8);
+        assert!(rows.iter().all(|row| row.chars().count() == 15));
+        // The quiet zone is fully lit; the finder pattern corner is dark
+        assert_eq!(rows[0].chars().next(), Some('\u{28FF}'));
+        assert_ne!(rows[1].chars().nth(2), Some('\u{28FF}'));
+    }
+
+    #[test]
+    fn test_connection_stage_marks() {
+        let marks = |reached| {
+            connection_stage_marks(reached)
+                .into_iter()
+                .map(|(_, mark)| mark)
+                .collect::<Vec<_>>()
+        };
+        use StageMark::*;
+        assert_eq!(marks(None), [Current, Pending, Pending, Pending, Pending]);
+        assert_eq!(
+            marks(Some(ConnectionStage::OfferAnswerExchanged)),
+            [Done, Done, Done, Current, Pending]
+        );
+        assert_eq!(
+            marks(Some(ConnectionStage::DataChannelOpen)),
+            [Done, Done, Done, Done, Done]
+        );
+    }
+}
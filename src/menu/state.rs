@@ -1,6 +1,24 @@
 // Menu state management and game mode definitions
 
+use std::ops::RangeInclusive;
+
 use crate::ai::BotType;
+use crate::config::Config;
+use crate::game::Player;
+use crate::leaderboard::{self, Leaderboard};
+use crate::network;
+use crate::recent_peers::{self, RecentPeers};
+use crate::stats::{self, Stats};
+use crate::ui::theme::THEME_NAMES;
+
+/// Longest player name accepted for the leaderboard
+const MAX_NAME_LEN: usize = 16;
+
+/// Most of a pasted non-peer-ID kept in the input, so the player can see what went in
+const MAX_PASTE_SHOWN: usize = 24;
+
+/// Winning scores offered on the settings screen
+const WINNING_SCORES: RangeInclusive<u8> = 1..=21;
 
 /// Application state machine
 #[derive(Debug, Clone)]
@@ -16,14 +34,23 @@ pub enum AppState {
 /// Game mode selection
 #[derive(Debug, Clone)]
 pub enum GameMode {
-    /// Local 2-player on same keyboard
-    LocalTwoPlayer,
+    /// Local 2-player on same keyboard (empty names are not recorded on the leaderboard)
+    LocalTwoPlayer {
+        left_name: String,
+        right_name: String,
+    },
     /// Host P2P game (will display peer ID for others to join)
     NetworkHost,
     /// Join P2P game with peer ID
     NetworkClient(String),
-    /// Single player vs AI opponent
-    SinglePlayerAI(BotType),
+    /// Watch the P2P game hosted by the peer ID
+    Spectate(String),
+    /// Single player vs AI opponent, the player on the given side
+    SinglePlayerAI(BotType, Player),
+    /// Two bots playing each other (left, right)
+    AiVsAi(BotType, BotType),
+    /// Warm-up against the Backboard bot: no scoring, adjustable ball speed
+    Practice,
 }
 
 /// Menu items
@@ -32,10 +59,26 @@ pub enum MenuItem {
     LocalTwoPlayer,
     HostP2P,
     JoinP2P,
+    Spectate,
     SinglePlayerAI,
+    Practice,
+    AiVsAi,
+    Leaderboard,
+    Settings,
     Quit,
 }
 
+/// What the bot selection dialog is picking a bot for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BotSelectionPurpose {
+    /// Opponent for single player
+    Opponent,
+    /// Left bot of an AI-vs-AI demo
+    DemoLeft,
+    /// Right bot of an AI-vs-AI demo (left already chosen)
+    DemoRight(BotType),
+}
+
 impl MenuItem {
     /// Get display text for menu item
     pub fn display_text(&self) -> &str {
@@ -43,7 +86,12 @@ impl MenuItem {
             MenuItem::LocalTwoPlayer => "Local 2-Player",
             MenuItem::HostP2P => "Host P2P Game",
             MenuItem::JoinP2P => "Join P2P Game",
+            MenuItem::Spectate => "Watch P2P Game",
             MenuItem::SinglePlayerAI => "Single Player vs AI",
+            MenuItem::Practice => "Practice",
+            MenuItem::AiVsAi => "AI vs AI Demo",
+            MenuItem::Leaderboard => "Leaderboard",
+            MenuItem::Settings => "Settings",
             MenuItem::Quit => "Quit",
         }
     }
@@ -54,12 +102,60 @@ impl MenuItem {
             MenuItem::LocalTwoPlayer,
             MenuItem::HostP2P,
             MenuItem::JoinP2P,
+            MenuItem::Spectate,
             MenuItem::SinglePlayerAI,
+            MenuItem::Practice,
+            MenuItem::AiVsAi,
+            MenuItem::Leaderboard,
+            MenuItem::Settings,
             MenuItem::Quit,
         ]
     }
 }
 
+/// Rows of the settings screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Setting {
+    WinningScore,
+    DefaultBot,
+    Theme,
+    BallTrail,
+    ShowFps,
+}
+
+impl Setting {
+    pub fn label(&self) -> &str {
+        match self {
+            Setting::WinningScore => "Winning score",
+            Setting::DefaultBot => "Default bot",
+            Setting::Theme => "Color theme",
+            Setting::BallTrail => "Ball trail",
+            Setting::ShowFps => "FPS counter",
+        }
+    }
+
+    /// Get all settings in display order
+    pub fn all() -> Vec<Setting> {
+        vec![
+            Setting::WinningScore,
+            Setting::DefaultBot,
+            Setting::Theme,
+            Setting::BallTrail,
+            Setting::ShowFps,
+        ]
+    }
+}
+
+/// Index after stepping `forward` or back through `len` options, wrapping;
+/// an unrecognized current value starts from the first option
+fn cycle(current: Option<usize>, len: usize, forward: bool) -> usize {
+    match current {
+        None => 0,
+        Some(i) if forward => (i + 1) % len,
+        Some(i) => (i + len - 1) % len,
+    }
+}
+
 /// Menu state
 pub struct MenuState {
     /// Currently selected menu item index
@@ -70,12 +166,47 @@ pub struct MenuState {
     pub peer_id_input: String,
     /// Whether currently in peer ID input mode
     pub in_input_mode: bool,
+    /// Why the entered peer ID can't be used, shown in the dialog until it is edited
+    pub peer_id_error: Option<String>,
+    /// Whether the peer ID being entered is a match to watch rather than join
+    pub spectating: bool,
+    /// Recently joined peers, loaded when the peer ID dialog opens
+    pub recent_peers: RecentPeers,
+    /// Recent peer currently filled into the input, if any
+    pub selected_recent_peer: Option<usize>,
     /// Whether currently in bot selection mode
     pub in_bot_selection_mode: bool,
     /// Selected bot index during selection
     pub selected_bot_index: usize,
     /// Available bots
     pub available_bots: Vec<BotType>,
+    /// What the current bot selection is for
+    pub bot_selection_purpose: BotSelectionPurpose,
+    /// Lifetime record against each bot, loaded when bot selection opens
+    pub stats: Stats,
+    /// Side the player takes against a bot
+    pub player_side: Player,
+    /// Whether currently entering local player names
+    pub in_name_entry_mode: bool,
+    /// Left and right player names being entered
+    pub player_names: [String; 2],
+    /// Which name field is being edited (0 = left, 1 = right)
+    pub name_field: usize,
+    /// Whether the leaderboard screen is open
+    pub in_leaderboard_mode: bool,
+    /// Leaderboard loaded when the screen is opened
+    pub leaderboard: Leaderboard,
+    /// Current config; edited in place by the settings screen
+    pub config: Config,
+    /// Whether the settings screen is open
+    pub in_settings_mode: bool,
+    /// Selected row on the settings screen
+    pub selected_setting_index: usize,
+    /// Whether anything was changed since the settings screen opened
+    pub settings_changed: bool,
+    /// Error shown below the menu: why saving the settings failed (until they are saved
+    /// again), or the network failure that ended the last game
+    pub error: Option<String>,
 }
 
 impl MenuState {
@@ -85,9 +216,34 @@ impl MenuState {
             items: MenuItem::all(),
             peer_id_input: String::new(),
             in_input_mode: false,
+            peer_id_error: None,
+            spectating: false,
+            recent_peers: RecentPeers::default(),
+            selected_recent_peer: None,
             in_bot_selection_mode: false,
             selected_bot_index: 0,
             available_bots: BotType::all(),
+            bot_selection_purpose: BotSelectionPurpose::Opponent,
+            stats: Stats::default(),
+            player_side: Player::Left,
+            in_name_entry_mode: false,
+            player_names: [String::new(), String::new()],
+            name_field: 0,
+            in_leaderboard_mode: false,
+            leaderboard: Leaderboard::default(),
+            config: Config::default(),
+            in_settings_mode: false,
+            selected_setting_index: 0,
+            settings_changed: false,
+            error: None,
+        }
+    }
+
+    /// Menu for the given config (used for the settings screen and default bot)
+    pub fn with_config(config: Config) -> Self {
+        Self {
+            config,
+            ..Self::new()
         }
     }
 
@@ -114,10 +270,27 @@ impl MenuState {
         }
     }
 
-    /// Enter peer ID input mode
-    pub fn start_peer_id_input(&mut self) {
+    /// Enter peer ID input mode, to join the peer's game or (`spectating`) watch it
+    pub fn start_peer_id_input(&mut self, spectating: bool) {
         self.in_input_mode = true;
+        self.spectating = spectating;
         self.peer_id_input.clear();
+        self.peer_id_error = None;
+        self.recent_peers = recent_peers::load();
+        self.selected_recent_peer = None;
+    }
+
+    /// Fill the input with the next (or previous) recent peer
+    pub fn cycle_recent_peer(&mut self, forward: bool) {
+        let count = self.recent_peers.peers.len();
+        if count == 0 {
+            return;
+        }
+
+        let index = cycle(self.selected_recent_peer, count, forward);
+        self.selected_recent_peer = Some(index);
+        self.peer_id_input = self.recent_peers.peers[index].peer_id.clone();
+        self.peer_id_error = None;
     }
 
     /// Exit peer ID input mode
@@ -127,26 +300,63 @@ impl MenuState {
     }
 
     /// Get peer ID and exit input mode (converts to uppercase for case-insensitive matching)
-    pub fn submit_peer_id(&mut self) -> String {
-        self.in_input_mode = false;
-        self.peer_id_input.to_uppercase()
+    /// If it isn't a valid peer ID the dialog stays open and says why
+    pub fn submit_peer_id(&mut self) -> Option<String> {
+        match network::parse_peer_id(&self.peer_id_input) {
+            Ok(peer_id) => {
+                self.in_input_mode = false;
+                Some(peer_id)
+            }
+            Err(e) => {
+                self.peer_id_error = Some(e);
+                None
+            }
+        }
+    }
+
+    /// Replace the peer ID input with pasted text, cleaned up if it holds a peer ID
+    pub fn paste_peer_id(&mut self, text: &str) {
+        self.selected_recent_peer = None;
+        match network::parse_peer_id(text) {
+            Ok(peer_id) => {
+                self.peer_id_input = peer_id;
+                self.peer_id_error = None;
+            }
+            Err(e) => {
+                self.peer_id_input = text
+                    .chars()
+                    .filter(|c| c.is_alphanumeric() || *c == '-')
+                    .take(MAX_PASTE_SHOWN)
+                    .collect();
+                self.peer_id_error = Some(e);
+            }
+        }
     }
 
     /// Add character to peer ID input
     pub fn add_char_to_peer_id(&mut self, c: char) {
         self.peer_id_input.push(c);
+        self.selected_recent_peer = None;
+        self.peer_id_error = None;
     }
 
     /// Remove last character from peer ID input
     pub fn backspace_peer_id(&mut self) {
         self.peer_id_input.pop();
+        self.selected_recent_peer = None;
+        self.peer_id_error = None;
     }
 
     /// Enter bot selection mode
-    pub fn start_bot_selection(&mut self) {
+    pub fn start_bot_selection(&mut self, purpose: BotSelectionPurpose) {
+        self.bot_selection_purpose = purpose;
         self.in_bot_selection_mode = true;
-        self.selected_bot_index = 0;
         self.available_bots = BotType::all();
+        // Start on the configured default bot
+        self.selected_bot_index = BotType::from_name(&self.config.ai.difficulty)
+            .and_then(|bot| self.available_bots.iter().position(|b| *b == bot))
+            .unwrap_or(0);
+        self.stats = stats::load();
     }
 
     /// Exit bot selection mode
@@ -172,11 +382,150 @@ impl MenuState {
         }
     }
 
+    /// Switch the side the player takes against the bot
+    pub fn toggle_player_side(&mut self) {
+        self.player_side = self.player_side.opponent();
+    }
+
     /// Get bot type and exit selection mode
     pub fn submit_bot_selection(&mut self) -> BotType {
         self.in_bot_selection_mode = false;
         self.available_bots[self.selected_bot_index]
     }
+
+    /// Enter player name entry, prefilled with the names used last time
+    pub fn start_name_entry(&mut self) {
+        let board = leaderboard::load();
+        self.player_names = [board.last_left_name, board.last_right_name];
+        self.name_field = 0;
+        self.in_name_entry_mode = true;
+    }
+
+    /// Exit player name entry
+    pub fn cancel_name_entry(&mut self) {
+        self.in_name_entry_mode = false;
+    }
+
+    /// Switch between the left and right name fields
+    pub fn toggle_name_field(&mut self) {
+        self.name_field = 1 - self.name_field;
+    }
+
+    /// Add character to the active name field
+    pub fn add_char_to_name(&mut self, c: char) {
+        let name = &mut self.player_names[self.name_field];
+        if name.chars().count() < MAX_NAME_LEN {
+            name.push(c);
+        }
+    }
+
+    /// Remove last character from the active name field
+    pub fn backspace_name(&mut self) {
+        self.player_names[self.name_field].pop();
+    }
+
+    /// Get (left, right) names and exit name entry
+    pub fn submit_names(&mut self) -> (String, String) {
+        self.in_name_entry_mode = false;
+        let [left, right] = &self.player_names;
+        (left.trim().to_string(), right.trim().to_string())
+    }
+
+    /// Open the leaderboard screen
+    pub fn start_leaderboard_view(&mut self) {
+        self.leaderboard = leaderboard::load();
+        self.in_leaderboard_mode = true;
+    }
+
+    /// Close the leaderboard screen
+    pub fn close_leaderboard_view(&mut self) {
+        self.in_leaderboard_mode = false;
+    }
+
+    /// Open the settings screen
+    pub fn start_settings(&mut self) {
+        self.in_settings_mode = true;
+        self.selected_setting_index = 0;
+        self.settings_changed = false;
+    }
+
+    /// Close the settings screen, returning the config to save if anything changed
+    pub fn close_settings(&mut self) -> Option<Config> {
+        self.in_settings_mode = false;
+        self.settings_changed.then(|| self.config.clone())
+    }
+
+    /// Get currently selected setting
+    pub fn selected_setting(&self) -> Setting {
+        Setting::all()[self.selected_setting_index]
+    }
+
+    /// Move selection up in the settings list
+    pub fn select_previous_setting(&mut self) {
+        self.selected_setting_index = cycle(
+            Some(self.selected_setting_index),
+            Setting::all().len(),
+            false,
+        );
+    }
+
+    /// Move selection down in the settings list
+    pub fn select_next_setting(&mut self) {
+        self.selected_setting_index = cycle(
+            Some(self.selected_setting_index),
+            Setting::all().len(),
+            true,
+        );
+    }
+
+    /// Step the selected setting to its next (or previous) value
+    pub fn adjust_setting(&mut self, forward: bool) {
+        let setting = self.selected_setting();
+        let config = &mut self.config;
+        match setting {
+            Setting::WinningScore => {
+                let score = &mut config.physics.winning_score;
+                *score = if forward {
+                    score.saturating_add(1)
+                } else {
+                    score.saturating_sub(1)
+                }
+                .clamp(*WINNING_SCORES.start(), *WINNING_SCORES.end());
+            }
+            Setting::DefaultBot => {
+                let bots = BotType::all();
+                let current = BotType::from_name(&config.ai.difficulty)
+                    .and_then(|bot| bots.iter().position(|b| *b == bot));
+                let bot = bots[cycle(current, bots.len(), forward)];
+                config.ai.difficulty = bot.display_name().to_lowercase();
+            }
+            Setting::Theme => {
+                let theme = &mut config.display.theme.name;
+                let current = THEME_NAMES
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(theme));
+                *theme = THEME_NAMES[cycle(current, THEME_NAMES.len(), forward)].to_string();
+            }
+            Setting::BallTrail => config.display.ball_trail = !config.display.ball_trail,
+            Setting::ShowFps => config.display.show_fps = !config.display.show_fps,
+        }
+        self.settings_changed = true;
+    }
+
+    /// Display text for a setting's current value
+    pub fn setting_value(&self, setting: Setting) -> String {
+        let on_off = |on: bool| if on { "On" } else { "Off" }.to_string();
+        match setting {
+            Setting::WinningScore => self.config.physics.winning_score.to_string(),
+            Setting::DefaultBot => BotType::from_name(&self.config.ai.difficulty)
+                .unwrap_or(BotType::all()[0])
+                .display_name()
+                .to_string(),
+            Setting::Theme => self.config.display.theme.name.to_lowercase(),
+            Setting::BallTrail => on_off(self.config.display.ball_trail),
+            Setting::ShowFps => on_off(self.config.display.show_fps),
+        }
+    }
 }
 
 impl Default for MenuState {
@@ -184,3 +533,54 @@ impl Default for MenuState {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peer_id_paste_and_submit() {
+        let mut menu = MenuState::new();
+        menu.in_input_mode = true;
+
+        // A copied connect command is cut down to its peer ID
+        menu.paste_peer_id("p2pong --connect abcd\n");
+        assert_eq!(menu.peer_id_input, "ABCD");
+        assert!(menu.peer_id_error.is_none());
+        assert_eq!(menu.submit_peer_id().as_deref(), Some("ABCD"));
+        assert!(!menu.in_input_mode);
+
+        // Anything else stays in the dialog with the reason shown until edited
+        menu.in_input_mode = true;
+        menu.paste_peer_id("https://example.com");
+        assert!(menu.peer_id_error.is_some());
+        assert_eq!(menu.submit_peer_id(), None);
+        assert!(menu.in_input_mode);
+        menu.backspace_peer_id();
+        assert!(menu.peer_id_error.is_none());
+    }
+
+    #[test]
+    fn test_adjust_settings() {
+        let mut menu = MenuState::with_config(Config::default());
+        menu.start_settings();
+        assert!(menu.close_settings().is_none());
+
+        menu.start_settings();
+        menu.config.physics.winning_score = *WINNING_SCORES.end();
+        menu.adjust_setting(true);
+        assert_eq!(menu.config.physics.winning_score, *WINNING_SCORES.end());
+
+        // Themes wrap around in both directions
+        while menu.selected_setting() != Setting::Theme {
+            menu.select_next_setting();
+        }
+        menu.adjust_setting(false);
+        assert_eq!(menu.setting_value(Setting::Theme), THEME_NAMES[2]);
+        menu.adjust_setting(true);
+        assert_eq!(menu.setting_value(Setting::Theme), THEME_NAMES[0]);
+
+        let saved = menu.close_settings().expect("settings changed");
+        assert_eq!(saved.display.theme.name, THEME_NAMES[0]);
+    }
+}
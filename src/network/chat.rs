@@ -0,0 +1,60 @@
+// In-game text chat between networked peers
+// Text from the peer is untrusted and is sanitized before it reaches the terminal
+
+/// Longest chat message, in characters
+pub const MAX_CHAT_LEN: usize = 120;
+
+/// Chat lines kept on screen
+const CHAT_LOG_LINES: usize = 4;
+
+/// Strip control characters (escape sequences, newlines, ...) and cap the length
+pub fn sanitize_chat(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_CHAT_LEN)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Most recent chat lines, oldest first
+#[derive(Debug, Default)]
+pub struct ChatLog {
+    lines: Vec<String>,
+}
+
+impl ChatLog {
+    /// Add a message from `sender`, scrolling the oldest line off when full
+    pub fn push(&mut self, sender: &str, text: &str) {
+        if self.lines.len() == CHAT_LOG_LINES {
+            self.lines.remove(0);
+        }
+        self.lines.push(format!("{}: {}", sender, text));
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_chat() {
+        assert_eq!(sanitize_chat("  gg\u{1b}[2J wp\n"), "gg[2J wp");
+        assert_eq!(sanitize_chat(&"x".repeat(500)).len(), MAX_CHAT_LEN);
+        assert_eq!(sanitize_chat("\u{7}\u{8}"), "");
+    }
+
+    #[test]
+    fn test_chat_log_scrolls() {
+        let mut log = ChatLog::default();
+        for i in 0..CHAT_LOG_LINES + 2 {
+            log.push("You", &i.to_string());
+        }
+        assert_eq!(log.lines().len(), CHAT_LOG_LINES);
+        assert_eq!(log.lines()[0], "You: 2");
+    }
+}
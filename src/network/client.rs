@@ -1,20 +1,27 @@
 // Network client interface for the game loop
 // Provides channels to communicate with the libp2p network thread
 
-use super::{protocol::BallState, NetworkMessage};
+use super::{
+    protocol::{BallState, MatchSetup},
+    NetworkMessage,
+};
+use crate::error::P2PongError;
+use crate::game::state::{MatchStats, PowerUp};
 use crate::game::InputAction;
-use std::io;
 use std::sync::mpsc;
 
 /// Connection mode for the network layer
 #[derive(Debug, Clone)]
 pub enum ConnectionMode {
-    /// Listen for incoming connections (Host)
-    Listen,
+    /// Listen for incoming connections (Host), and optionally for spectators
+    Listen { allow_spectators: bool },
 
     /// Connect to a specific peer (Client)
     /// The "multiaddr" is now just the peer ID to connect to
     Connect { multiaddr: String },
+
+    /// Watch the match a host is playing, without taking part (Spectator)
+    Spectate { peer_id: String },
 }
 
 /// Handle for the game loop to communicate with the network
@@ -35,24 +42,86 @@ pub enum NetworkCommand {
 
     /// Send a network message (for ball sync, etc.)
     SendMessage(NetworkMessage),
+
+    /// Send a message only to spectators (host; dropped if nobody is watching)
+    SendSpectators(NetworkMessage),
+
+    /// Tell the peer we're leaving, close the connection and stop the network thread
+    Disconnect,
+}
+
+/// Why a connection ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// The peer closed the connection (sent Disconnect)
+    PeerQuit,
+    /// The connection dropped and didn't recover within the reconnect window
+    Timeout,
+    /// ICE lost the network path to the peer (with no reconnect window to wait out)
+    IceFailure,
+    /// The network thread failed for another reason
+    Unknown,
+}
+
+/// Steps of connection setup, in the order they complete
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConnectionStage {
+    /// The signaling server accepted our peer ID
+    SignalingRegistered,
+    /// Local (and STUN) candidates are ready to go into the SDP
+    IceGathered,
+    /// Offer and answer have been swapped through the signaling server
+    OfferAnswerExchanged,
+    /// ICE found a working path to the peer
+    IceConnected,
+    /// The data channel is open for game traffic
+    DataChannelOpen,
+}
+
+impl ConnectionStage {
+    pub const ALL: [ConnectionStage; 5] = [
+        ConnectionStage::SignalingRegistered,
+        ConnectionStage::IceGathered,
+        ConnectionStage::OfferAnswerExchanged,
+        ConnectionStage::IceConnected,
+        ConnectionStage::DataChannelOpen,
+    ];
+
+    /// Checklist label for the waiting screens
+    pub fn label(self) -> &'static str {
+        match self {
+            ConnectionStage::SignalingRegistered => "Registered with signaling server",
+            ConnectionStage::IceGathered => "Network addresses gathered",
+            ConnectionStage::OfferAnswerExchanged => "Offer and answer exchanged",
+            ConnectionStage::IceConnected => "Peer reachable (ICE connected)",
+            ConnectionStage::DataChannelOpen => "Data channel open",
+        }
+    }
 }
 
 /// Events the network thread sends to the game loop
 #[derive(Debug)]
 pub enum NetworkEvent {
-    /// Received input from opponent
-    ReceivedInput(InputAction),
+    /// Received input from opponent, with its send-order sequence number
+    ReceivedInput { action: InputAction, sequence: u32 },
 
     /// Received ball state from host
     ReceivedBallState(BallState),
 
-    /// Received score update from host (authoritative)
+    /// Received score update from host (authoritative), with the match tally and the
+    /// match stats once the game is over
     ReceivedScore {
         left: u8,
         right: u8,
         game_over: bool,
+        left_games: u8,
+        right_games: u8,
+        stats: Option<MatchStats>,
     },
 
+    /// Connection quality over the last few heartbeats and pings
+    ConnectionStats { loss_pct: f32, jitter_ms: f32 },
+
     /// Received ping request (respond with pong)
     ReceivedPing { timestamp_ms: u64 },
 
@@ -68,6 +137,35 @@ pub enum NetworkEvent {
     /// Received quit request from opponent
     ReceivedQuitRequest,
 
+    /// Received match settings from host
+    ReceivedMatchSetup(MatchSetup),
+
+    /// The opponent's game has started
+    PeerReady,
+
+    /// Received lockstep inputs from the opponent
+    ReceivedLockstepInput {
+        first_frame: u64,
+        inputs: Vec<Vec<InputAction>>,
+        ack: u64,
+    },
+
+    /// Received both paddles' positions from the host (spectators)
+    ReceivedPaddles { left_y: f32, right_y: f32 },
+
+    /// Received the host's power-ups and paddle heights
+    ReceivedPowerUps {
+        powerups: Vec<PowerUp>,
+        left_paddle_height: f32,
+        right_paddle_height: f32,
+    },
+
+    /// Received a chat line from the opponent (already sanitized)
+    ReceivedChat(String),
+
+    /// Connection setup reached a new stage
+    ConnectionStage(ConnectionStage),
+
     /// Local peer ID is ready (for host to display)
     LocalPeerIdReady { peer_id: String },
 
@@ -77,13 +175,27 @@ pub enum NetworkEvent {
     /// Data channel opened and ready for messages
     DataChannelOpened,
 
-    /// Peer disconnected
-    Disconnected,
+    /// Peer's version handshake matched ours
+    PeerHello { protocol_version: u16 },
+
+    /// Connection dropped; ICE is trying to recover it
+    Reconnecting,
+
+    /// Connection recovered after `Reconnecting`
+    Reconnected,
+
+    /// Connection ended, and why
+    Disconnected(DisconnectReason),
 
     /// Network error occurred
     Error(String),
 }
 
+/// Commands can't be sent once the network thread has exited
+fn network_thread_gone() -> P2PongError {
+    P2PongError::Network("network thread has stopped".to_string())
+}
+
 impl NetworkClient {
     /// Create a new network client (called by start_network)
     pub fn new(tx: mpsc::Sender<NetworkCommand>, rx: mpsc::Receiver<NetworkEvent>) -> Self {
@@ -91,17 +203,31 @@ impl NetworkClient {
     }
 
     /// Send an input action to the opponent
-    pub fn send_input(&self, action: InputAction) -> io::Result<()> {
+    pub fn send_input(&self, action: InputAction) -> Result<(), P2PongError> {
         self.tx
             .send(NetworkCommand::SendInput(action))
-            .map_err(|e| io::Error::new(io::ErrorKind::BrokenPipe, e))
+            .map_err(|_| network_thread_gone())
     }
 
     /// Send a network message (for ball sync, etc.)
-    pub fn send_message(&self, msg: NetworkMessage) -> io::Result<()> {
+    pub fn send_message(&self, msg: NetworkMessage) -> Result<(), P2PongError> {
         self.tx
             .send(NetworkCommand::SendMessage(msg))
-            .map_err(|e| io::Error::new(io::ErrorKind::BrokenPipe, e))
+            .map_err(|_| network_thread_gone())
+    }
+
+    /// Send a message to anyone watching the match (host only)
+    pub fn send_spectators(&self, msg: NetworkMessage) -> Result<(), P2PongError> {
+        self.tx
+            .send(NetworkCommand::SendSpectators(msg))
+            .map_err(|_| network_thread_gone())
+    }
+
+    /// Close the connection, telling the peer first (the network thread then exits)
+    pub fn disconnect(&self) -> Result<(), P2PongError> {
+        self.tx
+            .send(NetworkCommand::Disconnect)
+            .map_err(|_| network_thread_gone())
     }
 
     /// Try to receive network events (non-blocking)
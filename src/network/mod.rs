@@ -1,20 +1,53 @@
 // P2P networking module for P2Pong
 // Handles WebRTC connection, message passing, and game synchronization
 
+pub mod chat;
 pub mod client;
 pub mod protocol;
+pub mod spectators;
+pub mod stats;
 pub mod webrtc_runtime;
 
-pub use client::{ConnectionMode, NetworkClient};
-pub use protocol::{BallState, NetworkMessage};
+pub use client::{ConnectionMode, ConnectionStage, DisconnectReason, NetworkClient};
+pub use protocol::{BallState, MatchSetup, NetMode, NetworkMessage, PROTOCOL_VERSION};
+
+use crate::config::types::IceServerConfig;
+use crate::error::P2PongError;
 
-use std::io;
 use std::sync::mpsc;
 use std::sync::{atomic::AtomicBool, Arc};
+use std::time::Duration;
+
+/// Peer IDs handed out at registration are this many letters (A-Z)
+pub const PEER_ID_LEN: usize = 4;
+
+/// Peer ID from what the player typed or pasted. Surrounding whitespace and anything up to
+/// `--connect` (as in a copied connect command) are dropped; case doesn't matter.
+/// The error says what is wrong, for showing in the peer ID dialog.
+pub fn parse_peer_id(text: &str) -> Result<String, String> {
+    let id = text
+        .split_once("--connect")
+        .map_or(text, |(_, id)| id)
+        .trim()
+        .to_uppercase();
+    if id.is_empty() {
+        return Err("Enter the host's peer ID".to_string());
+    }
+    if id.len() != PEER_ID_LEN || !id.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(format!("A peer ID is {} letters, like ABCD", PEER_ID_LEN));
+    }
+    Ok(id)
+}
 
 /// Initialize and start the network layer
 /// Returns a NetworkClient handle for the game loop to communicate with
-pub fn start_network(mode: ConnectionMode, signaling_server: String) -> io::Result<NetworkClient> {
+pub fn start_network(
+    mode: ConnectionMode,
+    signaling_server: String,
+    require_tls: bool,
+    ice_servers: &[IceServerConfig],
+    ice_disconnect_timeout_secs: u64,
+) -> Result<NetworkClient, P2PongError> {
     // Create channels for bidirectional communication
     let (event_tx, event_rx) = mpsc::channel();
     let (cmd_tx, cmd_rx) = mpsc::channel();
@@ -23,8 +56,42 @@ pub fn start_network(mode: ConnectionMode, signaling_server: String) -> io::Resu
     let connected = Arc::new(AtomicBool::new(false));
 
     // Spawn network thread with WebRTC runtime
-    webrtc_runtime::spawn_network_thread(mode, event_tx, cmd_rx, connected, signaling_server)?;
+    let ice = webrtc_runtime::IceSettings {
+        stun_servers: webrtc_runtime::stun_servers(ice_servers),
+        disconnect_timeout: (ice_disconnect_timeout_secs > 0)
+            .then(|| Duration::from_secs(ice_disconnect_timeout_secs)),
+    };
+    webrtc_runtime::spawn_network_thread(
+        mode,
+        event_tx,
+        cmd_rx,
+        connected,
+        signaling_server,
+        require_tls,
+        ice,
+    )
+    .map_err(|e| P2PongError::Network(format!("Can't start the network thread: {}", e)))?;
 
     // Return client handle for game loop
     Ok(NetworkClient::new(cmd_tx, event_rx))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_peer_id() {
+        assert_eq!(parse_peer_id("abcd").unwrap(), "ABCD");
+        assert_eq!(parse_peer_id("  WXYZ\n").unwrap(), "WXYZ");
+        assert_eq!(parse_peer_id("p2pong --connect QRST").unwrap(), "QRST");
+        assert_eq!(parse_peer_id("--connect  qrst ").unwrap(), "QRST");
+
+        assert!(parse_peer_id("").is_err());
+        assert!(parse_peer_id(" \n").is_err());
+        assert!(parse_peer_id("ABC").is_err());
+        assert!(parse_peer_id("AB CD").is_err());
+        assert!(parse_peer_id("AB12").is_err());
+        assert!(parse_peer_id("p2pong --connect").is_err());
+    }
+}
@@ -1,12 +1,20 @@
 // P2Pong network protocol definition
 // Messages exchanged over WebRTC data channels
 
-use crate::game::InputAction;
+use crate::config::{Config, PhysicsConfig};
+use crate::game::physics::{PaddleMode, ServePolicy};
+use crate::game::state::{MatchStats, Obstacle, PowerUp};
+use crate::game::{InputAction, Player};
 use serde::{Deserialize, Serialize};
 
+/// Version of the wire format and game rules. Bump whenever messages or physics
+/// change in a way that would desync an older peer.
+pub const PROTOCOL_VERSION: u16 = 15;
+
 /// Ball state for synchronization
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BallState {
+    pub index: u8, // Which ball (0 = main; multi-ball extras follow)
     pub x: f32,
     pub y: f32,
     pub vx: f32,
@@ -15,24 +23,182 @@ pub struct BallState {
     pub timestamp_ms: u64, // Timestamp for latency measurement
 }
 
+/// Ball state quantized for the wire: positions in whole virtual units, velocities in
+/// whole units per second (the field and speed cap both fit in an i16)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CompactBallState {
+    pub index: u8,
+    pub x: i16,
+    pub y: i16,
+    pub vx: i16,
+    pub vy: i16,
+    pub sequence: u32,
+}
+
+impl CompactBallState {
+    pub fn from_ball(state: &BallState) -> Self {
+        let quantize = |v: f32| v.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+        Self {
+            index: state.index,
+            x: quantize(state.x),
+            y: quantize(state.y),
+            vx: quantize(state.vx),
+            vy: quantize(state.vy),
+            sequence: state.sequence as u32,
+        }
+    }
+
+    /// Back to full precision (the send timestamp is not carried)
+    pub fn expand(&self) -> BallState {
+        BallState {
+            index: self.index,
+            x: self.x as f32,
+            y: self.y as f32,
+            vx: self.vx as f32,
+            vy: self.vy as f32,
+            sequence: self.sequence as u64,
+            timestamp_ms: 0,
+        }
+    }
+}
+
+/// How peers keep their games in sync
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetMode {
+    /// Host simulates and streams ball state; the client dead-reckons between syncs
+    #[default]
+    HostAuthoritative,
+    /// Both peers run the same deterministic simulation and only exchange inputs
+    Lockstep,
+}
+
+/// Match settings chosen by the host and sent to the client at handshake
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchSetup {
+    pub net_mode: NetMode,            // Sync model both peers use
+    pub host_side: Player,            // Side the host plays on; the client takes the other
+    pub tick_rate_hz: u32,            // Physics tick rate both peers simulate at
+    pub winning_score: u8,            // Points needed to win; the host's value is authoritative
+    pub paddle_mode: PaddleMode,      // Paddle control scheme both peers use
+    pub paddle_height: f32,           // Paddle height in virtual units
+    pub tap_distance: f32,            // Paddle movement per tap (both peers apply every input)
+    pub serve_seed: Option<u64>,      // Seed for reproducible serves, if any
+    pub serve_policy: ServePolicy,    // Who each serve goes to
+    pub games_to_win: u8,             // Match length (1 = single games)
+    pub time_limit_secs: Option<u64>, // Seconds per game, if timed
+    // Remaining rules; lockstep peers must simulate with identical values
+    pub ball_initial_speed: f32,
+    pub ball_speed_multiplier: f32,
+    pub reset_speed_each_round: bool,
+    pub max_ball_speed: f32,
+    pub spin_factor: f32,
+    pub ball_count: u8,
+    pub win_by_two: bool,
+    pub catch_enabled: bool,
+    pub catch_max_hold_secs: f32,
+    pub virtual_width: f32,
+    pub virtual_height: f32,
+    pub obstacles: Vec<Obstacle>,
+    pub powerups: bool,
+}
+
+impl MatchSetup {
+    /// Settings the host offers from its own config
+    pub fn from_config(config: &Config) -> Self {
+        let physics = &config.physics;
+        Self {
+            net_mode: config.network.net_mode,
+            host_side: config.network.host_side,
+            tick_rate_hz: physics.tick_rate_hz,
+            winning_score: physics.winning_score,
+            paddle_mode: physics.paddle_mode,
+            paddle_height: physics.paddle_height,
+            tap_distance: physics.paddle_tap_distance,
+            serve_seed: physics.serve_seed,
+            serve_policy: physics.serve_policy,
+            games_to_win: physics.games_to_win,
+            time_limit_secs: physics.time_limit_secs,
+            ball_initial_speed: physics.ball_initial_speed,
+            ball_speed_multiplier: physics.ball_speed_multiplier,
+            reset_speed_each_round: physics.reset_speed_each_round,
+            max_ball_speed: physics.max_ball_speed,
+            spin_factor: physics.spin_factor,
+            ball_count: physics.ball_count,
+            win_by_two: physics.win_by_two,
+            catch_enabled: physics.catch_enabled,
+            catch_max_hold_secs: physics.catch_max_hold_secs,
+            virtual_width: physics.virtual_width,
+            virtual_height: physics.virtual_height,
+            obstacles: physics.obstacles.clone(),
+            powerups: physics.powerups,
+        }
+    }
+
+    /// Physics config both peers simulate with
+    pub fn physics(&self) -> PhysicsConfig {
+        PhysicsConfig {
+            ball_initial_speed: self.ball_initial_speed,
+            paddle_height: self.paddle_height,
+            paddle_tap_distance: self.tap_distance,
+            paddle_mode: self.paddle_mode,
+            serve_seed: self.serve_seed,
+            serve_policy: self.serve_policy,
+            winning_score: self.winning_score,
+            win_by_two: self.win_by_two,
+            games_to_win: self.games_to_win,
+            time_limit_secs: self.time_limit_secs,
+            ball_speed_multiplier: self.ball_speed_multiplier,
+            reset_speed_each_round: self.reset_speed_each_round,
+            max_ball_speed: self.max_ball_speed,
+            spin_factor: self.spin_factor,
+            ball_count: self.ball_count,
+            virtual_width: self.virtual_width,
+            virtual_height: self.virtual_height,
+            obstacles: self.obstacles.clone(),
+            powerups: self.powerups,
+            tick_rate_hz: self.tick_rate_hz,
+            catch_enabled: self.catch_enabled,
+            catch_max_hold_secs: self.catch_max_hold_secs,
+        }
+    }
+}
+
 /// Messages exchanged between peers during gameplay
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum NetworkMessage {
-    /// Player input action
-    Input(InputAction),
+    /// Player input action, numbered in send order (the channel is unordered, so a
+    /// late input is dropped rather than applied after a newer one)
+    Input { action: InputAction, sequence: u32 },
 
     /// Ball physics state (sent by host)
     BallSync(BallState),
 
-    /// Score update from host (authoritative)
+    /// Quantized ball state; the network thread sends every BallSync in this form
+    BallSyncCompact(CompactBallState),
+
+    /// Score update from host (authoritative), with the match tally and, once the game
+    /// is over, the match stats (only the host runs the physics that counts them)
     ScoreSync {
         left: u8,
         right: u8,
         game_over: bool,
+        left_games: u8,
+        right_games: u8,
+        stats: Option<MatchStats>,
     },
 
-    /// Handshake message sent on connection
-    Hello { peer_name: String },
+    /// Version handshake, sent by both peers as soon as the data channel opens
+    /// (match settings such as the winning score follow in `MatchSetup`)
+    Hello { protocol_version: u16 },
+
+    /// Match settings from host (resent from when the data channel opens until the
+    /// client's Ready shows it arrived)
+    MatchSetup(MatchSetup),
+
+    /// The sender's game has started; play begins once both peers have seen the
+    /// other's. Resent until the peer's arrives, and answered once playing.
+    Ready,
 
     /// RTT measurement request
     Ping { timestamp_ms: u64 },
@@ -55,6 +221,30 @@ pub enum NetworkMessage {
 
     /// Graceful disconnect
     Disconnect,
+
+    /// Chat line typed by the peer (untrusted: sanitize before display)
+    Chat { text: String },
+
+    /// Power-ups changed on the host (spawned, collected or worn off): everything on
+    /// the field and both paddle heights. Ball speed changes follow in BallSync.
+    PowerUpEvent {
+        powerups: Vec<PowerUp>,
+        left_paddle_height: f32,
+        right_paddle_height: f32,
+    },
+
+    /// Both paddles' positions, sent by the host to spectators (players steer their
+    /// own paddles from input, so never receive this)
+    PaddleSync { left_y: f32, right_y: f32 },
+
+    /// Lockstep inputs: the sender's actions for each frame from `first_frame` on.
+    /// Every frame the peer hasn't acknowledged is resent, since the channel is lossy.
+    /// `ack` is the first frame of the peer's inputs the sender is still missing.
+    LockstepInput {
+        first_frame: u64,
+        inputs: Vec<Vec<InputAction>>,
+        ack: u64,
+    },
 }
 
 impl NetworkMessage {
@@ -75,12 +265,18 @@ mod tests {
 
     #[test]
     fn test_message_serialization() {
-        let msg = NetworkMessage::Input(InputAction::LeftPaddleUp);
+        let msg = NetworkMessage::Input {
+            action: InputAction::LeftPaddleUp,
+            sequence: 7,
+        };
         let bytes = msg.to_bytes().unwrap();
         let decoded = NetworkMessage::from_bytes(&bytes).unwrap();
 
         match decoded {
-            NetworkMessage::Input(InputAction::LeftPaddleUp) => {}
+            NetworkMessage::Input {
+                action: InputAction::LeftPaddleUp,
+                sequence: 7,
+            } => {}
             _ => panic!("Message didn't round-trip correctly"),
         }
     }
@@ -97,10 +293,118 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_hello_serialization() {
+        let msg = NetworkMessage::Hello {
+            protocol_version: PROTOCOL_VERSION,
+        };
+        let bytes = msg.to_bytes().unwrap();
+        let decoded = NetworkMessage::from_bytes(&bytes).unwrap();
+
+        match decoded {
+            NetworkMessage::Hello { protocol_version } => {
+                assert_eq!(protocol_version, PROTOCOL_VERSION)
+            }
+            _ => panic!("Hello didn't round-trip correctly, got: {:?}", decoded),
+        }
+    }
+
+    #[test]
+    fn test_score_sync_carries_match_stats() {
+        let stats = MatchStats {
+            hits: 12,
+            longest_rally: 5,
+            current_rally: 0,
+            top_speed: 840.0,
+            duration: 93.5,
+        };
+        let msg = NetworkMessage::ScoreSync {
+            left: 5,
+            right: 3,
+            game_over: true,
+            left_games: 1,
+            right_games: 0,
+            stats: Some(stats),
+        };
+        let bytes = msg.to_bytes().unwrap();
+        let decoded = NetworkMessage::from_bytes(&bytes).unwrap();
+
+        match decoded {
+            NetworkMessage::ScoreSync {
+                stats: Some(decoded_stats),
+                ..
+            } => assert_eq!(decoded_stats, stats),
+            _ => panic!("ScoreSync didn't round-trip correctly, got: {:?}", decoded),
+        }
+    }
+
+    #[test]
+    fn test_match_setup_serialization() {
+        let msg = NetworkMessage::MatchSetup(MatchSetup {
+            net_mode: NetMode::Lockstep,
+            host_side: Player::Right,
+            tick_rate_hz: 120,
+            winning_score: 11,
+            paddle_mode: PaddleMode::Smooth,
+            paddle_height: 120.0,
+            tap_distance: 30.0,
+            serve_seed: Some(7),
+            serve_policy: ServePolicy::TowardLoser,
+            ..MatchSetup::from_config(&Config::default())
+        });
+        let bytes = msg.to_bytes().unwrap();
+        let decoded = NetworkMessage::from_bytes(&bytes).unwrap();
+
+        match decoded {
+            NetworkMessage::MatchSetup(setup) => {
+                assert_eq!(setup.net_mode, NetMode::Lockstep);
+                assert_eq!(setup.host_side, Player::Right);
+                assert_eq!(setup.tick_rate_hz, 120);
+                assert_eq!(setup.winning_score, 11);
+                assert_eq!(setup.paddle_mode, PaddleMode::Smooth);
+                assert_eq!(setup.paddle_height, 120.0);
+                assert_eq!(setup.tap_distance, 30.0);
+                assert_eq!(setup.serve_seed, Some(7));
+                assert_eq!(setup.serve_policy, ServePolicy::TowardLoser);
+            }
+            _ => panic!("MatchSetup didn't round-trip correctly, got: {:?}", decoded),
+        }
+    }
+
+    #[test]
+    fn test_compact_ball_sync() {
+        let state = BallState {
+            index: 2,
+            x: 600.4,
+            y: 299.6,
+            vx: -812.5,
+            vy: 90000.0,
+            sequence: 42,
+            timestamp_ms: 1234,
+        };
+        let compact = CompactBallState::from_ball(&state);
+        let expanded = compact.expand();
+        assert_eq!((expanded.x, expanded.y), (600.0, 300.0));
+        assert_eq!(expanded.vx, -813.0);
+        assert_eq!(expanded.vy, i16::MAX as f32);
+        assert_eq!(expanded.sequence, 42);
+        assert_eq!(expanded.index, 2);
+
+        let full = NetworkMessage::BallSync(state).to_bytes().unwrap();
+        let small = NetworkMessage::BallSyncCompact(compact).to_bytes().unwrap();
+        assert!(small.len() < full.len() / 2);
+    }
+
     #[test]
     fn test_all_message_sizes() {
         let messages = vec![
-            ("Input", NetworkMessage::Input(InputAction::LeftPaddleUp)),
+            (
+                "Input",
+                NetworkMessage::Input {
+                    action: InputAction::LeftPaddleUp,
+                    sequence: 0,
+                },
+            ),
             (
                 "Ping",
                 NetworkMessage::Ping {
@@ -114,9 +418,31 @@ mod tests {
                 },
             ),
             ("Heartbeat", NetworkMessage::Heartbeat { sequence: 0 }),
+            (
+                "PaddleSync",
+                NetworkMessage::PaddleSync {
+                    left_y: 10.0,
+                    right_y: 20.0,
+                },
+            ),
+            (
+                "LockstepInput",
+                NetworkMessage::LockstepInput {
+                    first_frame: 1000,
+                    inputs: vec![vec![InputAction::LeftPaddleUp], Vec::new()],
+                    ack: 998,
+                },
+            ),
+            (
+                "Chat",
+                NetworkMessage::Chat {
+                    text: "x".repeat(super::super::chat::MAX_CHAT_LEN),
+                },
+            ),
             (
                 "BallSync",
                 NetworkMessage::BallSync(BallState {
+                    index: 0,
                     x: 1.0,
                     y: 2.0,
                     vx: 3.0,
@@ -0,0 +1,206 @@
+// Spectators watching a hosted match. Each has its own str0m connection on its own UDP
+// socket, set up by the host's signaling listener and driven here between polls of the
+// player connection. Spectators only receive: the host's game stream goes out to all of
+// them, and the only thing read back is a Disconnect.
+
+use anyhow::Result;
+use std::net::UdpSocket;
+use std::time::{Duration, Instant};
+
+use str0m::channel::ChannelId;
+use str0m::net::{Protocol, Receive};
+use str0m::{Event, IceConnectionState, Input, Output, Rtc};
+
+use super::protocol::{CompactBallState, MatchSetup, NetworkMessage, PROTOCOL_VERSION};
+use crate::debug;
+
+/// A spectator whose data channel hasn't opened by now is given up on
+const JOIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Most spectators watching at once (each has its own connection and socket)
+const MAX_SPECTATORS: usize = 8;
+
+#[derive(Default)]
+pub struct Spectators {
+    watchers: Vec<Watcher>,
+    /// Sent to each spectator as its channel opens
+    match_setup: Option<MatchSetup>,
+}
+
+struct Watcher {
+    rtc: Rtc,
+    socket: UdpSocket,
+    channel: Option<ChannelId>,
+    /// When str0m next wants a timeout
+    deadline: Option<Instant>,
+    joined_at: Instant,
+}
+
+impl Spectators {
+    /// Start driving a spectator's connection (its SDP answer has already gone out)
+    pub fn add(&mut self, rtc: Rtc, socket: UdpSocket) {
+        if self.watchers.len() >= MAX_SPECTATORS {
+            debug::log(
+                "SPECTATOR_JOIN",
+                &format!(
+                    "Turning spectator away: {} already watching",
+                    MAX_SPECTATORS
+                ),
+            );
+            return;
+        }
+        if let Err(e) = socket.set_nonblocking(true) {
+            debug::log("SPECTATOR_SOCKET", &format!("Dropping spectator: {}", e));
+            return;
+        }
+        debug::log("SPECTATOR_JOIN", "Spectator connecting");
+        self.watchers.push(Watcher {
+            rtc,
+            socket,
+            channel: None,
+            deadline: None,
+            joined_at: Instant::now(),
+        });
+    }
+
+    /// Remember the match settings for spectators who join later
+    pub fn set_match_setup(&mut self, setup: MatchSetup) {
+        self.match_setup = Some(setup);
+    }
+
+    /// Send a message to every spectator whose channel is open
+    pub fn broadcast(&mut self, msg: &NetworkMessage) {
+        if self.watchers.is_empty() {
+            return;
+        }
+        // Ball syncs go out quantized, as they do to the opponent
+        let bytes = match msg {
+            NetworkMessage::BallSync(state) => {
+                NetworkMessage::BallSyncCompact(CompactBallState::from_ball(state)).to_bytes()
+            }
+            msg => msg.to_bytes(),
+        };
+        if let Ok(bytes) = bytes {
+            for watcher in &mut self.watchers {
+                watcher.send(&bytes);
+            }
+        }
+    }
+
+    /// Feed each spectator's socket and timers to str0m, dropping those who have left
+    pub fn poll(&mut self) {
+        let match_setup = self.match_setup.as_ref();
+        self.watchers
+            .retain_mut(|watcher| match watcher.poll(match_setup) {
+                Ok(true) => true,
+                Ok(false) => {
+                    debug::log("SPECTATOR_LEFT", "Spectator left");
+                    false
+                }
+                Err(e) => {
+                    debug::error("SPECTATOR_ERROR", &format!("Dropping spectator: {}", e));
+                    false
+                }
+            });
+    }
+
+    /// Tell every spectator the match is over and close their connections
+    pub fn close_all(&mut self) {
+        let Ok(bytes) = NetworkMessage::Disconnect.to_bytes() else {
+            return;
+        };
+        for mut watcher in self.watchers.drain(..) {
+            watcher.send(&bytes);
+            watcher.flush();
+            watcher.rtc.disconnect();
+        }
+    }
+}
+
+impl Watcher {
+    fn send(&mut self, bytes: &[u8]) {
+        let Some(cid) = self.channel else {
+            return;
+        };
+        if let Some(mut channel) = self.rtc.channel(cid) {
+            if let Err(e) = channel.write(true, bytes) {
+                debug::error("SPECTATOR_SEND_ERROR", &format!("Send error: {}", e));
+            }
+        }
+    }
+
+    /// Put str0m's queued packets on the wire
+    fn flush(&mut self) {
+        while let Ok(Output::Transmit(transmit)) = self.rtc.poll_output() {
+            let _ = self
+                .socket
+                .send_to(&transmit.contents, transmit.destination);
+        }
+    }
+
+    /// Drive the connection; false once the spectator has gone
+    fn poll(&mut self, match_setup: Option<&MatchSetup>) -> Result<bool> {
+        let mut buf = [0u8; 8192];
+        loop {
+            match self.socket.recv_from(&mut buf) {
+                Ok((n, source)) => {
+                    let receive = Receive {
+                        proto: Protocol::Udp,
+                        source,
+                        destination: self.socket.local_addr()?,
+                        contents: buf[..n].try_into()?,
+                    };
+                    self.rtc
+                        .handle_input(Input::Receive(Instant::now(), receive))?;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let now = Instant::now();
+        if self.deadline.is_none_or(|deadline| now >= deadline) {
+            self.rtc.handle_input(Input::Timeout(now))?;
+        }
+
+        loop {
+            match self.rtc.poll_output()? {
+                Output::Transmit(transmit) => {
+                    let _ = self
+                        .socket
+                        .send_to(&transmit.contents, transmit.destination);
+                }
+                Output::Timeout(deadline) => {
+                    self.deadline = Some(deadline);
+                    break;
+                }
+                Output::Event(Event::ChannelOpen(cid, _)) => {
+                    debug::log("SPECTATOR_WATCHING", "Spectator channel open");
+                    self.channel = Some(cid);
+                    let hello = NetworkMessage::Hello {
+                        protocol_version: PROTOCOL_VERSION,
+                    };
+                    let setup = match_setup.cloned().map(NetworkMessage::MatchSetup);
+                    for msg in std::iter::once(hello).chain(setup) {
+                        if let Ok(bytes) = msg.to_bytes() {
+                            self.send(&bytes);
+                        }
+                    }
+                }
+                Output::Event(Event::ChannelData(data)) => {
+                    if let Ok(NetworkMessage::Disconnect) = NetworkMessage::from_bytes(&data.data) {
+                        return Ok(false);
+                    }
+                }
+                Output::Event(
+                    Event::ChannelClose(_)
+                    | Event::IceConnectionStateChange(IceConnectionState::Disconnected),
+                ) => return Ok(false),
+                Output::Event(_) => {}
+            }
+        }
+
+        let joining = self.channel.is_none() && self.joined_at.elapsed() > JOIN_TIMEOUT;
+        Ok(self.rtc.is_alive() && !joining)
+    }
+}
@@ -0,0 +1,141 @@
+// Connection quality from messages the game already exchanges: gaps in the peer's
+// heartbeat sequence give packet loss, and the spread of ping round trips gives jitter.
+// Kept by the network thread, which reports it as NetworkEvent::ConnectionStats.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Heartbeats and round trips remembered (at the default 2s heartbeat, the last ~40s)
+const WINDOW: usize = 20;
+
+/// Sequence jumps beyond this are taken as a peer restart rather than lost heartbeats
+const MAX_HEARTBEAT_GAP: u32 = WINDOW as u32;
+
+#[derive(Debug, Default)]
+pub struct ConnectionStats {
+    /// Heartbeat sequence expected next from the peer
+    next_heartbeat: Option<u32>,
+    /// Whether each recent heartbeat arrived (false = missed), oldest first
+    heartbeats: VecDeque<bool>,
+    /// Ping awaiting its pong: the timestamp it carries and when it left
+    pending_ping: Option<(u64, Instant)>,
+    /// Recent round-trip times in milliseconds, oldest first
+    rtts_ms: VecDeque<f32>,
+}
+
+impl ConnectionStats {
+    /// Note a heartbeat from the peer, counting any skipped sequence numbers as lost
+    pub fn heartbeat(&mut self, sequence: u32) {
+        if let Some(next) = self.next_heartbeat {
+            let ahead = sequence.wrapping_sub(next);
+            if ahead <= MAX_HEARTBEAT_GAP {
+                for _ in 0..ahead {
+                    push_capped(&mut self.heartbeats, false);
+                }
+            } else if next.wrapping_sub(sequence) <= MAX_HEARTBEAT_GAP {
+                return; // Late or duplicate
+            }
+            // Otherwise the peer restarted its count; carry on from here
+        }
+        push_capped(&mut self.heartbeats, true);
+        self.next_heartbeat = Some(sequence.wrapping_add(1));
+    }
+
+    /// Note a ping going out (a newer ping replaces one whose pong never came)
+    pub fn ping_sent(&mut self, timestamp_ms: u64) {
+        self.pending_ping = Some((timestamp_ms, Instant::now()));
+    }
+
+    /// Note a pong; true if it answered the pending ping and gave a new round trip
+    pub fn pong(&mut self, timestamp_ms: u64) -> bool {
+        match self.pending_ping {
+            Some((sent, at)) if sent == timestamp_ms => {
+                self.pending_ping = None;
+                self.round_trip(at.elapsed().as_secs_f32() * 1000.0);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn round_trip(&mut self, rtt_ms: f32) {
+        push_capped(&mut self.rtts_ms, rtt_ms);
+    }
+
+    /// Share of recent heartbeats that never arrived, in percent
+    pub fn loss_pct(&self) -> f32 {
+        if self.heartbeats.is_empty() {
+            return 0.0;
+        }
+        let missed = self.heartbeats.iter().filter(|arrived| !**arrived).count();
+        missed as f32 * 100.0 / self.heartbeats.len() as f32
+    }
+
+    /// Standard deviation of recent round trips, in milliseconds
+    pub fn jitter_ms(&self) -> f32 {
+        let n = self.rtts_ms.len() as f32;
+        if n < 2.0 {
+            return 0.0;
+        }
+        let mean = self.rtts_ms.iter().sum::<f32>() / n;
+        let variance = self
+            .rtts_ms
+            .iter()
+            .map(|rtt| (rtt - mean).powi(2))
+            .sum::<f32>()
+            / n;
+        variance.sqrt()
+    }
+}
+
+fn push_capped<T>(samples: &mut VecDeque<T>, sample: T) {
+    if samples.len() == WINDOW {
+        samples.pop_front();
+    }
+    samples.push_back(sample);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heartbeat_gaps_count_as_loss() {
+        let mut stats = ConnectionStats::default();
+        assert_eq!(stats.loss_pct(), 0.0);
+
+        for sequence in [0, 1, 3, 4] {
+            stats.heartbeat(sequence);
+        }
+        assert_eq!(stats.loss_pct(), 20.0); // 2 was missed
+
+        // A late heartbeat doesn't count again, and a restarted count isn't a huge loss
+        stats.heartbeat(2);
+        assert_eq!(stats.loss_pct(), 20.0);
+        stats.heartbeat(1000);
+        assert!(stats.loss_pct() < 20.0);
+
+        for sequence in 1001..1001 + WINDOW as u32 {
+            stats.heartbeat(sequence);
+        }
+        assert_eq!(stats.loss_pct(), 0.0);
+    }
+
+    #[test]
+    fn test_jitter_is_round_trip_spread() {
+        let mut stats = ConnectionStats::default();
+        stats.round_trip(40.0);
+        assert_eq!(stats.jitter_ms(), 0.0);
+        for rtt in [60.0, 40.0, 60.0] {
+            stats.round_trip(rtt);
+        }
+        assert!((stats.jitter_ms() - 10.0).abs() < 0.01);
+
+        // Only the pong for the pending ping counts
+        assert!(!stats.pong(5));
+        stats.ping_sent(5);
+        assert!(!stats.pong(4));
+        assert!(stats.pong(5));
+        assert!(!stats.pong(5));
+    }
+}
@@ -12,7 +12,8 @@ use std::sync::{
 use std::thread;
 use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::{self, error::UrlError, Message};
 
 use str0m::change::{SdpAnswer, SdpOffer};
 use str0m::channel::{ChannelConfig, ChannelId, Reliability};
@@ -20,20 +21,62 @@ use str0m::net::{Protocol, Receive};
 use str0m::{Candidate, Event, IceConnectionState, Input, Output, Rtc};
 
 use super::{
-    client::{ConnectionMode, NetworkCommand, NetworkEvent},
-    protocol::NetworkMessage,
+    chat::sanitize_chat,
+    client::{ConnectionMode, ConnectionStage, DisconnectReason, NetworkCommand, NetworkEvent},
+    protocol::{CompactBallState, NetworkMessage, PROTOCOL_VERSION},
+    spectators::Spectators,
+    stats::ConnectionStats,
+    PEER_ID_LEN,
 };
 
+use crate::config::types::IceServerConfig;
 use crate::debug;
 
-// STUN server for NAT traversal (Cloudflare public STUN server)
+/// How often the host pings the signaling server while waiting for spectators
+/// (proxies drop WebSockets that stay silent too long)
+const SIGNALING_KEEPALIVE: Duration = Duration::from_secs(30);
+
+// Default STUN server for NAT traversal (Cloudflare public STUN server)
 const STUN_SERVER: &str = "stun.cloudflare.com:3478";
+const DEFAULT_STUN_PORT: u16 = 3478;
+
+/// How each peer connection gathers candidates and notices a lost peer
+#[derive(Debug, Clone)]
+pub struct IceSettings {
+    /// STUN "host:port" addresses, tried in order
+    pub stun_servers: Vec<String>,
+    /// Roughly how long a silent peer is given before ICE reports it disconnected
+    /// (None = str0m's default, around 8 seconds)
+    pub disconnect_timeout: Option<Duration>,
+}
+
+/// STUN "host:port" addresses to query, in config order (Cloudflare if none configured).
+/// Only STUN URLs are used: the config loader drops TURN ones (str0m has no TURN client).
+pub fn stun_servers(ice_servers: &[IceServerConfig]) -> Vec<String> {
+    let mut servers = Vec::new();
+    for url in ice_servers.iter().flat_map(|server| &server.urls) {
+        if let Some(addr) = url.strip_prefix("stun:") {
+            // Drop any ?transport=... suffix and add the default port if missing
+            let addr = addr.split('?').next().unwrap_or(addr);
+            if addr.contains(':') {
+                servers.push(addr.to_string());
+            } else {
+                servers.push(format!("{}:{}", addr, DEFAULT_STUN_PORT));
+            }
+        }
+    }
+
+    if servers.is_empty() {
+        servers.push(STUN_SERVER.to_string());
+    }
+    servers
+}
 
 /// Generate a short, human-friendly peer ID (4 uppercase letters)
 fn generate_short_peer_id() -> String {
     use rand::Rng;
     let mut rng = rand::thread_rng();
-    (0..4)
+    (0..PEER_ID_LEN)
         .map(|_| {
             let idx = rng.gen_range(0..26);
             (b'A' + idx) as char
@@ -268,6 +311,12 @@ enum SignalingMessage {
         from: String,
         sdp: String,
     },
+    /// An offer to watch the target's match rather than play it
+    Spectate {
+        target: String,
+        from: String,
+        sdp: String,
+    },
     Answer {
         target: String,
         from: String,
@@ -283,6 +332,16 @@ enum SignalingMessage {
     },
 }
 
+type WsStream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// The host's signaling connection, kept open after pairing so spectators can join
+struct SpectatorSignaling {
+    ws_sink: futures::stream::SplitSink<WsStream, Message>,
+    ws_stream: futures::stream::SplitStream<WsStream>,
+    peer_id: String,
+}
+
 /// Initialize and run the WebRTC network in a background thread
 pub fn spawn_network_thread(
     mode: ConnectionMode,
@@ -290,45 +349,75 @@ pub fn spawn_network_thread(
     cmd_rx: mpsc::Receiver<NetworkCommand>,
     connected: Arc<AtomicBool>,
     signaling_server: String,
+    require_tls: bool,
+    ice: IceSettings,
 ) -> std::io::Result<()> {
     thread::spawn(move || {
         std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
             debug::log("THREAD_SPAWN", "Network thread started");
 
-            // Create minimal Tokio runtime only for signaling phase
+            // Create minimal Tokio runtime for signaling (kept by the host for spectators)
             let rt = Runtime::new().expect("Failed to create tokio runtime");
             debug::log("THREAD_RUNTIME", "Tokio runtime created");
 
             let result = rt.block_on(async {
                 debug::log("THREAD_ASYNC_START", "Entering async block");
 
-                match setup_signaling_and_sdp(mode.clone(), &event_tx, &signaling_server).await {
-                    Ok((rtc, udp_socket, channel_id)) => {
+                match setup_signaling_and_sdp(
+                    mode.clone(),
+                    &event_tx,
+                    &signaling_server,
+                    require_tls,
+                    &ice,
+                )
+                .await
+                {
+                    Ok(setup) => {
                         debug::log("SETUP_COMPLETE", "Signaling and SDP setup complete");
-                        Ok((rtc, udp_socket, channel_id))
+                        Ok(setup)
                     }
                     Err(e) => {
-                        debug::log("SETUP_ERROR", &format!("Setup error: {}", e));
+                        debug::error("SETUP_ERROR", &format!("Setup error: {}", e));
                         Err(e)
                     }
                 }
             });
 
-            // Drop Tokio runtime - no longer needed
-            drop(rt);
-
             match result {
-                Ok((rtc, udp_socket, channel_id)) => {
+                Ok((rtc, udp_socket, channel_id, signaling)) => {
+                    // The host goes on answering spectators on the runtime; otherwise
+                    // it is no longer needed
+                    let (spectator_tx, spectator_rx) = mpsc::channel();
+                    let _runtime = match signaling {
+                        Some(signaling) => {
+                            rt.spawn(listen_for_spectators(signaling, ice.clone(), spectator_tx));
+                            Some(rt)
+                        }
+                        None => {
+                            drop(rt);
+                            None
+                        }
+                    };
+
                     debug::log("POLLING_START", "Starting str0m polling loop");
 
-                    if let Err(e) =
-                        run_str0m_loop(rtc, udp_socket, channel_id, event_tx, cmd_rx, connected)
-                    {
-                        debug::log("LOOP_ERROR", &format!("Network loop error: {}", e));
+                    let loop_events = event_tx.clone();
+                    if let Err(e) = run_str0m_loop(
+                        rtc,
+                        udp_socket,
+                        channel_id,
+                        loop_events,
+                        cmd_rx,
+                        spectator_rx,
+                        connected,
+                    ) {
+                        debug::error("LOOP_ERROR", &format!("Network loop error: {}", e));
+                        let _ =
+                            event_tx.send(NetworkEvent::Disconnected(DisconnectReason::Unknown));
                     }
                 }
                 Err(e) => {
-                    debug::log("SETUP_FAILED", &format!("Setup failed: {}", e));
+                    debug::error("SETUP_FAILED", &format!("Setup failed: {}", e));
                     // Send error event to UI so user sees the error message
                     let _ = event_tx.send(NetworkEvent::Error(e.to_string()));
                 }
@@ -337,20 +426,66 @@ pub fn spawn_network_thread(
             debug::log("THREAD_END", "Network thread ending")
         }))
         .unwrap_or_else(|_| {
-            debug::log("THREAD_PANIC", "PANIC in network thread!");
+            debug::error("THREAD_PANIC", "PANIC in network thread!");
+            // Let the game fail the connection rather than wait on a dead thread
+            let msg = if debug::active_path().is_some() {
+                "Network thread crashed (see the debug log)"
+            } else {
+                "Network thread crashed"
+            };
+            let _ = event_tx.send(NetworkEvent::Error(msg.to_string()));
         });
     });
 
     Ok(())
 }
 
+/// Refuse a plain ws:// signaling server when network.require_tls is on
+fn check_signaling_tls(signaling_server: &str, require_tls: bool) -> Result<()> {
+    if require_tls && !signaling_server.starts_with("wss://") {
+        return Err(anyhow!(
+            "Signaling server {} is not encrypted; network.require_tls needs a wss:// URL",
+            signaling_server
+        ));
+    }
+    Ok(())
+}
+
+/// Describe a failed signaling connection, calling out TLS problems such as an
+/// untrusted or mismatched certificate
+fn signaling_connect_error(signaling_server: &str, error: tungstenite::Error) -> anyhow::Error {
+    match error {
+        tungstenite::Error::Tls(e) => anyhow!(
+            "Secure connection to signaling server {} failed: {} (check its certificate)",
+            signaling_server,
+            e
+        ),
+        tungstenite::Error::Url(UrlError::TlsFeatureNotEnabled) => anyhow!(
+            "Signaling server {} needs TLS, which this build lacks",
+            signaling_server
+        ),
+        e => anyhow!(
+            "Could not connect to signaling server {}: {}",
+            signaling_server,
+            e
+        ),
+    }
+}
+
 /// Setup signaling and SDP exchange, returns configured Rtc, UDP socket, and optional channel ID
 /// Client mode returns the channel_id from add_channel(), host mode returns None (channel comes from Event::ChannelOpen)
 async fn setup_signaling_and_sdp(
     mode: ConnectionMode,
     event_tx: &mpsc::Sender<NetworkEvent>,
     signaling_server: &str,
-) -> Result<(Rtc, UdpSocket, Option<ChannelId>)> {
+    require_tls: bool,
+    ice: &IceSettings,
+) -> Result<(
+    Rtc,
+    UdpSocket,
+    Option<ChannelId>,
+    Option<SpectatorSignaling>,
+)> {
     debug::log("SETUP_START", "setup_signaling_and_sdp() started");
 
     // Generate a unique peer ID (4 uppercase letters)
@@ -362,7 +497,10 @@ async fn setup_signaling_and_sdp(
         "SETUP_CONNECT",
         &format!("Connecting to signaling server: {}", signaling_server),
     );
-    let (ws_stream, _) = connect_async(signaling_server).await?;
+    check_signaling_tls(signaling_server, require_tls)?;
+    let (ws_stream, _) = connect_async(signaling_server)
+        .await
+        .map_err(|e| signaling_connect_error(signaling_server, e))?;
     debug::log(
         "SETUP_CONNECTED",
         &format!("Connected to signaling server: {}", signaling_server),
@@ -396,11 +534,166 @@ async fn setup_signaling_and_sdp(
         }
     }
 
+    let _ = event_tx.send(NetworkEvent::ConnectionStage(
+        ConnectionStage::SignalingRegistered,
+    ));
+
+    // The host can share its ID as soon as it's registered (candidate gathering,
+    // including the STUN query, can take a few seconds more)
+    if matches!(mode, ConnectionMode::Listen { .. }) {
+        let _ = event_tx.send(NetworkEvent::LocalPeerIdReady {
+            peer_id: peer_id.clone(),
+        });
+    }
+
+    let (mut rtc, udp_socket) = bind_rtc(ice).await?;
+
+    let _ = event_tx.send(NetworkEvent::ConnectionStage(ConnectionStage::IceGathered));
+
+    // Handle based on connection mode
+    debug::log("SETUP_MODE_SELECT", &format!("Connection mode: {:?}", mode));
+    let channel_id = match mode {
+        ConnectionMode::Listen { .. } => {
+            debug::log(
+                "SETUP_HOST_MODE",
+                &format!("Entering host mode, peer_id: {}", peer_id),
+            );
+
+            handle_host_mode(&mut rtc, &mut ws_sink, &mut ws_stream, &peer_id, event_tx).await?
+        }
+        ConnectionMode::Connect {
+            multiaddr: ref target_peer,
+        }
+        | ConnectionMode::Spectate {
+            peer_id: ref target_peer,
+        } => {
+            let spectate = matches!(mode, ConnectionMode::Spectate { .. });
+            debug::log(
+                "SETUP_CLIENT_MODE",
+                &format!(
+                    "Connecting to peer: {} (spectating: {})",
+                    target_peer, spectate
+                ),
+            );
+
+            handle_client_mode(
+                &mut rtc,
+                &mut ws_sink,
+                &mut ws_stream,
+                &peer_id,
+                event_tx,
+                target_peer.clone(),
+                spectate,
+            )
+            .await?
+        }
+    };
+
+    debug::log("SETUP_COMPLETE", "SDP and ICE exchange complete");
+    let _ = event_tx.send(NetworkEvent::ConnectionStage(
+        ConnectionStage::OfferAnswerExchanged,
+    ));
+
+    // A host allowing spectators stays on the signaling server so they can find it
+    if matches!(
+        mode,
+        ConnectionMode::Listen {
+            allow_spectators: true
+        }
+    ) {
+        let signaling = SpectatorSignaling {
+            ws_sink,
+            ws_stream,
+            peer_id,
+        };
+        return Ok((rtc, udp_socket, channel_id, Some(signaling)));
+    }
+
+    // Properly close WebSocket connection after signaling completes
+    // Give the sink a moment to flush any pending frames
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    drop(ws_stream); // Drop stream first
+    drop(ws_sink); // Then drop sink
+
+    // For client mode, channel_id is Some(id). For host mode, it's None.
+    Ok((rtc, udp_socket, channel_id, None))
+}
+
+/// Host: after pairing, answer offers to watch the match until signaling closes. Each
+/// spectator gets its own Rtc and socket, handed to the polling loop.
+async fn listen_for_spectators(
+    mut signaling: SpectatorSignaling,
+    ice: IceSettings,
+    spectator_tx: mpsc::Sender<(Rtc, UdpSocket)>,
+) {
+    let mut keepalive = tokio::time::interval(SIGNALING_KEEPALIVE);
+    loop {
+        tokio::select! {
+            msg = signaling.ws_stream.next() => {
+                let text = match msg {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(_)) => continue,
+                    _ => {
+                        debug::log("SPECTATOR_SIGNALING_CLOSED", "No more spectators can join");
+                        return;
+                    }
+                };
+                let Ok(SignalingMessage::Spectate { from, sdp, .. }) = serde_json::from_str(&text)
+                else {
+                    continue;
+                };
+                debug::log("SPECTATOR_OFFER", &format!("{} wants to watch", from));
+
+                let (rtc, udp_socket, answer_sdp) = match accept_spectator(&sdp, &ice).await {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        debug::error("SPECTATOR_OFFER_ERROR", &format!("Not answering {}: {}", from, e));
+                        continue;
+                    }
+                };
+                let answer = SignalingMessage::Answer {
+                    target: from,
+                    from: signaling.peer_id.clone(),
+                    sdp: answer_sdp,
+                };
+                let Ok(json) = serde_json::to_string(&answer) else {
+                    continue;
+                };
+                if signaling.ws_sink.send(Message::Text(json)).await.is_err()
+                    || spectator_tx.send((rtc, udp_socket)).is_err()
+                {
+                    return; // Signaling or the polling loop has gone
+                }
+            }
+            _ = keepalive.tick() => {
+                if signaling.ws_sink.send(Message::Ping(Vec::new())).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Answer a spectator's offer on a connection of its own
+async fn accept_spectator(offer_sdp: &str, ice: &IceSettings) -> Result<(Rtc, UdpSocket, String)> {
+    let (mut rtc, udp_socket) = bind_rtc(ice).await?;
+    let offer = SdpOffer::from_sdp_string(offer_sdp)?;
+    let answer = rtc.sdp_api().accept_offer(offer)?;
+    Ok((rtc, udp_socket, answer.to_sdp_string()))
+}
+
+/// Create an Rtc and a UDP socket on the preferred local interface, with host and
+/// (when a STUN server answers) server reflexive candidates
+async fn bind_rtc(ice: &IceSettings) -> Result<(Rtc, UdpSocket)> {
     // Create str0m Rtc instance
     debug::log("SETUP_WEBRTC", "Creating str0m Rtc instance");
-    let mut rtc = Rtc::builder()
-        .set_rtp_mode(false) // Data channels only, no RTP media
-        .build();
+    let mut config = Rtc::builder().set_rtp_mode(false); // Data channels only, no RTP media
+    if let Some(timeout) = ice.disconnect_timeout {
+        // A working pair is re-checked every max RTO; once checks go unanswered they
+        // repeat at ever shorter intervals, so the pair fails after about two max RTOs
+        config.set_max_stun_rto(timeout / 2);
+    }
+    let mut rtc = config.build();
     debug::log("SETUP_WEBRTC_CREATED", "Rtc instance created");
 
     // Discover local network IP FIRST
@@ -445,14 +738,22 @@ async fn setup_signaling_and_sdp(
         &format!("Socket state before STUN: {}", socket_before_stun),
     );
 
-    debug::log(
-        "STUN_QUERY_START",
-        &format!("Querying STUN server: {}", STUN_SERVER),
-    );
-
-    // If STUN fails on the primary interface (e.g., WiFi while VPN is active),
-    // and we have a VPN interface, try STUN through VPN interface instead
-    let stun_result = query_stun_server(&udp_socket, STUN_SERVER).await;
+    // Try each configured STUN server in order until one answers
+    let mut stun_result = Err(anyhow!("No STUN servers configured"));
+    for stun_server in &ice.stun_servers {
+        debug::log(
+            "STUN_QUERY_START",
+            &format!("Querying STUN server: {}", stun_server),
+        );
+        stun_result = query_stun_server(&udp_socket, stun_server).await;
+        match &stun_result {
+            Ok(_) => break,
+            Err(e) => debug::log(
+                "STUN_SERVER_FAILED",
+                &format!("STUN server {} failed: {}", stun_server, e),
+            ),
+        }
+    }
 
     match stun_result {
         Ok(public_addr) => {
@@ -503,13 +804,13 @@ async fn setup_signaling_and_sdp(
             );
             match Candidate::server_reflexive(public_addr, host_addr, "udp") {
                 Ok(srflx_cand) => {
-                    if let Some(_) = rtc.add_local_candidate(srflx_cand) {
+                    if rtc.add_local_candidate(srflx_cand).is_some() {
                         debug::log(
                             "SETUP_SRFLX_CANDIDATE",
                             &format!("Server reflexive candidate added: {}", public_addr),
                         );
                     } else {
-                        debug::log("STUN_ADD_FAILED", "Failed to add srflx candidate to rtc");
+                        debug::error("STUN_ADD_FAILED", "Failed to add srflx candidate to rtc");
                     }
                 }
                 Err(e) => {
@@ -529,51 +830,7 @@ async fn setup_signaling_and_sdp(
         }
     }
 
-    // Handle based on connection mode
-    debug::log("SETUP_MODE_SELECT", &format!("Connection mode: {:?}", mode));
-    let channel_id = match mode {
-        ConnectionMode::Listen { .. } => {
-            debug::log(
-                "SETUP_HOST_MODE",
-                &format!("Entering host mode, peer_id: {}", peer_id),
-            );
-
-            // Send local peer ID to display in TUI
-            let _ = event_tx.send(NetworkEvent::LocalPeerIdReady {
-                peer_id: peer_id.clone(),
-            });
-
-            handle_host_mode(&mut rtc, &mut ws_sink, &mut ws_stream, &peer_id, event_tx).await?
-        }
-        ConnectionMode::Connect { multiaddr } => {
-            let target_peer = multiaddr;
-            debug::log(
-                "SETUP_CLIENT_MODE",
-                &format!("Connecting to peer: {}", target_peer),
-            );
-
-            handle_client_mode(
-                &mut rtc,
-                &mut ws_sink,
-                &mut ws_stream,
-                &peer_id,
-                event_tx,
-                target_peer.clone(),
-            )
-            .await?
-        }
-    };
-
-    debug::log("SETUP_COMPLETE", "SDP and ICE exchange complete");
-
-    // Properly close WebSocket connection after signaling completes
-    // Give the sink a moment to flush any pending frames
-    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-    drop(ws_stream); // Drop stream first
-    drop(ws_sink); // Then drop sink
-
-    // For client mode, channel_id is Some(id). For host mode, it's None.
-    Ok((rtc, udp_socket, channel_id.into()))
+    Ok((rtc, udp_socket))
 }
 
 /// Host mode: wait for offer from client
@@ -600,22 +857,19 @@ async fn handle_host_mode(
         if let Some(Ok(Message::Text(text))) = ws_stream.next().await {
             let msg: SignalingMessage = serde_json::from_str(&text)?;
 
-            match msg {
-                SignalingMessage::Offer { from, sdp, .. } => {
-                    debug::log("HOST_OFFER", &format!("Received offer from {}", from));
-                    // Debug: log full received offer SDP
-                    let offer_candidate_count = sdp
-                        .lines()
-                        .filter(|l| l.starts_with("a=candidate:"))
-                        .count();
-                    debug::log("SDP_OFFER_FULL", &sdp);
-                    debug::log(
-                        "SDP_OFFER_CANDIDATES",
-                        &format!("Offer has {} ICE candidates", offer_candidate_count),
-                    );
-                    break (sdp, from); // Capture the remote peer ID for the answer
-                }
-                _ => {}
+            if let SignalingMessage::Offer { from, sdp, .. } = msg {
+                debug::log("HOST_OFFER", &format!("Received offer from {}", from));
+                // Debug: log full received offer SDP
+                let offer_candidate_count = sdp
+                    .lines()
+                    .filter(|l| l.starts_with("a=candidate:"))
+                    .count();
+                debug::log("SDP_OFFER_FULL", &sdp);
+                debug::log(
+                    "SDP_OFFER_CANDIDATES",
+                    &format!("Offer has {} ICE candidates", offer_candidate_count),
+                );
+                break (sdp, from); // Capture the remote peer ID for the answer
             }
         } else {
             return Err(anyhow!("WebSocket closed while waiting for offer"));
@@ -661,7 +915,7 @@ async fn handle_host_mode(
     Ok(None)
 }
 
-/// Client mode: create offer and send to host
+/// Client mode: create offer and send to host (spectating, the offer asks to watch its match)
 async fn handle_client_mode(
     rtc: &mut Rtc,
     ws_sink: &mut futures::stream::SplitSink<
@@ -678,6 +932,7 @@ async fn handle_client_mode(
     peer_id: &str,
     _event_tx: &mpsc::Sender<NetworkEvent>,
     target_peer: String,
+    spectate: bool,
 ) -> Result<Option<ChannelId>> {
     debug::log("CLIENT_MODE", "handle_client_mode() started");
 
@@ -711,11 +966,12 @@ async fn handle_client_mode(
         &format!("Offer has {} ICE candidates", candidate_count),
     );
 
-    // Send offer to target
-    let offer_msg = SignalingMessage::Offer {
-        target: target_peer.clone(),
-        from: peer_id.to_string(),
-        sdp: offer_sdp,
+    // Send offer to target (a spectator's offer joins the match the target is hosting)
+    let (target, from, sdp) = (target_peer.clone(), peer_id.to_string(), offer_sdp);
+    let offer_msg = if spectate {
+        SignalingMessage::Spectate { target, from, sdp }
+    } else {
+        SignalingMessage::Offer { target, from, sdp }
     };
     ws_sink
         .send(Message::Text(serde_json::to_string(&offer_msg)?))
@@ -746,7 +1002,7 @@ async fn handle_client_mode(
                     break sdp;
                 }
                 SignalingMessage::Error { message } => {
-                    debug::log("CLIENT_ERROR", &format!("Server error: {}", message));
+                    debug::error("CLIENT_ERROR", &format!("Server error: {}", message));
                     return Err(anyhow!("Connection failed: {}", message));
                 }
                 _ => {}
@@ -788,6 +1044,7 @@ fn run_str0m_loop(
     initial_channel_id: Option<ChannelId>,
     event_tx: mpsc::Sender<NetworkEvent>,
     cmd_rx: mpsc::Receiver<NetworkCommand>,
+    spectator_rx: mpsc::Receiver<(Rtc, UdpSocket)>,
     connected: Arc<AtomicBool>,
 ) -> Result<()> {
     debug::log("POLLING_LOOP", "Starting main polling loop");
@@ -800,6 +1057,18 @@ fn run_str0m_loop(
     // str0m needs to be notified at its requested deadline for ICE keepalives
     let mut str0m_deadline: Option<Instant> = None;
 
+    // Set while ICE is disconnected, so recovery can be reported
+    let mut ice_lost = false;
+
+    // Loss and jitter, reported to the game as they change
+    let mut stats = ConnectionStats::default();
+
+    // Numbers outgoing inputs so the peer can drop ones that arrive late
+    let mut input_sequence: u32 = 0;
+
+    // Host: connections of anyone watching the match
+    let mut spectators = Spectators::default();
+
     loop {
         // Phase 1: Poll str0m for outputs
         loop {
@@ -823,7 +1092,7 @@ fn run_str0m_loop(
                             );
                         }
                         Err(e) => {
-                            debug::log("UDP_SEND_ERROR", &format!("Failed to send: {}", e));
+                            debug::error("UDP_SEND_ERROR", &format!("Failed to send: {}", e));
                         }
                     }
                 }
@@ -839,8 +1108,22 @@ fn run_str0m_loop(
                     break; // Exit poll loop to wait for input
                 }
                 Output::Event(event) => {
+                    let channel_opened = matches!(event, Event::ChannelOpen(..));
+
                     // Process str0m event
-                    handle_str0m_event(event, &event_tx, &connected, &mut active_channel_id)?;
+                    handle_str0m_event(
+                        event,
+                        &event_tx,
+                        &connected,
+                        &mut active_channel_id,
+                        &mut ice_lost,
+                        &mut stats,
+                    )?;
+
+                    // Introduce ourselves before any game traffic
+                    if channel_opened {
+                        send_hello(&mut rtc, active_channel_id);
+                    }
                 }
             }
         }
@@ -883,17 +1166,44 @@ fn run_str0m_loop(
                 }
             }
             Err(e) => {
-                debug::log("UDP_ERROR", &format!("Socket error: {}", e));
+                debug::error("UDP_ERROR", &format!("Socket error: {}", e));
                 return Err(e.into());
             }
         }
 
+        // Spectators are driven between polls of the player connection
+        while let Ok((rtc, socket)) = spectator_rx.try_recv() {
+            spectators.add(rtc, socket);
+        }
+        spectators.poll();
+
         // Phase 3: Process commands from game loop (non-blocking)
-        while let Ok(cmd) = cmd_rx.try_recv() {
+        loop {
+            let cmd = match cmd_rx.try_recv() {
+                Ok(cmd) => cmd,
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    // The game loop is gone without saying goodbye; close anyway
+                    debug::log("LOOP_EXIT", "Game loop dropped its network client");
+                    spectators.close_all();
+                    close_connection(&mut rtc, &udp_socket, active_channel_id);
+                    return Ok(());
+                }
+            };
             match cmd {
+                NetworkCommand::Disconnect => {
+                    debug::log("LOOP_EXIT", "Disconnect requested by game loop");
+                    spectators.close_all();
+                    close_connection(&mut rtc, &udp_socket, active_channel_id);
+                    return Ok(());
+                }
                 NetworkCommand::SendInput(action) => {
                     if let Some(cid) = active_channel_id {
-                        let msg = NetworkMessage::Input(action);
+                        let msg = NetworkMessage::Input {
+                            action,
+                            sequence: input_sequence,
+                        };
+                        input_sequence = input_sequence.wrapping_add(1);
                         if let Ok(bytes) = msg.to_bytes() {
                             if let Some(mut channel) = rtc.channel(cid) {
                                 match channel.write(true, &bytes) {
@@ -914,12 +1224,28 @@ fn run_str0m_loop(
                         }
                     }
                 }
+                NetworkCommand::SendSpectators(msg) => spectators.broadcast(&msg),
                 NetworkCommand::SendMessage(msg) => {
+                    // Spectators who join later get the same match settings
+                    if let NetworkMessage::MatchSetup(setup) = &msg {
+                        spectators.set_match_setup(setup.clone());
+                    }
+                    // Ball syncs go out quantized
+                    let msg = match msg {
+                        NetworkMessage::BallSync(state) => {
+                            NetworkMessage::BallSyncCompact(CompactBallState::from_ball(&state))
+                        }
+                        msg => msg,
+                    };
+                    if let NetworkMessage::Ping { timestamp_ms } = msg {
+                        stats.ping_sent(timestamp_ms);
+                    }
+
                     if let Some(cid) = active_channel_id {
                         if let Ok(bytes) = msg.to_bytes() {
                             if let Some(mut channel) = rtc.channel(cid) {
                                 // Log sequence for BallSync to track delivery
-                                if let NetworkMessage::BallSync(ref state) = msg {
+                                if let NetworkMessage::BallSyncCompact(ref state) = msg {
                                     debug::log(
                                         "SEND_BALLSYNC",
                                         &format!(
@@ -932,7 +1258,7 @@ fn run_str0m_loop(
 
                                 match channel.write(true, &bytes) {
                                     Ok(_) => {
-                                        if let NetworkMessage::BallSync(ref state) = msg {
+                                        if let NetworkMessage::BallSyncCompact(ref state) = msg {
                                             debug::log(
                                                 "SEND_BALLSYNC_OK",
                                                 &format!("channel.write OK seq={}", state.sequence),
@@ -955,10 +1281,58 @@ fn run_str0m_loop(
                         }
                     }
                 }
+            }
+        }
+    }
+}
 
+fn send_connection_stats(event_tx: &mpsc::Sender<NetworkEvent>, stats: &ConnectionStats) {
+    let _ = event_tx.send(NetworkEvent::ConnectionStats {
+        loss_pct: stats.loss_pct(),
+        jitter_ms: stats.jitter_ms(),
+    });
+}
+
+/// Send our protocol version to the peer
+fn send_hello(rtc: &mut Rtc, channel_id: Option<ChannelId>) {
+    let msg = NetworkMessage::Hello {
+        protocol_version: PROTOCOL_VERSION,
+    };
+    let (Some(cid), Ok(bytes)) = (channel_id, msg.to_bytes()) else {
+        return;
+    };
+    if let Some(mut channel) = rtc.channel(cid) {
+        match channel.write(true, &bytes) {
+            Ok(_) => debug::log("SEND_HELLO", &format!("Protocol v{}", PROTOCOL_VERSION)),
+            Err(e) => debug::error("SEND_HELLO_ERROR", &format!("Send error: {}", e)),
+        }
+    }
+}
+
+/// Send Disconnect to the peer, flush it onto the wire, then close the peer connection
+fn close_connection(rtc: &mut Rtc, udp_socket: &UdpSocket, channel_id: Option<ChannelId>) {
+    if let (Some(cid), Ok(bytes)) = (channel_id, NetworkMessage::Disconnect.to_bytes()) {
+        if let Some(mut channel) = rtc.channel(cid) {
+            match channel.write(true, &bytes) {
+                Ok(_) => debug::log("SEND_DISCONNECT", "Told peer we're leaving"),
+                Err(e) => debug::error("SEND_DISCONNECT_ERROR", &format!("Send error: {}", e)),
             }
         }
     }
+
+    // Drain str0m's pending packets so the message leaves before we stop polling
+    while let Ok(output) = rtc.poll_output() {
+        match output {
+            Output::Transmit(transmit) => {
+                let _ = udp_socket.send_to(&transmit.contents, transmit.destination);
+            }
+            Output::Timeout(_) => break,
+            Output::Event(_) => {}
+        }
+    }
+
+    rtc.disconnect();
+    debug::log("CONNECTION_CLOSED", "Peer connection closed");
 }
 
 /// Handle events from str0m
@@ -967,6 +1341,8 @@ fn handle_str0m_event(
     event_tx: &mpsc::Sender<NetworkEvent>,
     connected: &Arc<AtomicBool>,
     active_channel_id: &mut Option<ChannelId>,
+    ice_lost: &mut bool,
+    stats: &mut ConnectionStats,
 ) -> Result<()> {
     match event {
         Event::Connected => {
@@ -987,23 +1363,34 @@ fn handle_str0m_event(
                 IceConnectionState::Checking => {
                     debug::log("ICE_STATE_CHECKING", "ICE checking candidate pairs");
                 }
-                IceConnectionState::Connected => {
-                    debug::log("ICE_STATE_CONNECTED", "ICE found working pair");
-                    // Note: Event::Connected is the actual connection signal, not this
-                }
-                IceConnectionState::Completed => {
-                    debug::log("ICE_STATE_COMPLETED", "ICE completed all checks");
+                IceConnectionState::Connected | IceConnectionState::Completed => {
+                    debug::log("ICE_STATE_CONNECTED", &format!("ICE working: {:?}", state));
+                    // Note: Event::Connected is the initial connection signal, not this,
+                    // but a working pair after a drop means the connection recovered
+                    if *ice_lost {
+                        *ice_lost = false;
+                        connected.store(true, Ordering::Relaxed);
+                        let _ = event_tx.send(NetworkEvent::Reconnected);
+                    } else {
+                        let _ = event_tx
+                            .send(NetworkEvent::ConnectionStage(ConnectionStage::IceConnected));
+                    }
                 }
                 IceConnectionState::Disconnected => {
+                    // ICE keeps checking after a drop, so let the game wait for recovery
                     debug::log("ICE_STATE_DISCONNECTED", "ICE connection lost");
                     connected.store(false, Ordering::Relaxed);
-                    let _ = event_tx.send(NetworkEvent::Disconnected);
+                    *ice_lost = true;
+                    let _ = event_tx.send(NetworkEvent::Reconnecting);
                 }
             }
         }
         Event::ChannelOpen(cid, label) => {
             debug::log("CHANNEL_OPEN", &format!("Data channel opened: {}", label));
             *active_channel_id = Some(cid);
+            let _ = event_tx.send(NetworkEvent::ConnectionStage(
+                ConnectionStage::DataChannelOpen,
+            ));
             let _ = event_tx.send(NetworkEvent::DataChannelOpened);
         }
         Event::ChannelData(channel_data) => {
@@ -1014,9 +1401,9 @@ fn handle_str0m_event(
             );
             if let Ok(msg) = NetworkMessage::from_bytes(&channel_data.data) {
                 match msg {
-                    NetworkMessage::Input(action) => {
-                        debug::log("RECV_INPUT", &format!("Input: {:?}", action));
-                        let _ = event_tx.send(NetworkEvent::ReceivedInput(action));
+                    NetworkMessage::Input { action, sequence } => {
+                        debug::log("RECV_INPUT", &format!("Input #{}: {:?}", sequence, action));
+                        let _ = event_tx.send(NetworkEvent::ReceivedInput { action, sequence });
                     }
                     NetworkMessage::BallSync(state) => {
                         debug::log(
@@ -1028,19 +1415,39 @@ fn handle_str0m_event(
                         );
                         let _ = event_tx.send(NetworkEvent::ReceivedBallState(state));
                     }
+                    NetworkMessage::BallSyncCompact(compact) => {
+                        let state = compact.expand();
+                        debug::log(
+                            "RECV_BALLSYNC",
+                            &format!(
+                                "seq={}, pos=({}, {})",
+                                compact.sequence, compact.x, compact.y
+                            ),
+                        );
+                        let _ = event_tx.send(NetworkEvent::ReceivedBallState(state));
+                    }
                     NetworkMessage::ScoreSync {
                         left,
                         right,
                         game_over,
+                        left_games,
+                        right_games,
+                        stats,
                     } => {
                         debug::log(
                             "RECV_SCORE",
-                            &format!("Score: {} - {}, game_over={}", left, right, game_over),
+                            &format!(
+                                "Score: {} - {}, game_over={}, games: {} - {}",
+                                left, right, game_over, left_games, right_games
+                            ),
                         );
                         let _ = event_tx.send(NetworkEvent::ReceivedScore {
                             left,
                             right,
                             game_over,
+                            left_games,
+                            right_games,
+                            stats,
                         });
                     }
                     NetworkMessage::Ping { timestamp_ms } => {
@@ -1049,10 +1456,15 @@ fn handle_str0m_event(
                     }
                     NetworkMessage::Pong { timestamp_ms } => {
                         debug::log("RECV_PONG", &format!("Pong: {}", timestamp_ms));
+                        if stats.pong(timestamp_ms) {
+                            send_connection_stats(event_tx, stats);
+                        }
                         let _ = event_tx.send(NetworkEvent::ReceivedPong { timestamp_ms });
                     }
                     NetworkMessage::Heartbeat { sequence } => {
                         debug::log("HEARTBEAT_RECV", &format!("Heartbeat #{}", sequence));
+                        stats.heartbeat(sequence);
+                        send_connection_stats(event_tx, stats);
                     }
                     NetworkMessage::RematchRequest => {
                         debug::log("RECV_REMATCH_REQUEST", "Opponent wants to rematch");
@@ -1066,13 +1478,69 @@ fn handle_str0m_event(
                         debug::log("RECV_QUIT_REQUEST", "Opponent wants to quit");
                         let _ = event_tx.send(NetworkEvent::ReceivedQuitRequest);
                     }
+                    NetworkMessage::PaddleSync { left_y, right_y } => {
+                        let _ = event_tx.send(NetworkEvent::ReceivedPaddles { left_y, right_y });
+                    }
+                    NetworkMessage::MatchSetup(setup) => {
+                        debug::log("RECV_MATCH_SETUP", &format!("Match setup: {:?}", setup));
+                        let _ = event_tx.send(NetworkEvent::ReceivedMatchSetup(setup));
+                    }
+                    NetworkMessage::Ready => {
+                        debug::log("RECV_READY", "Opponent is ready");
+                        let _ = event_tx.send(NetworkEvent::PeerReady);
+                    }
                     NetworkMessage::Disconnect => {
-                        let _ = event_tx.send(NetworkEvent::Disconnected);
+                        debug::log("RECV_DISCONNECT", "Peer closed the connection");
+                        let _ =
+                            event_tx.send(NetworkEvent::Disconnected(DisconnectReason::PeerQuit));
+                    }
+                    NetworkMessage::Hello { protocol_version } => {
+                        debug::log(
+                            "RECV_HELLO",
+                            &format!("Peer protocol v{}", protocol_version),
+                        );
+                        let event = if protocol_version == PROTOCOL_VERSION {
+                            NetworkEvent::PeerHello { protocol_version }
+                        } else {
+                            NetworkEvent::Error(format!(
+                                "Incompatible version: opponent uses protocol v{}, you use v{}",
+                                protocol_version, PROTOCOL_VERSION
+                            ))
+                        };
+                        let _ = event_tx.send(event);
+                    }
+                    NetworkMessage::LockstepInput {
+                        first_frame,
+                        inputs,
+                        ack,
+                    } => {
+                        let _ = event_tx.send(NetworkEvent::ReceivedLockstepInput {
+                            first_frame,
+                            inputs,
+                            ack,
+                        });
+                    }
+                    NetworkMessage::PowerUpEvent {
+                        powerups,
+                        left_paddle_height,
+                        right_paddle_height,
+                    } => {
+                        let _ = event_tx.send(NetworkEvent::ReceivedPowerUps {
+                            powerups,
+                            left_paddle_height,
+                            right_paddle_height,
+                        });
+                    }
+                    NetworkMessage::Chat { text } => {
+                        debug::log("RECV_CHAT", &format!("Chat: {} bytes", text.len()));
+                        let text = sanitize_chat(&text);
+                        if !text.is_empty() {
+                            let _ = event_tx.send(NetworkEvent::ReceivedChat(text));
+                        }
                     }
-                    _ => {}
                 }
             } else {
-                debug::log("DECODE_ERROR", &format!("Failed to decode message"));
+                debug::error("DECODE_ERROR", "Failed to decode message");
             }
         }
         _ => {
@@ -1084,3 +1552,49 @@ fn handle_str0m_event(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stun_servers_from_config() {
+        assert_eq!(stun_servers(&[]), vec![STUN_SERVER.to_string()]);
+
+        let servers = vec![
+            IceServerConfig {
+                urls: vec!["turn:turn.example.com:3478".to_string()],
+                username: Some("user".to_string()),
+                credential: Some("secret".to_string()),
+            },
+            IceServerConfig {
+                urls: vec![
+                    "stun:stun.example.com".to_string(),
+                    "stun:10.0.0.1:19302?transport=udp".to_string(),
+                ],
+                ..IceServerConfig::default()
+            },
+        ];
+        assert_eq!(
+            stun_servers(&servers),
+            vec!["stun.example.com:3478", "10.0.0.1:19302"]
+        );
+
+        // TURN-only configs still get the default STUN server
+        assert_eq!(stun_servers(&servers[..1]), vec![STUN_SERVER.to_string()]);
+    }
+
+    #[test]
+    fn test_signaling_tls_errors() {
+        assert!(check_signaling_tls("ws://localhost:8080", false).is_ok());
+        assert!(check_signaling_tls("wss://signal.example.com", true).is_ok());
+        let refused = check_signaling_tls("ws://signal.example.com", true).unwrap_err();
+        assert!(refused.to_string().contains("require_tls"));
+
+        let error = signaling_connect_error(
+            "wss://signal.example.com",
+            tungstenite::Error::Url(UrlError::TlsFeatureNotEnabled),
+        );
+        assert!(error.to_string().contains("wss://signal.example.com"));
+    }
+}
@@ -0,0 +1,144 @@
+// Peers recently joined as a client, offered again in the join dialog
+// Stored as JSON next to config.toml, most recent first
+
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::loader::get_config_path;
+use crate::json_store;
+
+/// How many peers are remembered
+const MAX_RECENT_PEERS: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RecentPeer {
+    pub peer_id: String,
+    pub last_connected: u64, // Unix seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>, // Optional label, set by editing recent_peers.json
+}
+
+impl RecentPeer {
+    /// Rough time since the last connection, e.g. "5m ago"
+    pub fn age_label(&self, now: u64) -> String {
+        let secs = now.saturating_sub(self.last_connected);
+        match secs {
+            0..60 => "just now".to_string(),
+            60..3600 => format!("{}m ago", secs / 60),
+            3600..86400 => format!("{}h ago", secs / 3600),
+            _ => format!("{}d ago", secs / 86400),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RecentPeers {
+    pub peers: Vec<RecentPeer>,
+}
+
+impl RecentPeers {
+    /// Move `peer_id` to the front (keeping its nickname) and forget the oldest beyond the limit
+    pub fn record(&mut self, peer_id: &str, now: u64) {
+        let nickname = self
+            .peers
+            .iter()
+            .position(|peer| peer.peer_id == peer_id)
+            .and_then(|i| self.peers.remove(i).nickname);
+
+        self.peers.insert(
+            0,
+            RecentPeer {
+                peer_id: peer_id.to_string(),
+                last_connected: now,
+                nickname,
+            },
+        );
+        self.peers.truncate(MAX_RECENT_PEERS);
+    }
+}
+
+/// Current time in Unix seconds
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Get the path to the recent peers file (alongside config.toml)
+pub fn get_recent_peers_path() -> PathBuf {
+    get_config_path().with_file_name("recent_peers.json")
+}
+
+/// Load recent peers, starting empty if missing or unreadable
+pub fn load() -> RecentPeers {
+    json_store::load(&get_recent_peers_path())
+}
+
+/// Remember a successful connection to `peer_id`
+pub fn record_connection(peer_id: &str) -> Result<(), io::Error> {
+    let path = get_recent_peers_path();
+    let mut recent: RecentPeers = json_store::load(&path);
+    recent.record(peer_id, unix_now());
+    json_store::save(&recent, &path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_dedupes_and_trims() {
+        let mut recent = RecentPeers::default();
+        for (i, id) in ["AAAA", "BBBB", "CCCC"].iter().enumerate() {
+            recent.record(id, i as u64);
+        }
+        recent.peers[2].nickname = Some("Sam".to_string());
+
+        // Reconnecting moves the peer to the front and keeps its nickname
+        recent.record("AAAA", 10);
+        let ids: Vec<_> = recent.peers.iter().map(|p| p.peer_id.as_str()).collect();
+        assert_eq!(ids, ["AAAA", "CCCC", "BBBB"]);
+        assert_eq!(recent.peers[0].nickname.as_deref(), Some("Sam"));
+        assert_eq!(recent.peers[0].last_connected, 10);
+
+        for i in 0..10 {
+            recent.record(&format!("P{}", i), 20 + i);
+        }
+        assert_eq!(recent.peers.len(), MAX_RECENT_PEERS);
+        assert_eq!(recent.peers[0].peer_id, "P9");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "p2pong-recent-peers-test-{}.json",
+            std::process::id()
+        ));
+        assert_eq!(
+            json_store::load::<RecentPeers>(&path),
+            RecentPeers::default()
+        );
+
+        let mut recent = RecentPeers::default();
+        recent.record("ABCD", 1_700_000_000);
+        json_store::save(&recent, &path).unwrap();
+        assert_eq!(json_store::load::<RecentPeers>(&path), recent);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_age_label() {
+        let peer = RecentPeer {
+            peer_id: "ABCD".to_string(),
+            last_connected: 1000,
+            nickname: None,
+        };
+        assert_eq!(peer.age_label(1030), "just now");
+        assert_eq!(peer.age_label(1000 + 5 * 60), "5m ago");
+        assert_eq!(peer.age_label(1000 + 2 * 86400), "2d ago");
+    }
+}
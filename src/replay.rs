@@ -0,0 +1,586 @@
+// Game recordings for `--replay <file>`
+// Physics is deterministic given the inputs (serves use physics.serve_seed), so a replay
+// is just the physics settings plus every input applied, keyed by physics tick.
+// File layout: "P2PR", u16 format version (little endian), then the bincode body.
+// Highlights (a networked match's decisive point, which the inputs alone can't reproduce
+// since the host's physics decides it) store drawn frames instead, under "P2PH".
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::loader::get_config_path;
+use crate::config::PhysicsConfig;
+use crate::debug;
+use crate::game::{self, GameState, InputAction, Player};
+
+const MAGIC: &[u8; 4] = b"P2PR";
+const HIGHLIGHT_MAGIC: &[u8; 4] = b"P2PH";
+
+/// Current replay format; files from newer versions are refused rather than misread
+pub const REPLAY_VERSION: u16 = 1;
+
+/// Current highlight format
+pub const HIGHLIGHT_VERSION: u16 = 1;
+
+/// Longest stretch of a point a highlight keeps; a longer point loses its start
+const MAX_HIGHLIGHT_SECS: u64 = 30;
+
+/// A recorded game
+#[derive(Debug, Clone)]
+pub struct Replay {
+    pub physics: PhysicsConfig,
+    /// Paddle height multipliers (left, right) the game was played with
+    pub paddle_scales: (f32, f32),
+    /// Inputs in order, each applied just before the physics tick it's keyed by
+    pub inputs: Vec<(u64, InputAction)>,
+    /// Physics ticks run while recording
+    pub ticks: u64,
+    /// Side of the human player in a solo game (None for two-player games)
+    pub player_side: Option<Player>,
+    /// Goals were followed by slow motion, which holds back the next serve
+    pub goal_slow_motion: bool,
+}
+
+/// Game settings as stored in a replay: the physics plus the paddle handicaps
+#[derive(Serialize, Deserialize)]
+struct ReplaySetup {
+    #[serde(flatten)]
+    physics: PhysicsConfig,
+    #[serde(default = "unscaled_paddles")]
+    paddle_scales: (f32, f32),
+    #[serde(default)]
+    player_side: Option<Player>,
+    #[serde(default)]
+    goal_slow_motion: bool,
+}
+
+fn unscaled_paddles() -> (f32, f32) {
+    (1.0, 1.0)
+}
+
+#[derive(Serialize, Deserialize)]
+struct ReplayBody {
+    // Stored as JSON so settings added later fall back to their defaults
+    physics: String,
+    inputs: Vec<(u64, InputAction)>,
+    ticks: u64,
+}
+
+impl Replay {
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        let setup = ReplaySetup {
+            physics: self.physics.clone(),
+            paddle_scales: self.paddle_scales,
+            player_side: self.player_side,
+            goal_slow_motion: self.goal_slow_motion,
+        };
+        let body = ReplayBody {
+            physics: serde_json::to_string(&setup).map_err(io::Error::other)?,
+            inputs: self.inputs.clone(),
+            ticks: self.ticks,
+        };
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&REPLAY_VERSION.to_le_bytes());
+        bytes.extend(bincode::serialize(&body).map_err(io::Error::other)?);
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+        if bytes.len() < 6 || &bytes[..4] != MAGIC {
+            return Err(invalid("not a P2Pong replay".to_string()));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version > REPLAY_VERSION {
+            return Err(invalid(format!(
+                "replay format v{} is newer than this build supports (v{})",
+                version, REPLAY_VERSION
+            )));
+        }
+
+        let body: ReplayBody =
+            bincode::deserialize(&bytes[6..]).map_err(|e| invalid(e.to_string()))?;
+        let setup: ReplaySetup =
+            serde_json::from_str(&body.physics).map_err(|e| invalid(e.to_string()))?;
+        Ok(Self {
+            physics: setup.physics,
+            paddle_scales: setup.paddle_scales,
+            inputs: body.inputs,
+            ticks: body.ticks,
+            player_side: setup.player_side,
+            goal_slow_motion: setup.goal_slow_motion,
+        })
+    }
+
+    /// Number of inputs applied before `tick`, starting the search at `from`
+    pub fn inputs_before(&self, tick: u64, from: usize) -> usize {
+        from + self.inputs[from..]
+            .iter()
+            .take_while(|(at, _)| *at <= tick)
+            .count()
+    }
+
+    /// Physics ticks at which `side` scored, found by playing the game back headlessly
+    pub fn scoring_ticks(&self, side: Player) -> Vec<u64> {
+        let mut state = GameState::new(0, 0, &self.physics);
+        state.set_paddle_scales(self.paddle_scales);
+        state.goal_slow_motion = self.goal_slow_motion;
+        let dt = 1.0 / self.physics.tick_rate_hz.max(1) as f32;
+
+        let mut scored = Vec::new();
+        let mut next = 0;
+        for tick in 0..self.ticks {
+            let end = self.inputs_before(tick, next);
+            for &(_, action) in &self.inputs[next..end] {
+                apply_input(&mut state, action);
+            }
+            next = end;
+
+            let before = state.score(side);
+            game::update_with_events(&mut state, dt);
+            if state.score(side) > before {
+                scored.push(tick);
+            }
+        }
+        scored
+    }
+}
+
+/// Records a game while it's played (only when replay.record is on)
+pub struct Recorder {
+    replay: Replay,
+}
+
+impl Recorder {
+    pub fn new(
+        physics: &PhysicsConfig,
+        paddle_scales: (f32, f32),
+        player_side: Option<Player>,
+        goal_slow_motion: bool,
+    ) -> Self {
+        Self {
+            replay: Replay {
+                physics: physics.clone(),
+                paddle_scales,
+                inputs: Vec::new(),
+                ticks: 0,
+                player_side,
+                goal_slow_motion,
+            },
+        }
+    }
+
+    /// Note an input applied to the game before the next physics tick
+    pub fn input(&mut self, action: InputAction) {
+        self.replay.inputs.push((self.replay.ticks, action));
+    }
+
+    /// Note one physics tick
+    pub fn tick(&mut self) {
+        self.replay.ticks += 1;
+    }
+
+    /// Write the recording to the last-replay file, logging any failure
+    pub fn save(&self) {
+        let path = get_replay_path();
+        let result = self
+            .replay
+            .to_bytes()
+            .and_then(|bytes| fs::write(&path, bytes));
+        match result {
+            Ok(()) => debug::log("REPLAY", &format!("Saved to {}", path.display())),
+            Err(e) => debug::warn("REPLAY", &format!("Failed to save replay: {}", e)),
+        }
+    }
+}
+
+/// Get the path of the most recent recording (alongside config.toml)
+pub fn get_replay_path() -> PathBuf {
+    get_config_path().with_file_name("last_replay.p2r")
+}
+
+/// A file given to `--replay`: a full game or a saved highlight
+pub enum Recording {
+    Game(Replay),
+    Highlight(Highlight),
+}
+
+pub fn load(path: &Path) -> io::Result<Recording> {
+    let bytes = fs::read(path)?;
+    if bytes.starts_with(HIGHLIGHT_MAGIC) {
+        Highlight::from_bytes(&bytes).map(Recording::Highlight)
+    } else {
+        Replay::from_bytes(&bytes).map(Recording::Game)
+    }
+}
+
+/// What a highlight shows of one drawn frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighlightFrame {
+    pub balls: Vec<(f32, f32)>,
+    /// (y, height) of the left and right paddle
+    pub paddles: [(f32, f32); 2],
+    pub score: (u8, u8),
+}
+
+impl HighlightFrame {
+    fn capture(state: &GameState) -> Self {
+        Self {
+            balls: state.balls().map(|ball| (ball.x, ball.y)).collect(),
+            paddles: [
+                (state.left_paddle.y, state.left_paddle.height),
+                (state.right_paddle.y, state.right_paddle.height),
+            ],
+            score: (state.left_score, state.right_score),
+        }
+    }
+
+    /// Put the balls, paddles and score of this frame into `state` for drawing
+    pub fn show(&self, state: &mut GameState) {
+        for (ball, &(x, y)) in state.balls_mut().zip(&self.balls) {
+            ball.x = x;
+            ball.y = y;
+        }
+        (state.left_paddle.y, state.left_paddle.height) = self.paddles[0];
+        (state.right_paddle.y, state.right_paddle.height) = self.paddles[1];
+        (state.left_score, state.right_score) = self.score;
+        state.push_ball_trail();
+    }
+}
+
+/// The decisive point of a networked match, as one player saw it
+#[derive(Debug, Clone)]
+pub struct Highlight {
+    pub left_name: String,
+    pub right_name: String,
+    /// Final score of the deciding game
+    pub final_score: (u8, u8),
+    pub physics: PhysicsConfig,
+    /// Frames per second the frames were captured at
+    pub fps: u64,
+    pub frames: Vec<HighlightFrame>,
+    /// The point ran longer than the buffer, so its start is missing
+    pub truncated: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct HighlightBody {
+    // Stored as JSON so settings added later fall back to their defaults
+    physics: String,
+    left_name: String,
+    right_name: String,
+    final_score: (u8, u8),
+    fps: u64,
+    frames: Vec<HighlightFrame>,
+    truncated: bool,
+}
+
+impl Highlight {
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        let body = HighlightBody {
+            physics: serde_json::to_string(&self.physics).map_err(io::Error::other)?,
+            left_name: self.left_name.clone(),
+            right_name: self.right_name.clone(),
+            final_score: self.final_score,
+            fps: self.fps,
+            frames: self.frames.clone(),
+            truncated: self.truncated,
+        };
+
+        let mut bytes = HIGHLIGHT_MAGIC.to_vec();
+        bytes.extend_from_slice(&HIGHLIGHT_VERSION.to_le_bytes());
+        bytes.extend(bincode::serialize(&body).map_err(io::Error::other)?);
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+        if bytes.len() < 6 || &bytes[..4] != HIGHLIGHT_MAGIC {
+            return Err(invalid("not a P2Pong highlight".to_string()));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version > HIGHLIGHT_VERSION {
+            return Err(invalid(format!(
+                "highlight format v{} is newer than this build supports (v{})",
+                version, HIGHLIGHT_VERSION
+            )));
+        }
+
+        let body: HighlightBody =
+            bincode::deserialize(&bytes[6..]).map_err(|e| invalid(e.to_string()))?;
+        let physics = serde_json::from_str(&body.physics).map_err(|e| invalid(e.to_string()))?;
+        Ok(Self {
+            left_name: body.left_name,
+            right_name: body.right_name,
+            final_score: body.final_score,
+            physics,
+            fps: body.fps,
+            frames: body.frames,
+            truncated: body.truncated,
+        })
+    }
+
+    /// Write the highlight to a new timestamped file beside config.toml
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        let path = get_config_path().with_file_name(format!("highlight_{}.p2h", secs));
+        fs::write(&path, self.to_bytes()?)?;
+        debug::log("HIGHLIGHT", &format!("Saved to {}", path.display()));
+        Ok(path)
+    }
+}
+
+/// The frames of the point being played, kept for saving once the match is decided
+pub struct HighlightBuffer {
+    frames: VecDeque<HighlightFrame>,
+    capacity: usize,
+    truncated: bool,
+    point_over: bool,
+}
+
+impl HighlightBuffer {
+    pub fn new(fps: u64) -> Self {
+        let capacity = (fps * MAX_HIGHLIGHT_SECS).max(1) as usize;
+        Self {
+            frames: VecDeque::with_capacity(capacity),
+            capacity,
+            truncated: false,
+            point_over: false,
+        }
+    }
+
+    /// Note a drawn frame. A score change starts a new point; once a game is over the
+    /// buffer holds its last point until the next game starts.
+    pub fn record(&mut self, state: &GameState) {
+        let frame = HighlightFrame::capture(state);
+        let new_point = self
+            .frames
+            .back()
+            .is_some_and(|last| last.score != frame.score);
+
+        if state.game_over {
+            // Keep the frame showing the final score, then hold the point
+            if !self.point_over {
+                self.push(frame);
+                self.point_over = true;
+            }
+            return;
+        }
+        if self.point_over || new_point {
+            self.frames.clear();
+            self.truncated = false;
+            self.point_over = false;
+        }
+        self.push(frame);
+    }
+
+    fn push(&mut self, frame: HighlightFrame) {
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+            self.truncated = true;
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// The buffered point as a highlight, if there is one
+    pub fn highlight(
+        &self,
+        physics: &PhysicsConfig,
+        fps: u64,
+        names: (&str, &str),
+    ) -> Option<Highlight> {
+        let last = self.frames.back()?;
+        Some(Highlight {
+            left_name: names.0.to_string(),
+            right_name: names.1.to_string(),
+            final_score: last.score,
+            physics: physics.clone(),
+            fps,
+            frames: self.frames.iter().cloned().collect(),
+            truncated: self.truncated,
+        })
+    }
+}
+
+/// Apply a recorded input the way the game loops do
+pub fn apply_input(state: &mut GameState, action: InputAction) {
+    if state.paused && action != InputAction::Pause {
+        return;
+    }
+
+    match action {
+        InputAction::Rematch if state.match_over() => state.reset_game(),
+        InputAction::LeftPaddleUp => game::physics::paddle_input(state, Player::Left, false),
+        InputAction::LeftPaddleDown => game::physics::paddle_input(state, Player::Left, true),
+        InputAction::RightPaddleUp => game::physics::paddle_input(state, Player::Right, false),
+        InputAction::RightPaddleDown => game::physics::paddle_input(state, Player::Right, true),
+        InputAction::LeftCatch => game::physics::press_catch(state, Player::Left),
+        InputAction::RightCatch => game::physics::press_catch(state, Player::Right),
+        InputAction::Pause => state.toggle_pause(),
+        InputAction::LeftScoreUp => state.adjust_score(Player::Left, true),
+        InputAction::LeftScoreDown => state.adjust_score(Player::Left, false),
+        InputAction::RightScoreUp => state.adjust_score(Player::Right, true),
+        InputAction::RightScoreDown => state.adjust_score(Player::Right, false),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Replay {
+        Replay {
+            physics: PhysicsConfig {
+                serve_seed: Some(7),
+                ..PhysicsConfig::default()
+            },
+            paddle_scales: (1.0, 1.3),
+            inputs: vec![
+                (0, InputAction::LeftPaddleUp),
+                (3, InputAction::RightPaddleDown),
+                (3, InputAction::Pause),
+            ],
+            ticks: 10,
+            player_side: Some(Player::Right),
+            goal_slow_motion: true,
+        }
+    }
+
+    #[test]
+    fn test_round_trip_and_version_check() {
+        let replay = sample();
+        let mut bytes = replay.to_bytes().unwrap();
+        let loaded = Replay::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.inputs, replay.inputs);
+        assert_eq!(loaded.ticks, replay.ticks);
+        assert_eq!(loaded.physics.serve_seed, Some(7));
+        assert_eq!(loaded.paddle_scales, (1.0, 1.3));
+        assert_eq!(loaded.player_side, Some(Player::Right));
+        assert!(loaded.goal_slow_motion);
+
+        bytes[4] = (REPLAY_VERSION + 1) as u8;
+        assert!(Replay::from_bytes(&bytes).is_err());
+        assert!(Replay::from_bytes(b"PK\x03\x04").is_err());
+    }
+
+    #[test]
+    fn test_highlight_keeps_the_last_point() {
+        let physics = PhysicsConfig::default();
+        let mut state = GameState::new(80, 24, &physics);
+        let mut buffer = HighlightBuffer::new(2); // Room for 60 frames
+        assert!(buffer.highlight(&physics, 2, ("You", "Opponent")).is_none());
+
+        // A long first point overflows the buffer
+        for _ in 0..100 {
+            buffer.record(&state);
+        }
+        // The next point starts with the score change and ends the game
+        state.left_score = 1;
+        for _ in 0..5 {
+            buffer.record(&state);
+        }
+        state.left_score = 2;
+        state.game_over = true;
+        buffer.record(&state);
+        buffer.record(&state);
+
+        let highlight = buffer.highlight(&physics, 2, ("You", "Opponent")).unwrap();
+        assert_eq!(highlight.frames.len(), 6);
+        assert!(!highlight.truncated);
+        assert_eq!(highlight.final_score, (2, 0));
+
+        let loaded = Highlight::from_bytes(&highlight.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.frames, highlight.frames);
+        assert_eq!(loaded.left_name, "You");
+        assert!(Replay::from_bytes(&highlight.to_bytes().unwrap()).is_err());
+
+        // A point longer than the buffer keeps its end and says so
+        state.game_over = false;
+        state.left_score = 0;
+        for _ in 0..100 {
+            buffer.record(&state);
+        }
+        state.game_over = true;
+        buffer.record(&state);
+        let highlight = buffer.highlight(&physics, 2, ("You", "Opponent")).unwrap();
+        assert_eq!(highlight.frames.len(), 60);
+        assert!(highlight.truncated);
+    }
+
+    #[test]
+    fn test_inputs_before() {
+        let replay = sample();
+        assert_eq!(replay.inputs_before(0, 0), 1);
+        assert_eq!(replay.inputs_before(2, 1), 1);
+        assert_eq!(replay.inputs_before(3, 1), 3);
+    }
+
+    #[test]
+    fn test_playback_reproduces_recorded_game() {
+        let physics = PhysicsConfig {
+            serve_seed: Some(42),
+            ..PhysicsConfig::default()
+        };
+        let dt = 1.0 / physics.tick_rate_hz as f32;
+
+        // Record a game with some paddle movement
+        let mut played = GameState::new(80, 24, &physics);
+        played.set_paddle_scales((1.0, 1.3));
+        played.goal_slow_motion = true;
+        let mut recorder = Recorder::new(&physics, played.paddle_scales, None, true);
+        for tick in 0..600 {
+            if tick % 7 == 0 {
+                let action = if tick % 14 == 0 {
+                    InputAction::LeftPaddleUp
+                } else {
+                    InputAction::RightPaddleDown
+                };
+                apply_input(&mut played, action);
+                recorder.input(action);
+            }
+            game::update_with_events(&mut played, dt);
+            recorder.tick();
+        }
+
+        // Feed the saved inputs back in
+        let replay = Replay::from_bytes(&recorder.replay.to_bytes().unwrap()).unwrap();
+        let mut replayed = GameState::new(80, 24, &replay.physics);
+        replayed.set_paddle_scales(replay.paddle_scales);
+        replayed.goal_slow_motion = replay.goal_slow_motion;
+        let mut next = 0;
+        for tick in 0..replay.ticks {
+            let end = replay.inputs_before(tick, next);
+            for &(_, action) in &replay.inputs[next..end] {
+                apply_input(&mut replayed, action);
+            }
+            next = end;
+            game::update_with_events(&mut replayed, dt);
+        }
+
+        assert_eq!(replayed.ball.x, played.ball.x);
+        assert_eq!(replayed.ball.y, played.ball.y);
+        assert_eq!(replayed.left_paddle.y, played.left_paddle.y);
+        assert_eq!(replayed.right_paddle.height, played.right_paddle.height);
+        assert_eq!(
+            (replayed.left_score, replayed.right_score),
+            (played.left_score, played.right_score)
+        );
+
+        // Headless playback finds the same goals
+        let right_goals = replay.scoring_ticks(Player::Right);
+        assert_eq!(right_goals.len(), played.right_score as usize);
+        assert!(right_goals.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
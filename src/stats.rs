@@ -0,0 +1,85 @@
+// Lifetime win/loss record against each bot
+// Stored as JSON next to config.toml
+
+use std::io;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ai::BotType;
+use crate::config::loader::get_config_path;
+use crate::json_store;
+
+/// Player's (wins, losses) against each bot type
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Stats {
+    pub vs_easy: (u32, u32),
+    pub vs_hard: (u32, u32),
+    pub vs_backboard: (u32, u32),
+    pub vs_adaptive: (u32, u32),
+    pub vs_offensive: (u32, u32),
+    pub vs_custom: (u32, u32),
+}
+
+impl Stats {
+    /// (wins, losses) against `bot`
+    pub fn record(&self, bot: BotType) -> (u32, u32) {
+        match bot {
+            BotType::Easy => self.vs_easy,
+            BotType::Hard => self.vs_hard,
+            BotType::Backboard => self.vs_backboard,
+            BotType::Adaptive => self.vs_adaptive,
+            BotType::Offensive => self.vs_offensive,
+            BotType::Custom => self.vs_custom,
+        }
+    }
+
+    /// Count a finished game against `bot`
+    pub fn record_result(&mut self, bot: BotType, won: bool) {
+        let record = match bot {
+            BotType::Easy => &mut self.vs_easy,
+            BotType::Hard => &mut self.vs_hard,
+            BotType::Backboard => &mut self.vs_backboard,
+            BotType::Adaptive => &mut self.vs_adaptive,
+            BotType::Offensive => &mut self.vs_offensive,
+            BotType::Custom => &mut self.vs_custom,
+        };
+        if won {
+            record.0 += 1;
+        } else {
+            record.1 += 1;
+        }
+    }
+}
+
+/// Get the path to the stats file (alongside config.toml)
+pub fn get_stats_path() -> PathBuf {
+    get_config_path().with_file_name("stats.json")
+}
+
+/// Load stats, starting from zeros if missing or unreadable
+pub fn load() -> Stats {
+    json_store::load(&get_stats_path())
+}
+
+/// Write stats back to disk
+pub fn save(stats: &Stats) -> Result<(), io::Error> {
+    json_store::save(stats, &get_stats_path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_result() {
+        let mut stats = Stats::default();
+        stats.record_result(BotType::Easy, true);
+        stats.record_result(BotType::Easy, false);
+        stats.record_result(BotType::Hard, false);
+        assert_eq!(stats.record(BotType::Easy), (1, 1));
+        assert_eq!(stats.record(BotType::Hard), (0, 1));
+        assert_eq!(stats.record(BotType::Backboard), (0, 0));
+    }
+}
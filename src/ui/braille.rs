@@ -3,7 +3,6 @@ use ratatui::style::Color;
 /// Braille canvas for high-resolution terminal rendering
 /// Each terminal cell contains a 2×4 grid of Braille dots
 /// This gives us 2× horizontal and 4× vertical resolution
-
 pub struct BrailleCanvas {
     width: usize,                    // Width in terminal cells
     height: usize,                   // Height in terminal cells
@@ -67,11 +66,6 @@ impl BrailleCanvas {
         }
     }
 
-    /// Fill a rectangle with pixels
-    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize) {
-        self.fill_rect_with_color(x, y, width, height, None);
-    }
-
     /// Fill a rectangle with pixels and a specific color
     pub fn fill_rect_with_color(
         &mut self,
@@ -118,15 +112,16 @@ impl BrailleCanvas {
     }
 
     /// Draw a horizontal line (1 pixel thick) across the canvas
-    pub fn draw_horizontal_line(&mut self, y: usize) {
+    pub fn draw_horizontal_line(&mut self, y: usize, color: Option<Color>) {
         let width = self.pixel_width();
         for x in 0..width {
-            self.set_pixel(x, y);
+            self.set_pixel_with_color(x, y, color);
         }
     }
 
     /// Draw a block-style digit (0-9) at the given pixel position
     /// Each digit is 10 pixels wide × 16 pixels tall (5×4 cells)
+    #[allow(clippy::unusual_byte_groupings)] // Patterns are grouped to show the 10px glyph shape
     pub fn draw_digit(&mut self, digit: u8, x: usize, y: usize) {
         if digit > 9 {
             return;
@@ -330,8 +325,7 @@ impl BrailleCanvas {
         let pattern = &patterns[digit as usize];
 
         // Draw the digit pixel by pixel
-        for row in 0..16 {
-            let row_bits = pattern[row];
+        for (row, &row_bits) in pattern.iter().enumerate() {
             for col in 0..10 {
                 if (row_bits >> (9 - col)) & 1 == 1 {
                     self.set_pixel(x + col, y + row);
@@ -1,6 +1,7 @@
 pub mod braille;
 pub mod overlay;
 pub mod render;
+pub mod theme;
 
 pub use overlay::OverlayMessage;
-pub use render::render;
+pub use render::{is_too_small, render, ConnectionQuality, FieldArea, RenderOptions};
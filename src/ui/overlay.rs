@@ -2,11 +2,14 @@
 
 use ratatui::{
     layout::{Alignment, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
+use serde::{Deserialize, Serialize};
+
+use crate::config::types::DisplayConfig;
 
 /// A message to display as an overlay in the center of the screen
 #[derive(Debug, Clone)]
@@ -15,6 +18,20 @@ pub struct OverlayMessage {
     pub lines: Vec<String>,
     /// Style preset for the overlay
     pub style: OverlayStyle,
+    /// Where on screen the overlay is placed
+    pub position: OverlayPosition,
+    /// Whether to darken everything behind the overlay
+    pub dim_background: bool,
+}
+
+/// Vertical placement of an overlay
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverlayPosition {
+    #[default]
+    Center,
+    Top,
+    Bottom,
 }
 
 /// Predefined styles for overlay messages
@@ -32,6 +49,8 @@ impl OverlayMessage {
         Self {
             lines,
             style: OverlayStyle::Info,
+            position: OverlayPosition::Center,
+            dim_background: false,
         }
     }
 
@@ -40,9 +59,29 @@ impl OverlayMessage {
         Self {
             lines,
             style: OverlayStyle::Error,
+            position: OverlayPosition::Center,
+            dim_background: false,
         }
     }
 
+    /// Override where the overlay is placed
+    pub fn with_position(mut self, position: OverlayPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Override whether the background is dimmed
+    pub fn with_dim_background(mut self, dim: bool) -> Self {
+        self.dim_background = dim;
+        self
+    }
+
+    /// Apply the user's configured overlay placement and dimming
+    pub fn with_display_config(self, display: &DisplayConfig) -> Self {
+        self.with_position(display.overlay_position)
+            .with_dim_background(display.overlay_dim_background)
+    }
+
     /// Get the color for the border and title based on style
     fn border_color(&self) -> Color {
         match self.style {
@@ -71,16 +110,14 @@ pub fn render_overlay(frame: &mut Frame, message: &OverlayMessage, area: Rect) {
         .unwrap_or(0);
 
     // Add padding for borders and spacing
-    let overlay_width = (max_line_length as u16 + 6).min(area.width - 4);
-    let overlay_height = (message.lines.len() as u16 + 4).min(area.height - 4);
-
-    // Center the overlay
-    let overlay_area = Rect {
-        x: area.x + (area.width.saturating_sub(overlay_width)) / 2,
-        y: area.y + (area.height.saturating_sub(overlay_height)) / 2,
-        width: overlay_width,
-        height: overlay_height,
-    };
+    let overlay_width = (max_line_length as u16 + 6).min(area.width.saturating_sub(4));
+    let overlay_height = (message.lines.len() as u16 + 4).min(area.height.saturating_sub(4));
+
+    let overlay_area = place_overlay(area, overlay_width, overlay_height, message.position);
+
+    if message.dim_background {
+        dim_area(frame, area);
+    }
 
     // Clear the area behind the overlay
     frame.render_widget(Clear, overlay_area);
@@ -111,3 +148,76 @@ pub fn render_overlay(frame: &mut Frame, message: &OverlayMessage, area: Rect) {
 
     frame.render_widget(paragraph, inner_area);
 }
+
+/// Compute the overlay rectangle: horizontally centered, vertically per `position`
+fn place_overlay(area: Rect, width: u16, height: u16, position: OverlayPosition) -> Rect {
+    // Keep a one-row gap from the screen edge for top/bottom placement
+    let y = match position {
+        OverlayPosition::Center => area.y + (area.height.saturating_sub(height)) / 2,
+        OverlayPosition::Top => area.y + 1.min(area.height.saturating_sub(height)),
+        OverlayPosition::Bottom => area.y + area.height.saturating_sub(height).saturating_sub(1),
+    };
+
+    Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y,
+        width,
+        height,
+    }
+}
+
+/// Darken already-drawn content by blending foreground colors toward black
+fn dim_area(frame: &mut Frame, area: Rect) {
+    let buffer = frame.buffer_mut();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &mut buffer[(x, y)];
+            match dim_color(cell.fg) {
+                Some(dimmed) => {
+                    cell.set_fg(dimmed);
+                }
+                // Terminal-default and palette colors can't be blended; let the terminal dim them
+                None => {
+                    cell.set_style(Style::default().add_modifier(Modifier::DIM));
+                }
+            }
+        }
+    }
+}
+
+/// Blend an RGB color halfway toward black (None for terminal-default and palette colors)
+fn dim_color(color: Color) -> Option<Color> {
+    match color {
+        Color::Rgb(r, g, b) => Some(Color::Rgb(r / 2, g / 2, b / 2)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlay_placement() {
+        let area = Rect::new(0, 0, 100, 40);
+
+        let center = place_overlay(area, 20, 6, OverlayPosition::Center);
+        assert_eq!((center.x, center.y), (40, 17));
+
+        let top = place_overlay(area, 20, 6, OverlayPosition::Top);
+        assert_eq!(top.y, 1);
+
+        let bottom = place_overlay(area, 20, 6, OverlayPosition::Bottom);
+        assert_eq!(bottom.y + bottom.height, 39);
+    }
+
+    #[test]
+    fn test_dim_color() {
+        assert_eq!(
+            dim_color(Color::Rgb(200, 100, 50)),
+            Some(Color::Rgb(100, 50, 25))
+        );
+        assert_eq!(dim_color(Color::White), None);
+        assert_eq!(dim_color(Color::Reset), None);
+    }
+}
@@ -1,15 +1,19 @@
 use ratatui::{
-    layout::Rect,
+    layout::{Alignment, Rect},
     style::{Color, Style},
     text::{Line, Span},
-    widgets::{Block, Paragraph},
+    widgets::{Block, Paragraph, Wrap},
     Frame,
 };
 
 use super::braille::BrailleCanvas;
 use super::overlay::{render_overlay, OverlayMessage};
+use super::theme::Theme;
+use crate::ai::predict_ball_intercept;
+use crate::config::types::DisplayConfig;
 use crate::game::{
     physics::{BALL_SIZE, PADDLE_MARGIN, PADDLE_WIDTH},
+    state::PowerUpKind,
     GameState, Player,
 };
 
@@ -21,19 +25,123 @@ use crate::game::{
 const UI_HEADER_ROWS: u16 = 5; // Top area before playable field (score + border)
 const UI_FOOTER_ROWS: u16 = 1; // Bottom border
 
+// Power-up glyphs in Braille pixels ('#' = dot): plus to grow, minus to shrink,
+// chevrons for speed
+const GROW_GLYPH: [&str; 5] = ["..#..", "..#..", "#####", "..#..", "..#.."];
+const SHRINK_GLYPH: [&str; 5] = [".....", ".....", "#####", ".....", "....."];
+const SPEED_GLYPH: [&str; 5] = ["#..#..", ".#..#.", "..#..#", ".#..#.", "#..#.."];
+
+// Smallest terminal the game is drawn in (room for both scores and a few field rows)
+const MIN_TERMINAL_WIDTH: u16 = 40;
+const MIN_TERMINAL_HEIGHT: u16 = 12;
+
+// FPS counter colors: green at the 60 FPS target, yellow when slipping, red below
+const FPS_GOOD: f32 = 57.0;
+const FPS_OK: f32 = 45.0;
+
+// Danger zone: fraction of the field (from each goal) where the marker starts glowing
+const DANGER_ZONE_FRACTION: f32 = 0.35;
+const DANGER_ZONE_WIDTH_PIXELS: usize = 2;
+
+// Assist mode: gap between the dots of the predicted ball path (virtual units), and their color
+const ASSIST_DOT_SPACING: f32 = 30.0;
+const ASSIST_COLOR: Color = Color::Rgb(80, 80, 80);
+
+// Connection meter beside the RTT: bars lit out of these, one lost per threshold passed
+const CONNECTION_BARS: [&str; 4] = ["\u{2582}", "\u{2584}", "\u{2586}", "\u{2588}"];
+const LOSS_THRESHOLDS_PCT: [f32; 3] = [1.0, 5.0, 15.0];
+const JITTER_THRESHOLDS_MS: [f32; 2] = [10.0, 30.0];
+
+/// Packet loss and jitter for the connection meter (networked games)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConnectionQuality {
+    pub loss_pct: f32,
+    pub jitter_ms: f32,
+}
+
+impl ConnectionQuality {
+    /// Bars to light (at least one while connected)
+    fn bars(&self) -> usize {
+        let passed = LOSS_THRESHOLDS_PCT
+            .iter()
+            .filter(|t| self.loss_pct >= **t)
+            .count()
+            + JITTER_THRESHOLDS_MS
+                .iter()
+                .filter(|t| self.jitter_ms >= **t)
+                .count();
+        CONNECTION_BARS.len().saturating_sub(passed).max(1)
+    }
+}
+
+/// Optional rendering features
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderOptions<'a> {
+    /// Ball position is a client-side guess (no recent sync); drawn dimmed
+    pub ball_predicted: bool,
+    /// Draw a warm marker at each goal that brightens as the ball approaches
+    pub danger_zone: bool,
+    /// Draw a fading trail of recent ball positions
+    pub ball_trail: bool,
+    /// Training aid: dot the predicted ball path to the player's paddle (to either
+    /// paddle without a `your_player`). Never set in networked games.
+    pub assist: bool,
+    /// Letterbox the field to its virtual aspect ratio instead of stretching it
+    pub preserve_aspect: bool,
+    /// Colors for the field, paddles and ball
+    pub theme: Theme,
+    /// Averaged frames per second to show in the corner (None = hidden)
+    pub fps: Option<f32>,
+    /// Recent chat lines (networked games), oldest first
+    pub chat_lines: &'a [String],
+    /// Chat message being typed, if the chat prompt is open
+    pub chat_input: Option<&'a str>,
+    /// Loss and jitter for the meter beside the RTT (networked games, once measured)
+    pub connection: Option<ConnectionQuality>,
+    /// Extra line between the scores (a ghost race shows the pace here)
+    pub header_note: Option<&'a str>,
+}
+
+impl RenderOptions<'_> {
+    /// Options taken from the display config
+    pub fn from_display(display: &DisplayConfig) -> Self {
+        Self {
+            ball_predicted: false,
+            danger_zone: display.show_danger_zone,
+            ball_trail: display.ball_trail,
+            assist: false, // Only the modes that allow it turn it on
+            preserve_aspect: display.preserve_aspect,
+            theme: Theme::from_config(&display.theme),
+            fps: None,
+            chat_lines: &[],
+            chat_input: None,
+            connection: None,
+            header_note: None,
+        }
+    }
+}
+
 pub fn render(
     frame: &mut Frame,
     state: &GameState,
     rtt_ms: Option<u64>,
     overlay: Option<&OverlayMessage>,
-    _your_player: Option<Player>,
+    your_player: Option<Player>,
+    options: RenderOptions,
 ) {
     let area = frame.area();
 
-    // Draw background (true black RGB, not terminal default)
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(0, 0, 0)));
+    let theme = options.theme;
+
+    // Draw background (explicit RGB, not terminal default)
+    let bg = Block::default().style(Style::default().bg(theme.background));
     frame.render_widget(bg, area);
 
+    if is_too_small(area.width, area.height) {
+        draw_too_small(frame, area, theme.foreground);
+        return;
+    }
+
     // Create Braille canvas for entire screen (including score area and borders)
     let canvas_width = area.width as usize;
     let canvas_height = area.height as usize;
@@ -42,91 +150,174 @@ pub fn render(
     // Draw Braille scores at the top (centered in header area)
     draw_braille_scores(&mut canvas, state);
 
-    // Calculate playable area dimensions
-    let playable_height_rows = area.height - UI_HEADER_ROWS - UI_FOOTER_ROWS;
-    let playable_height_pixels = playable_height_rows as usize * 4;
-    let playable_offset_y = UI_HEADER_ROWS as usize * 4; // Start after header
-
-    // Draw top border (just before playable area starts, where ball bounces at y=0)
-    // When ball.y = 0, it's at the top. With offset, that's playable_offset_y.
-    // Border should be 1 pixel above where ball can go.
-    let top_border_y = playable_offset_y - 1;
-    canvas.draw_horizontal_line(top_border_y);
-
-    // Draw bottom border (at the last pixel of playable area, where ball bounces at y=VIRTUAL_HEIGHT)
-    // When ball.y = VIRTUAL_HEIGHT, pixel_y = VIRTUAL_HEIGHT * scale_y + offset = playable_height_pixels + offset
-    // Border should be at the last pixel the ball can reach
-    let bottom_border_y = playable_offset_y + playable_height_pixels - 1;
-    canvas.draw_horizontal_line(bottom_border_y);
+    // Place the playable field below the header (letterboxed if keeping the aspect ratio)
+    let field = FieldArea::new(area.width, area.height, state, options.preserve_aspect);
+    draw_field_border(&mut canvas, &field, theme.border);
+
+    // Draw obstacles like the border
+    for obstacle in &state.obstacles {
+        canvas.fill_rect_with_color(
+            field.pixel_x(obstacle.x),
+            field.pixel_y(obstacle.y),
+            (obstacle.width * field.scale_x()).max(1.0) as usize,
+            (obstacle.height * field.scale_y()).max(1.0) as usize,
+            Some(theme.border),
+        );
+    }
 
-    // Calculate scale from virtual to Braille pixels
-    let scale_x = (canvas.pixel_width()) as f32 / state.field_width;
-    let scale_y = playable_height_pixels as f32 / state.field_height;
+    for powerup in &state.powerups {
+        let (x, y) = powerup.position;
+        draw_powerup_at(&mut canvas, &field, powerup.kind, x, y, theme.foreground);
+    }
 
     // Draw paddles in Braille (use same X positions as physics)
-    let left_paddle_pixel_y = (state.left_paddle.y * scale_y) as usize + playable_offset_y;
+    let (left_paddle_color, right_paddle_color) = theme.paddle_colors(your_player);
     draw_braille_paddle_at(
         &mut canvas,
-        left_paddle_pixel_y,
-        state.left_paddle.height,
+        &field,
         PADDLE_MARGIN,
-        scale_x,
-        scale_y,
-        None,
+        state.left_paddle.y,
+        state.left_paddle.height,
+        Some(left_paddle_color),
     );
 
     let right_paddle_x = state.field_width - PADDLE_MARGIN - PADDLE_WIDTH;
-    let right_paddle_pixel_y = (state.right_paddle.y * scale_y) as usize + playable_offset_y;
     draw_braille_paddle_at(
         &mut canvas,
-        right_paddle_pixel_y,
-        state.right_paddle.height,
+        &field,
         right_paddle_x,
-        scale_x,
-        scale_y,
-        None,
+        state.right_paddle.y,
+        state.right_paddle.height,
+        Some(right_paddle_color),
     );
 
-    // Draw ball in Braille
-    let ball_pixel_y = (state.ball.y * scale_y) as usize + playable_offset_y;
-    draw_braille_ball_at(&mut canvas, state.ball.x, ball_pixel_y, scale_x, scale_y);
+    // Draw the assist path and trail first so the ball's own color wins where they
+    // share a cell
+    if options.assist && !state.game_over {
+        let sides = match your_player {
+            Some(player) => vec![player],
+            None => vec![Player::Left, Player::Right],
+        };
+        for side in sides {
+            for (x, y) in assist_path(state, side) {
+                let (px, py) = (field.pixel_x(x), field.pixel_y(y));
+                canvas.set_pixel_with_color(px, py, Some(ASSIST_COLOR));
+            }
+        }
+    }
+    if options.ball_trail {
+        draw_ball_trail(&mut canvas, state, &field);
+    }
+
+    // Draw ball in Braille (dimmed while only predicted)
+    let ball_color = if options.ball_predicted {
+        Color::DarkGray
+    } else {
+        theme.ball
+    };
+    for ball in state.balls() {
+        draw_braille_ball_at(&mut canvas, &field, ball.x, ball.y, Some(ball_color));
+    }
+
+    // Draw goal danger markers
+    if options.danger_zone {
+        for side in [Player::Left, Player::Right] {
+            let intensity = danger_intensity(state, side);
+            if intensity > 0.0 {
+                draw_danger_zone(&mut canvas, &field, side, intensity, theme.danger);
+            }
+        }
+    }
 
     // Draw center line
-    draw_center_line_at(
-        &mut canvas,
-        scale_x,
-        playable_offset_y,
-        playable_height_pixels,
-        state.field_width,
-    );
+    draw_center_line_at(&mut canvas, &field, state.field_width);
 
     // Draw RTT if networked (top right corner)
     if let Some(rtt) = rtt_ms {
-        draw_rtt(frame, area, rtt);
+        draw_rtt(frame, area, rtt, options.connection);
     }
 
     // Render the Braille canvas (pass whether RTT is shown to adjust rendering)
-    render_braille_canvas(frame, &canvas, area, rtt_ms.is_some());
+    render_braille_canvas(frame, &canvas, area, rtt_ms.is_some(), theme.foreground);
+
+    // Draw FPS after the canvas so its blank cells don't cover it
+    if let Some(fps) = options.fps {
+        draw_fps(frame, area, fps);
+    }
+
+    // Match tally and game clock between the scores (when in use)
+    if !state.match_state.is_single_game() {
+        draw_header_text(frame, area, 0, state.match_state.label(), theme.foreground);
+    }
+    if let Some(time_left) = state.time_left() {
+        draw_header_text(
+            frame,
+            area,
+            2,
+            clock_label(state, time_left),
+            theme.foreground,
+        );
+    }
+
+    if let Some(note) = options.header_note {
+        draw_header_text(frame, area, 1, note.to_string(), theme.foreground);
+    }
 
-    // Render overlay message if present (on top of everything)
+    // Chat sits at the bottom of the field, above the bottom border
+    if !options.chat_lines.is_empty() || options.chat_input.is_some() {
+        draw_chat(frame, area, options.chat_lines, options.chat_input);
+    }
+
+    // Render overlay message if present (on top of everything); a paused game
+    // without another overlay shows the pause banner
     if let Some(overlay_message) = overlay {
         render_overlay(frame, overlay_message, area);
+    } else if state.paused {
+        render_overlay(
+            frame,
+            &OverlayMessage::info(vec!["PAUSED".to_string()]),
+            area,
+        );
     }
 }
 
+/// Whether a `cols` x `rows` terminal is too small to draw the game
+pub fn is_too_small(cols: u16, rows: u16) -> bool {
+    cols < MIN_TERMINAL_WIDTH || rows < MIN_TERMINAL_HEIGHT
+}
+
+fn draw_too_small(frame: &mut Frame, area: Rect, color: Color) {
+    let text = format!(
+        "Terminal too small \u{2014} please resize (min {}x{})",
+        MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT
+    );
+
+    // Vertically centred, wrapping onto a second line if needed
+    let text_area = Rect {
+        y: area.y + area.height.saturating_sub(2) / 2,
+        height: area.height.min(2),
+        ..area
+    };
+    let paragraph = Paragraph::new(text)
+        .style(Style::default().fg(color))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, text_area);
+}
+
 fn draw_braille_paddle_at(
     canvas: &mut BrailleCanvas,
-    pixel_y: usize,
-    vh: f32,
+    field: &FieldArea,
     vx: f32,
-    scale_x: f32,
-    scale_y: f32,
+    vy: f32,
+    vh: f32,
     color: Option<Color>,
 ) {
-    // Convert virtual X coordinate to Braille pixel coordinates
-    let pixel_x = (vx * scale_x) as usize;
-    let pixel_height = (vh * scale_y) as usize;
-    let pixel_width = (PADDLE_WIDTH * scale_x) as usize;
+    // Convert virtual coordinates to Braille pixel coordinates
+    let pixel_x = field.pixel_x(vx);
+    let pixel_y = field.pixel_y(vy);
+    let pixel_height = (vh * field.scale_y()) as usize;
+    let pixel_width = (PADDLE_WIDTH * field.scale_x()) as usize;
 
     // Draw solid rectangle with color
     canvas.fill_rect_with_color(pixel_x, pixel_y, pixel_width, pixel_height, color);
@@ -134,45 +325,234 @@ fn draw_braille_paddle_at(
 
 fn draw_braille_ball_at(
     canvas: &mut BrailleCanvas,
+    field: &FieldArea,
     vx: f32,
-    pixel_y: usize,
-    scale_x: f32,
-    scale_y: f32,
+    vy: f32,
+    color: Option<Color>,
 ) {
-    // Ball position (vx, pixel_y) - vx is virtual X, pixel_y is absolute pixel Y
     // Convert BALL_SIZE from virtual coords to Braille pixels
-    let ball_pixel_width = (BALL_SIZE * scale_x) as usize;
-    let ball_pixel_height = (BALL_SIZE * scale_y) as usize;
-
-    // Convert ball center X to pixel coordinates
-    let center_pixel_x = (vx * scale_x) as usize;
+    let ball_pixel_width = (BALL_SIZE * field.scale_x()) as usize;
+    let ball_pixel_height = (BALL_SIZE * field.scale_y()) as usize;
 
     // Calculate top-left corner (center the ball on its position)
-    let ball_x = center_pixel_x.saturating_sub(ball_pixel_width / 2);
-    let ball_y = pixel_y.saturating_sub(ball_pixel_height / 2);
+    let ball_x = field.pixel_x(vx).saturating_sub(ball_pixel_width / 2);
+    let ball_y = field.pixel_y(vy).saturating_sub(ball_pixel_height / 2);
 
     // Draw ball as solid rectangle
-    canvas.fill_rect(ball_x, ball_y, ball_pixel_width, ball_pixel_height);
+    canvas.fill_rect_with_color(ball_x, ball_y, ball_pixel_width, ball_pixel_height, color);
 }
 
-fn draw_center_line_at(
+fn draw_powerup_at(
     canvas: &mut BrailleCanvas,
-    scale_x: f32,
-    offset_y: usize,
-    height: usize,
+    field: &FieldArea,
+    kind: PowerUpKind,
+    vx: f32,
+    vy: f32,
+    color: Color,
+) {
+    let glyph = match kind {
+        PowerUpKind::GrowPaddle => GROW_GLYPH,
+        PowerUpKind::ShrinkPaddle => SHRINK_GLYPH,
+        PowerUpKind::SpeedBoost => SPEED_GLYPH,
+    };
+
+    // Center the glyph on its position
+    let left = field.pixel_x(vx).saturating_sub(glyph[0].len() / 2);
+    let top = field.pixel_y(vy).saturating_sub(glyph.len() / 2);
+    for (row, line) in glyph.iter().enumerate() {
+        for (col, dot) in line.bytes().enumerate() {
+            if dot == b'#' {
+                canvas.set_pixel_with_color(left + col, top + row, Some(color));
+            }
+        }
+    }
+}
+
+/// Where the playable field sits on the Braille canvas, in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldArea {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
     field_width: f32,
+    field_height: f32,
+}
+
+impl FieldArea {
+    /// Field area for a `cols` x `rows` terminal. With `preserve_aspect` the field keeps
+    /// the virtual field's aspect ratio and is centred, leaving margins on two sides.
+    pub fn new(cols: u16, rows: u16, state: &GameState, preserve_aspect: bool) -> Self {
+        let mut x = 0;
+        let mut y = UI_HEADER_ROWS as usize * 4; // Start after header
+        let mut width = cols as usize * 2;
+        let mut height = rows.saturating_sub(UI_HEADER_ROWS + UI_FOOTER_ROWS) as usize * 4;
+
+        // Braille pixels are roughly square (2x4 dots in a 1:2 cell)
+        if preserve_aspect {
+            let aspect = state.field_width / state.field_height;
+            if width as f32 > height as f32 * aspect {
+                let fitted = (height as f32 * aspect) as usize;
+                x = (width - fitted) / 2;
+                width = fitted;
+            } else {
+                let fitted = (width as f32 / aspect) as usize;
+                y += (height - fitted.min(height)) / 2;
+                height = fitted.min(height);
+            }
+        }
+
+        Self {
+            x,
+            y,
+            width,
+            height,
+            field_width: state.field_width,
+            field_height: state.field_height,
+        }
+    }
+
+    fn scale_x(&self) -> f32 {
+        self.width as f32 / self.field_width
+    }
+
+    fn scale_y(&self) -> f32 {
+        self.height as f32 / self.field_height
+    }
+
+    fn pixel_x(&self, vx: f32) -> usize {
+        self.x + (vx * self.scale_x()) as usize
+    }
+
+    fn pixel_y(&self, vy: f32) -> usize {
+        self.y + (vy * self.scale_y()) as usize
+    }
+
+    /// Virtual y coordinate at the middle of terminal `row` (clamped to the field)
+    pub fn field_y_at_row(&self, row: u16) -> f32 {
+        let offset = row as f32 * 4.0 + 2.0 - self.y as f32;
+        (offset / self.height.max(1) as f32 * self.field_height).clamp(0.0, self.field_height)
+    }
+}
+
+/// Border lines above and below the field, closed at the sides when it's pillarboxed
+fn draw_field_border(canvas: &mut BrailleCanvas, field: &FieldArea, color: Color) {
+    // The ball bounces at y = 0 one pixel below the top border and reaches the
+    // bottom border's pixel at y = field height
+    let top = field.y - 1;
+    let bottom = field.y + field.height - 1;
+
+    if field.x == 0 {
+        canvas.draw_horizontal_line(top, Some(color));
+        canvas.draw_horizontal_line(bottom, Some(color));
+        return;
+    }
+
+    let left = field.x - 1;
+    let right = field.x + field.width;
+    let width = right - left + 1;
+    let height = bottom - top + 1;
+    canvas.fill_rect_with_color(left, top, width, 1, Some(color));
+    canvas.fill_rect_with_color(left, bottom, width, 1, Some(color));
+    canvas.fill_rect_with_color(left, top, 1, height, Some(color));
+    canvas.fill_rect_with_color(right, top, 1, height, Some(color));
+}
+
+/// Gray level for trail point `index` of `len` (oldest is dimmest)
+fn trail_color(index: usize, len: usize) -> Color {
+    let level = 40 + (160 * (index + 1) / len.max(1)) as u8;
+    Color::Rgb(level, level, level)
+}
+
+fn draw_ball_trail(canvas: &mut BrailleCanvas, state: &GameState, field: &FieldArea) {
+    let len = state.ball_trail.len();
+    for (i, &(x, y)) in state.ball_trail.iter().enumerate() {
+        let color = Some(trail_color(i, len));
+        canvas.set_pixel_with_color(field.pixel_x(x), field.pixel_y(y), color);
+    }
+}
+
+/// Points along the path the ball heading for `side` will take to that paddle's face,
+/// bouncing off the walls (empty if no ball is coming)
+fn assist_path(state: &GameState, side: Player) -> Vec<(f32, f32)> {
+    let ball = state.ball_toward(side);
+    let paddle_face = match side {
+        Player::Left => PADDLE_MARGIN + PADDLE_WIDTH,
+        Player::Right => state.field_width - PADDLE_MARGIN - PADDLE_WIDTH,
+    };
+    let height = state.field_height;
+    let y_at = |x| predict_ball_intercept(ball.x, ball.y, ball.vx, ball.vy, x, height);
+    if y_at(paddle_face).is_none() {
+        return Vec::new();
+    }
+
+    let distance = paddle_face - ball.x;
+    let dots = (distance.abs() / ASSIST_DOT_SPACING) as usize;
+    (1..=dots)
+        .map(|i| ball.x + distance * i as f32 / dots as f32)
+        .filter_map(|x| y_at(x).map(|y| (x, y)))
+        .collect()
+}
+
+/// How close a ball is to scoring on `side`'s goal (0.0 = far or moving away, 1.0 = at the line)
+fn danger_intensity(state: &GameState, side: Player) -> f32 {
+    let ball = state.ball_toward(side);
+    let (distance, approaching) = match side {
+        Player::Left => (ball.x, ball.vx < 0.0),
+        Player::Right => (state.field_width - ball.x, ball.vx > 0.0),
+    };
+
+    if !approaching {
+        return 0.0;
+    }
+
+    let zone = state.field_width * DANGER_ZONE_FRACTION;
+    (1.0 - distance / zone).clamp(0.0, 1.0)
+}
+
+fn draw_danger_zone(
+    canvas: &mut BrailleCanvas,
+    field: &FieldArea,
+    side: Player,
+    intensity: f32,
+    full: Color,
 ) {
-    let center_pixel_x = (field_width / 2.0 * scale_x) as usize;
+    // The theme's danger color, brightening with intensity
+    let level = (60.0 + 195.0 * intensity) / 255.0;
+    let color = match full {
+        Color::Rgb(r, g, b) => {
+            let scale = |channel: u8| (channel as f32 * level) as u8;
+            Color::Rgb(scale(r), scale(g), scale(b))
+        }
+        named => named,
+    };
+
+    let start_x = match side {
+        Player::Left => field.x,
+        Player::Right => (field.x + field.width).saturating_sub(DANGER_ZONE_WIDTH_PIXELS),
+    };
+
+    canvas.fill_rect_with_color(
+        start_x,
+        field.y,
+        DANGER_ZONE_WIDTH_PIXELS,
+        field.height,
+        Some(color),
+    );
+}
+
+fn draw_center_line_at(canvas: &mut BrailleCanvas, field: &FieldArea, field_width: f32) {
+    let center_pixel_x = field.pixel_x(field_width / 2.0);
 
     // Draw dotted center line (every other pixel) in playable area only
-    for y in (0..height).step_by(4) {
-        let pixel_y = offset_y + y;
+    for y in (0..field.height).step_by(4) {
+        let pixel_y = field.y + y;
         canvas.set_pixel(center_pixel_x, pixel_y);
         canvas.set_pixel(center_pixel_x, pixel_y + 1);
     }
 }
 
-fn draw_rtt(frame: &mut Frame, area: Rect, rtt_ms: u64) {
+fn draw_rtt(frame: &mut Frame, area: Rect, rtt_ms: u64, connection: Option<ConnectionQuality>) {
     // Show RTT in top right corner
     let rtt_text = if rtt_ms > 0 {
         format!("RTT: {}ms", rtt_ms)
@@ -188,10 +568,30 @@ fn draw_rtt(frame: &mut Frame, area: Rect, rtt_ms: u64) {
         Color::Red
     };
 
-    let width = rtt_text.len() as u16;
+    // Connection meter to the left of the RTT, colored by how many bars are lit
+    let mut spans = Vec::new();
+    if let Some(connection) = connection {
+        let lit = connection.bars();
+        let meter_color = match lit {
+            4 => Color::Green,
+            2 | 3 => Color::Yellow,
+            _ => Color::Red,
+        };
+        for (i, bar) in CONNECTION_BARS.iter().enumerate() {
+            let color = if i < lit {
+                meter_color
+            } else {
+                Color::DarkGray
+            };
+            spans.push(Span::styled(*bar, Style::default().fg(color)));
+        }
+        spans.push(Span::raw(" "));
+    }
+    let width = (spans.len() + rtt_text.len()) as u16;
+    spans.push(Span::styled(rtt_text, Style::default().fg(rtt_color)));
     let left_offset = 2;
 
-    let rtt_widget = Paragraph::new(rtt_text).style(Style::default().fg(rtt_color));
+    let rtt_widget = Paragraph::new(Line::from(spans));
 
     let rtt_area = Rect {
         x: area.x + area.width.saturating_sub(width + left_offset),
@@ -203,7 +603,112 @@ fn draw_rtt(frame: &mut Frame, area: Rect, rtt_ms: u64) {
     frame.render_widget(rtt_widget, rtt_area);
 }
 
-fn render_braille_canvas(frame: &mut Frame, canvas: &BrailleCanvas, area: Rect, show_rtt: bool) {
+/// Game clock text: mm:ss left, or SUDDEN DEATH once time is up on a tie
+fn clock_label(state: &GameState, time_left: f32) -> String {
+    if time_left <= 0.0 && !state.game_over {
+        return "SUDDEN DEATH".to_string();
+    }
+    let secs = time_left.ceil() as u64;
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+/// Text centered in header `row`, between the two score digits
+fn draw_header_text(frame: &mut Frame, area: Rect, row: u16, text: String, color: Color) {
+    let width = text.len() as u16;
+
+    let left_score_end = area.width / 3 + 3;
+    let x = (area.width.saturating_sub(width)) / 2;
+    if x <= left_score_end || row >= area.height {
+        return; // Too small: would overlap the scores
+    }
+
+    let widget = Paragraph::new(text).style(Style::default().fg(color));
+    let text_area = Rect {
+        x: area.x + x,
+        y: area.y + row,
+        width,
+        height: 1,
+    };
+    frame.render_widget(widget, text_area);
+}
+
+fn draw_fps(frame: &mut Frame, area: Rect, fps: f32) {
+    // Show FPS in top left corner, left of the left score
+    let fps_text = format!("FPS: {:.0}", fps);
+
+    let fps_color = if fps >= FPS_GOOD {
+        Color::Green
+    } else if fps >= FPS_OK {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+
+    let width = fps_text.len() as u16;
+    let left_offset = 2;
+
+    // Left score starts at a third of the width (in cells, less its half-width)
+    let left_score_cell = (area.width / 3).saturating_sub(3);
+    if left_offset + width >= left_score_cell {
+        return; // Too narrow: would overlap the score
+    }
+
+    let fps_widget = Paragraph::new(fps_text).style(Style::default().fg(fps_color));
+
+    let fps_area = Rect {
+        x: area.x + left_offset,
+        y: area.y,
+        width,
+        height: 1,
+    };
+
+    frame.render_widget(fps_widget, fps_area);
+}
+
+fn draw_chat(frame: &mut Frame, area: Rect, lines: &[String], input: Option<&str>) {
+    let bottom_row = area.y + area.height.saturating_sub(UI_FOOTER_ROWS + 1);
+    let max_width = area.width.saturating_sub(4) as usize;
+
+    // Prompt on the bottom row, log scrolling upwards above it (newest lowest)
+    let prompt = input.map(|text| {
+        Line::from(vec![
+            Span::styled("Say: ", Style::default().fg(Color::Yellow)),
+            Span::styled(format!("{}_", text), Style::default().fg(Color::White)),
+        ])
+    });
+    let log = lines.iter().rev().map(|line| {
+        Line::from(Span::styled(
+            line.as_str(),
+            Style::default().fg(Color::Gray),
+        ))
+    });
+
+    for (row, line) in prompt.into_iter().chain(log).enumerate() {
+        let Some(y) = bottom_row.checked_sub(row as u16) else {
+            break;
+        };
+        if y < area.y + UI_HEADER_ROWS {
+            break; // Never draw over the score header
+        }
+
+        let width = (line.width().min(max_width)) as u16;
+        let line_area = Rect {
+            x: area.x + 2,
+            y,
+            width,
+            height: 1,
+        };
+        frame.render_widget(Paragraph::new(line), line_area);
+    }
+}
+
+fn render_braille_canvas(
+    frame: &mut Frame,
+    canvas: &BrailleCanvas,
+    area: Rect,
+    show_rtt: bool,
+    default_color: Color,
+) {
     // Render each row of the Braille canvas
     // For row 0 (where RTT is), render left portion only IF RTT is being displayed
     // For row 3 (where game over is), render left and right segments (skip center fifth)
@@ -219,7 +724,7 @@ fn render_braille_canvas(frame: &mut Frame, canvas: &BrailleCanvas, area: Rect,
             let mut left_spans = Vec::new();
             for x in 0..left_segment_width {
                 let ch = canvas.to_char(x, y);
-                let color = canvas.get_color(x, y).unwrap_or(Color::White);
+                let color = canvas.get_color(x, y).unwrap_or(default_color);
                 let display_ch = if ch == '\u{2800}' { ' ' } else { ch };
                 left_spans.push(Span::styled(
                     display_ch.to_string(),
@@ -244,7 +749,7 @@ fn render_braille_canvas(frame: &mut Frame, canvas: &BrailleCanvas, area: Rect,
             let mut right_spans = Vec::new();
             for x in right_start..cell_width {
                 let ch = canvas.to_char(x, y);
-                let color = canvas.get_color(x, y).unwrap_or(Color::White);
+                let color = canvas.get_color(x, y).unwrap_or(default_color);
                 let display_ch = if ch == '\u{2800}' { ' ' } else { ch };
                 right_spans.push(Span::styled(
                     display_ch.to_string(),
@@ -275,7 +780,7 @@ fn render_braille_canvas(frame: &mut Frame, canvas: &BrailleCanvas, area: Rect,
 
             for x in 0..render_width {
                 let ch = canvas.to_char(x, y);
-                let color = canvas.get_color(x, y).unwrap_or(Color::White);
+                let color = canvas.get_color(x, y).unwrap_or(default_color);
                 // Convert empty Braille to space so text can show through
                 let display_ch = if ch == '\u{2800}' { ' ' } else { ch };
                 spans.push(Span::styled(
@@ -319,3 +824,116 @@ fn draw_braille_scores(canvas: &mut BrailleCanvas, state: &GameState) {
     // Draw right score
     canvas.draw_digit(state.right_score, right_score_x, score_y);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PhysicsConfig;
+
+    #[test]
+    fn test_danger_intensity_tracks_approach() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+
+        // Ball at center heading left: outside the zone
+        state.ball.x = state.field_width / 2.0;
+        state.ball.vx = -100.0;
+        assert_eq!(danger_intensity(&state, Player::Left), 0.0);
+
+        // Near the left goal and approaching: glowing, and only on the left
+        state.ball.x = state.field_width * 0.05;
+        let near = danger_intensity(&state, Player::Left);
+        assert!(near > 0.8);
+        assert_eq!(danger_intensity(&state, Player::Right), 0.0);
+
+        // Same spot but moving away: no warning
+        state.ball.vx = 100.0;
+        assert_eq!(danger_intensity(&state, Player::Left), 0.0);
+    }
+
+    #[test]
+    fn test_field_area_letterboxes_to_aspect() {
+        let state = GameState::new(80, 24, &PhysicsConfig::default());
+
+        // Stretched: the whole width and everything below the header
+        let stretched = FieldArea::new(200, 46, &state, false);
+        assert_eq!((stretched.x, stretched.width), (0, 400));
+        assert_eq!((stretched.y, stretched.height), (20, 160));
+
+        // Wide terminal: pillarboxed to 2:1 and centred
+        let wide = FieldArea::new(200, 46, &state, true);
+        assert_eq!((wide.x, wide.width), (40, 320));
+        assert_eq!((wide.y, wide.height), (20, 160));
+
+        // Tall terminal: letterboxed top and bottom
+        let tall = FieldArea::new(40, 46, &state, true);
+        assert_eq!((tall.x, tall.width), (0, 80));
+        assert_eq!((tall.y, tall.height), (80, 40));
+        assert_eq!(tall.field_y_at_row(0), 0.0);
+        assert_eq!(tall.field_y_at_row(45), state.field_height);
+    }
+
+    #[test]
+    fn test_render_survives_small_terminals() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let state = GameState::new(80, 24, &PhysicsConfig::default());
+        for (cols, rows) in [(1, 1), (20, 5), (39, 30), (80, 11), (40, 12)] {
+            let mut terminal = Terminal::new(TestBackend::new(cols, rows)).unwrap();
+            terminal
+                .draw(|f| render(f, &state, Some(20), None, None, RenderOptions::default()))
+                .unwrap();
+        }
+        assert!(is_too_small(39, 30));
+        assert!(!is_too_small(MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT));
+    }
+
+    #[test]
+    fn test_connection_bars() {
+        let quality = |loss_pct, jitter_ms| ConnectionQuality {
+            loss_pct,
+            jitter_ms,
+        };
+        assert_eq!(quality(0.0, 2.0).bars(), 4);
+        assert_eq!(quality(5.0, 2.0).bars(), 2);
+        assert_eq!(quality(0.0, 12.0).bars(), 3);
+        assert_eq!(quality(50.0, 80.0).bars(), 1);
+    }
+
+    #[test]
+    fn test_assist_path_follows_bounces_to_the_paddle() {
+        let mut state = GameState::new(80, 24, &PhysicsConfig::default());
+        state.ball.x = 600.0;
+        state.ball.y = 500.0;
+        state.ball.vx = -400.0;
+        state.ball.vy = 300.0;
+
+        let path = assist_path(&state, Player::Left);
+        let &(end_x, _) = path.last().unwrap();
+        assert_eq!(end_x, PADDLE_MARGIN + PADDLE_WIDTH);
+        assert!(path
+            .iter()
+            .all(|&(_, y)| (0.0..=state.field_height).contains(&y)));
+        // Heads down into the bottom wall, then back up
+        assert!(path[0].1 > 500.0);
+        assert!(path.last().unwrap().1 < path[path.len() / 2].1);
+
+        // Nothing is coming the other way
+        assert!(assist_path(&state, Player::Right).is_empty());
+    }
+
+    #[test]
+    fn test_clock_label() {
+        let physics = PhysicsConfig {
+            time_limit_secs: Some(90),
+            ..PhysicsConfig::default()
+        };
+        let mut state = GameState::new(80, 24, &physics);
+        assert_eq!(clock_label(&state, state.time_left().unwrap()), "01:30");
+
+        state.elapsed = 89.5;
+        assert_eq!(clock_label(&state, state.time_left().unwrap()), "00:01");
+
+        state.elapsed = 90.0;
+        assert_eq!(clock_label(&state, 0.0), "SUDDEN DEATH");
+    }
+}
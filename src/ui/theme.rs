@@ -0,0 +1,187 @@
+// Color themes for the game field
+
+use ratatui::style::Color;
+
+use crate::config::types::ThemeConfig;
+use crate::game::Player;
+
+/// Names accepted by `Theme::named`
+pub const THEME_NAMES: [&str; 3] = ["classic", "amber", "matrix"];
+
+/// Resolved colors used when drawing the game field
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub ball: Color,
+    pub left_paddle: Color,
+    pub right_paddle: Color,
+    /// Paddle colors when one side is yours (AI and network games)
+    pub your_paddle: Color,
+    pub opponent_paddle: Color,
+    pub border: Color,
+    pub background: Color,
+    /// Scores, center line and anything else without its own color
+    pub foreground: Color,
+    /// Goal danger markers at full strength (RGB colors fade in as the ball nears)
+    pub danger: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl Theme {
+    /// White on black with cyan and magenta paddles
+    pub fn classic() -> Self {
+        Self {
+            ball: Color::White,
+            left_paddle: Color::Cyan,
+            right_paddle: Color::Magenta,
+            your_paddle: Color::Cyan,
+            opponent_paddle: Color::Magenta,
+            border: Color::White,
+            background: Color::Rgb(0, 0, 0),
+            foreground: Color::White,
+            danger: Color::Rgb(255, 89, 0),
+        }
+    }
+
+    /// Amber phosphor monitor
+    pub fn amber() -> Self {
+        let amber = Color::Rgb(255, 176, 0);
+        Self {
+            ball: Color::Rgb(255, 214, 90),
+            left_paddle: amber,
+            right_paddle: Color::Rgb(255, 96, 32),
+            your_paddle: amber,
+            opponent_paddle: Color::Rgb(255, 96, 32),
+            border: Color::Rgb(170, 110, 0),
+            background: Color::Rgb(12, 6, 0),
+            foreground: amber,
+            danger: Color::Rgb(255, 60, 0),
+        }
+    }
+
+    /// Green-on-black terminal
+    pub fn matrix() -> Self {
+        let green = Color::Rgb(0, 255, 65);
+        Self {
+            ball: Color::Rgb(180, 255, 190),
+            left_paddle: green,
+            right_paddle: Color::Rgb(0, 200, 200),
+            your_paddle: green,
+            opponent_paddle: Color::Rgb(0, 200, 200),
+            border: Color::Rgb(0, 143, 17),
+            background: Color::Rgb(0, 8, 0),
+            foreground: green,
+            danger: Color::Rgb(220, 255, 0),
+        }
+    }
+
+    /// Built-in theme by name (case-insensitive)
+    pub fn named(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "classic" => Some(Self::classic()),
+            "amber" => Some(Self::amber()),
+            "matrix" => Some(Self::matrix()),
+            _ => None,
+        }
+    }
+
+    /// Theme from config: the named base theme (classic if unknown) with any
+    /// valid hex overrides applied; unparseable overrides are ignored
+    pub fn from_config(config: &ThemeConfig) -> Self {
+        let mut theme = Self::named(&config.name).unwrap_or_default();
+
+        let overrides = [
+            (&config.ball_color, &mut theme.ball),
+            (&config.left_paddle_color, &mut theme.left_paddle),
+            (&config.right_paddle_color, &mut theme.right_paddle),
+            (&config.your_paddle_color, &mut theme.your_paddle),
+            (&config.opponent_paddle_color, &mut theme.opponent_paddle),
+            (&config.border_color, &mut theme.border),
+            (&config.background_color, &mut theme.background),
+            (&config.danger_zone_color, &mut theme.danger),
+        ];
+        for (hex, color) in overrides {
+            if let Some(parsed) = hex.as_deref().and_then(parse_hex_color) {
+                *color = parsed;
+            }
+        }
+
+        theme
+    }
+
+    /// (left, right) paddle colors: yours and your opponent's when you play one side,
+    /// otherwise each side's own color
+    pub fn paddle_colors(&self, your_player: Option<Player>) -> (Color, Color) {
+        match your_player {
+            Some(Player::Left) => (self.your_paddle, self.opponent_paddle),
+            Some(Player::Right) => (self.opponent_paddle, self.your_paddle),
+            None => (self.left_paddle, self.right_paddle),
+        }
+    }
+}
+
+/// Parse "#rrggbb" (leading '#' optional) into an RGB color
+pub fn parse_hex_color(hex: &str) -> Option<Color> {
+    let digits = hex.trim().trim_start_matches('#');
+    if digits.len() != 6 || !digits.is_ascii() {
+        return None;
+    }
+
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#00ffff"), Some(Color::Rgb(0, 255, 255)));
+        assert_eq!(parse_hex_color("FF8000"), Some(Color::Rgb(255, 128, 0)));
+        assert_eq!(parse_hex_color("#0ff"), None);
+        assert_eq!(parse_hex_color("#gg0000"), None);
+    }
+
+    #[test]
+    fn test_theme_from_config() {
+        let config = ThemeConfig {
+            name: "Matrix".to_string(),
+            ball_color: Some("#ff0000".to_string()),
+            border_color: Some("not a color".to_string()),
+            ..ThemeConfig::default()
+        };
+        let theme = Theme::from_config(&config);
+        assert_eq!(theme.ball, Color::Rgb(255, 0, 0));
+        assert_eq!(theme.border, Theme::matrix().border);
+        assert_eq!(theme.left_paddle, Theme::matrix().left_paddle);
+
+        // Unknown theme names fall back to classic
+        let config = ThemeConfig {
+            name: "neon".to_string(),
+            ..ThemeConfig::default()
+        };
+        assert_eq!(Theme::from_config(&config), Theme::classic());
+    }
+
+    #[test]
+    fn test_paddle_colors_follow_your_side() {
+        let theme = Theme::classic();
+        assert_eq!(
+            theme.paddle_colors(Some(Player::Right)),
+            (Color::Magenta, Color::Cyan)
+        );
+        assert_eq!(
+            theme.paddle_colors(Some(Player::Left)),
+            (Color::Cyan, Color::Magenta)
+        );
+        assert_eq!(
+            theme.paddle_colors(None),
+            (theme.left_paddle, theme.right_paddle)
+        );
+    }
+}